@@ -0,0 +1,42 @@
+//! Detect the version of the compiler so that the generated code can use newer features when
+//! they are available. The procedural macro is always built by the compiler that will compile its
+//! output, so this is reliable.
+//!
+//! Each capability is exposed to the crate as a `derivative_<capability>` `cfg`. When the version
+//! can't be detected, none of them are enabled and the lowest common denominator is generated.
+
+use std::env;
+use std::process::Command;
+
+/// The capabilities and the minor version of Rust 1.x which introduced them.
+const CAPABILITIES: &[(&str, u32)] = &[
+    // `#[coverage(off)]` on `impl` blocks, on nightly compilers.
+    ("coverage_attribute", 81),
+];
+
+fn main() {
+    let minor = rustc_minor_version();
+
+    for &(capability, since) in CAPABILITIES {
+        println!("cargo:rustc-check-cfg=cfg(derivative_{})", capability);
+        if minor.is_some_and(|minor| minor >= since) {
+            println!("cargo:rustc-cfg=derivative_{}", capability);
+        }
+    }
+
+    // Set by `cargo llvm-cov` on nightly compilers, used by the tests of the `coverage` feature.
+    println!("cargo:rustc-check-cfg=cfg(coverage_nightly)");
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+
+    // eg. `rustc 1.32.0-nightly (…)`
+    let mut pieces = version.split_whitespace().nth(1)?.split('.');
+    if pieces.next()? != "1" {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
@@ -0,0 +1,336 @@
+use proc_macro2;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+use attr;
+use error::Error;
+use strict;
+use syn;
+
+#[derive(Debug)]
+pub struct Input<'a> {
+    pub attrs: attr::Input,
+    pub body: Body<'a>,
+    pub generics: &'a syn::Generics,
+    pub ident: syn::Ident,
+    pub vis: &'a syn::Visibility,
+}
+
+#[derive(Debug)]
+pub enum Body<'a> {
+    Enum(Vec<Variant<'a>>),
+    Struct(Style, Vec<Field<'a>>),
+}
+
+#[derive(Debug)]
+pub struct Variant<'a> {
+    pub attrs: attr::Input,
+    pub fields: Vec<Field<'a>>,
+    pub ident: syn::Ident,
+    pub style: Style,
+}
+
+#[derive(Debug)]
+pub struct Field<'a> {
+    pub attrs: attr::Field,
+    pub ident: Option<syn::Ident>,
+    pub ty: &'a syn::Type,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Style {
+    Struct,
+    Tuple,
+    Unit,
+}
+
+impl<'a> Input<'a> {
+    pub fn from_ast(item: &'a syn::DeriveInput) -> Result<Input<'a>, Error> {
+        Input::from_ast_with_extensions(item, "derivative", &[])
+    }
+
+    /// Parse `item`, reading its `#[<attribute>(…)]` attributes.
+    ///
+    /// In addition to the traits supported by *derivative*, the traits named in `extensions` are
+    /// accepted. Their attributes can be retrieved with `attr::Input::extension` and
+    /// `attr::Field::extension`. An extension used on a type that also derives `Derivative`
+    /// should use its own `attribute`, as *derivative* rejects traits it does not know.
+    pub fn from_ast_with_extensions(
+        item: &'a syn::DeriveInput,
+        attribute: &str,
+        extensions: &[&str],
+    ) -> Result<Input<'a>, Error> {
+        let attrs = attr::Input::from_ast_with_extensions(&item.attrs, attribute, extensions)?;
+
+        let mut body = match item.data {
+            syn::Data::Enum(syn::DataEnum { ref variants, .. }) => {
+                Body::Enum(enum_from_ast(variants, attribute, extensions)?)
+            }
+            syn::Data::Struct(syn::DataStruct { ref fields, .. }) => {
+                let (style, fields) = struct_from_ast(fields, attribute, extensions)?;
+                Body::Struct(style, fields)
+            }
+            syn::Data::Union(_) => {
+                return Err(Error::unsupported(
+                    "derivative does not support unions",
+                    item.ident.span(),
+                ));
+            }
+        };
+
+        if attrs.debug_hide_phantom() {
+            let fields: Vec<&mut Field> = match body {
+                Body::Enum(ref mut variants) => {
+                    variants.iter_mut().flat_map(|v| v.fields.iter_mut()).collect()
+                }
+                Body::Struct(_, ref mut fields) => fields.iter_mut().collect(),
+            };
+            for field in fields {
+                if is_phantom_data(field.ty) {
+                    field.attrs.ignore_debug_implicitly();
+                }
+            }
+        }
+
+        match body {
+            Body::Enum(ref mut variants) => {
+                for variant in variants {
+                    let types = attrs.ignore_types().iter().chain(variant.attrs.ignore_types());
+                    ignore_types(&mut variant.fields, &types.collect::<Vec<_>>());
+                    if variant.attrs.debug_ignore() {
+                        for field in &mut variant.fields {
+                            field.attrs.ignore_debug_implicitly();
+                        }
+                    }
+                    delegate_debug(&mut variant.fields)?;
+                }
+            }
+            Body::Struct(_, ref mut fields) => {
+                ignore_types(fields, &attrs.ignore_types().iter().collect::<Vec<_>>());
+                delegate_debug(fields)?;
+            }
+        }
+
+        if let Some(policy) = attrs.bindgen() {
+            match body {
+                Body::Enum(ref mut variants) => {
+                    for variant in variants {
+                        bindgen_fields(&mut variant.fields, policy);
+                    }
+                }
+                Body::Struct(_, ref mut fields) => bindgen_fields(fields, policy),
+            }
+        }
+
+        let input = Input {
+            attrs,
+            body,
+            generics: &item.generics,
+            ident: item.ident.clone(),
+            vis: &item.vis,
+        };
+
+        if input.attrs.strict {
+            strict::check(&input)?;
+        }
+
+        Ok(input)
+    }
+
+    /// Whether the type, one of its variants or one of its fields is `#[deprecated]`, in which
+    /// case the generated code uses deprecated items.
+    pub fn uses_deprecated(&self) -> bool {
+        let fields_deprecated = |fields: &[Field]| fields.iter().any(|f| f.attrs.deprecated());
+
+        self.attrs.deprecated
+            || match self.body {
+                Body::Enum(ref variants) => variants
+                    .iter()
+                    .any(|v| v.attrs.deprecated || fields_deprecated(&v.fields)),
+                Body::Struct(_, ref fields) => fields_deprecated(fields),
+            }
+    }
+}
+
+impl<'a> Body<'a> {
+    pub fn all_fields(&self) -> Vec<&Field<'_>> {
+        match *self {
+            Body::Enum(ref variants) => variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter())
+                .collect(),
+            Body::Struct(_, ref fields) => fields.iter().collect(),
+        }
+    }
+}
+
+impl<'a> Field<'a> {
+    /// The span of the field in the input: its name, or its type for a tuple field.
+    ///
+    /// The code generated for a single field uses it, so that the errors in that code point at
+    /// the field and IDEs navigate from it to the field.
+    pub fn span(&self) -> proc_macro2::Span {
+        match self.ident {
+            Some(ref ident) => ident.span(),
+            None => self.ty.span(),
+        }
+    }
+}
+
+fn enum_from_ast<'a>(
+    variants: &'a syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    attribute: &str,
+    extensions: &[&str],
+) -> Result<Vec<Variant<'a>>, Error> {
+    variants
+        .iter()
+        .map(|variant| {
+            let (style, fields) = struct_from_ast(&variant.fields, attribute, extensions)?;
+            Ok(Variant {
+                attrs: attr::Input::from_ast_with_extensions(&variant.attrs, attribute, extensions)?,
+                fields,
+                ident: variant.ident.clone(),
+                style,
+            })
+        })
+        .collect()
+}
+
+fn struct_from_ast<'a>(
+    fields: &'a syn::Fields,
+    attribute: &str,
+    extensions: &[&str],
+) -> Result<(Style, Vec<Field<'a>>), Error> {
+    match *fields {
+        syn::Fields::Named(ref fields) => Ok((
+            Style::Struct,
+            fields_from_ast(&fields.named, attribute, extensions)?,
+        )),
+        syn::Fields::Unnamed(ref fields) => Ok((
+            Style::Tuple,
+            fields_from_ast(&fields.unnamed, attribute, extensions)?,
+        )),
+        syn::Fields::Unit => Ok((Style::Unit, Vec::new())),
+    }
+}
+
+fn fields_from_ast<'a>(
+    fields: &'a syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+    attribute: &str,
+    extensions: &[&str],
+) -> Result<Vec<Field<'a>>, Error> {
+    fields
+        .iter()
+        .map(|field| {
+            Ok(Field {
+                attrs: attr::Field::from_ast_with_extensions(field, attribute, extensions)?,
+                ident: field.ident.clone(),
+                ty: &field.ty,
+            })
+        })
+        .collect()
+}
+
+/// Whether `ty` is `PhantomData<…>`, whatever the path to it.
+pub(crate) fn is_phantom_data(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Path(ref ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Ignore the fields whose type is one of `types`.
+fn ignore_types(fields: &mut [Field], types: &[&syn::Type]) {
+    for field in fields {
+        if types.iter().any(|ty| type_matches(ty, field.ty)) {
+            field.attrs.ignore_type();
+        }
+    }
+}
+
+/// Ignore the other fields for `Debug` if one of `fields` is marked `Debug(delegate)`, as the
+/// structure or variant is shown as that field alone.
+fn delegate_debug(fields: &mut [Field]) -> Result<(), Error> {
+    let delegates: Vec<_> = fields
+        .iter()
+        .filter(|field| field.attrs.debug_delegate())
+        .map(Field::span)
+        .collect();
+    match *delegates.as_slice() {
+        [] => return Ok(()),
+        [_] => (),
+        [_, other, ..] => {
+            return Err(Error::malformed(
+                "only one field can be marked `Debug(delegate)`",
+                other,
+            ));
+        }
+    }
+
+    for field in fields {
+        if !field.attrs.debug_delegate() {
+            field.attrs.ignore_debug_implicitly();
+        }
+    }
+    Ok(())
+}
+
+/// Handle the fields generated by bindgen with `policy`: the bitfields, whose type is
+/// `__BindgenBitfieldUnit<…>`, and the anonymous unions and structures, which are named
+/// `__bindgen_anon_N`.
+fn bindgen_fields(fields: &mut [Field], policy: attr::Bindgen) {
+    for field in fields {
+        let anonymous = field
+            .ident
+            .as_ref()
+            .is_some_and(|ident| ident.to_string().starts_with("__bindgen_anon_"));
+        let bitfield = match *field.ty {
+            syn::Type::Path(ref ty) => ty
+                .path
+                .segments
+                .iter()
+                .last()
+                .is_some_and(|segment| segment.ident == "__BindgenBitfieldUnit"),
+            _ => false,
+        };
+
+        if anonymous || bitfield {
+            field.attrs.set_bindgen(policy);
+        }
+    }
+}
+
+/// Whether the type `ty` of a field is the type `pattern` given to `ignore_types`.
+///
+/// A path matches the paths it is a suffix of, so that `PhantomData` matches
+/// `std::marker::PhantomData<T>`, and the generic arguments are only compared when `pattern`
+/// has some.
+fn type_matches(pattern: &syn::Type, ty: &syn::Type) -> bool {
+    match (pattern, ty) {
+        (syn::Type::Path(pattern), syn::Type::Path(ty))
+            if pattern.qself.is_none() && ty.qself.is_none() =>
+        {
+            let pattern = &pattern.path.segments;
+            let ty = &ty.path.segments;
+            pattern.len() <= ty.len()
+                && pattern
+                    .iter()
+                    .zip(ty.iter().skip(ty.len() - pattern.len()))
+                    .all(|(pattern, ty)| {
+                        pattern.ident == ty.ident
+                            && (pattern.arguments.is_empty()
+                                || pattern.arguments.clone().into_token_stream().to_string()
+                                    == ty.arguments.clone().into_token_stream().to_string())
+                    })
+        }
+        _ => {
+            pattern.clone().into_token_stream().to_string()
+                == ty.clone().into_token_stream().to_string()
+        }
+    }
+}
@@ -0,0 +1,2761 @@
+use std::str::FromStr;
+
+use proc_macro2;
+use quote::ToTokens;
+use syn;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+
+use case::RenameRule;
+use compat;
+use error::Error;
+
+/// Represent the `derivative` attributes on the input type (`struct`/`enum`).
+#[derive(Debug, Default)]
+pub struct Input {
+    /// Whether `Accessors` is present and its specific attributes.
+    pub accessors: Option<InputAccessors>,
+    /// Whether `AsMut` is present and its specific attributes.
+    pub as_mut: Option<InputAsMut>,
+    /// Whether `AsRef` is present and its specific attributes.
+    pub as_ref: Option<InputAsRef>,
+    /// Whether `Binary` is present and its specific attributes.
+    pub binary: Option<InputRadix>,
+    /// Whether `Borrow` is present and its specific attributes.
+    pub borrow: Option<InputBorrow>,
+    /// Whether `BorrowMut` is present and its specific attributes.
+    pub borrow_mut: Option<InputBorrowMut>,
+    /// Whether `Builder` is present and its specific attributes.
+    pub builder: Option<InputBuilder>,
+    /// Whether `Clone` is present and its specific attributes.
+    pub clone: Option<InputClone>,
+    /// Whether `Copy` is present and its specific attributes.
+    pub copy: Option<InputCopy>,
+    /// Whether `Debug` is present and its specific attributes.
+    pub debug: Option<InputDebug>,
+    /// Whether `Default` is present and its specitif attributes.
+    pub default: Option<InputDefault>,
+    /// Whether `Deref` is present and its specific attributes.
+    pub deref: Option<InputDeref>,
+    /// Whether `DerefMut` is present and its specific attributes.
+    pub deref_mut: Option<InputDerefMut>,
+    /// Whether `Drop` is present and its specific attributes.
+    pub drop: Option<InputDrop>,
+    /// Whether `Eq` is present and its specitif attributes.
+    pub eq: Option<InputEq>,
+    /// Whether `Extend` is present and its specific attributes.
+    pub extend: Option<InputIter>,
+    /// Whether `From` is present and its specific attributes.
+    pub from: Option<InputFrom>,
+    /// Whether `FromIterator` is present and its specific attributes.
+    pub from_iterator: Option<InputIter>,
+    /// Whether `FromStr` is present and its specific attributes.
+    pub from_str: Option<InputFromStr>,
+    /// Whether `Hash` is present and its specific attributes.
+    pub hash: Option<InputHash>,
+    /// Whether `Index` is present and its specific attributes.
+    pub index: Option<InputIndex>,
+    /// Whether `IndexMut` is present and its specific attributes.
+    pub index_mut: Option<InputIndex>,
+    /// Whether `LowerHex` is present and its specific attributes.
+    pub lower_hex: Option<InputRadix>,
+    /// Whether `Octal` is present and its specific attributes.
+    pub octal: Option<InputRadix>,
+    /// Whether `Ord` is present and its specific attributes.
+    pub ord: Option<InputOrd>,
+    /// Whether `Eq` is present and its specitif attributes.
+    pub partial_eq: Option<InputPartialEq>,
+    /// Whether `PartialOrd` is present and its specific attributes.
+    pub partial_ord: Option<InputPartialOrd>,
+    /// Whether `Read` is present and its specific attributes.
+    pub read: Option<InputIo>,
+    /// Whether `TryFrom` is present and its specific attributes.
+    pub try_from: Option<InputTryFrom>,
+    /// Whether `UpperHex` is present and its specific attributes.
+    pub upper_hex: Option<InputRadix>,
+    /// Whether `VariantNames` is present and its specific attributes.
+    pub variant_names: Option<InputVariantNames>,
+    /// Whether `Write` is present and its specific attributes.
+    pub write: Option<InputIo>,
+    /// The extension traits that are present and their specific attributes.
+    pub extensions: Vec<InputExtension>,
+    /// Whether the type is marked `strict`.
+    pub strict: bool,
+    /// Whether a `#[derive(Copy)]` attribute follows the `derivative` derive.
+    pub derives_copy: bool,
+    /// The integer type given to `#[repr(…)]`, if any.
+    pub repr: Option<syn::Ident>,
+    /// Whether the type is `#[repr(packed)]`, so that its fields can't be borrowed.
+    pub packed: bool,
+    /// Whether the type, or the variant, is `#[deprecated]`.
+    pub deprecated: bool,
+    /// The types given to `ignore_types`, whose fields are ignored by `Debug`, `Hash`,
+    /// `PartialEq`, `PartialOrd` and `Ord`.
+    ignore_types: Vec<syn::Type>,
+    /// The `bindgen` attribute if present and how the fields generated by bindgen are handled.
+    bindgen: Option<Bindgen>,
+    /// The case convention of the variant names, from `rename_all="…"`.
+    rename_all: Option<RenameRule>,
+    /// The foreign type this type mirrors, from `remote="…"`.
+    remote: Option<syn::Path>,
+    /// The names used in the attributes, in order.
+    traits: Vec<syn::Ident>,
+}
+
+/// How the fields that bindgen generates for bitfields and anonymous unions and structures are
+/// handled, from the `bindgen` attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bindgen {
+    /// The fields are shown as `..` by `Debug`, and ignored by `Hash`, `PartialEq`, `PartialOrd`
+    /// and `Ord`.
+    Skip,
+    /// The fields are shown, hashed and compared as their bytes.
+    Opaque,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative` attributes on a field.
+pub struct Field {
+    /// The parameters for `AsMut`.
+    as_mut: FieldAsRef,
+    /// The parameters for `AsRef`.
+    as_ref: FieldAsRef,
+    /// Whether the field is left out of the builder, from `Builder="ignore"`.
+    ignore_builder: bool,
+    /// The parameters for `Clone`.
+    clone: FieldClone,
+    /// The parameters for `Copy`.
+    copy_bound: Option<Vec<syn::WherePredicate>>,
+    /// The parameters for `Debug`.
+    debug: FieldDebug,
+    /// The parameters for `Default`.
+    default: FieldDefault,
+    /// Whether the field is the target of `Deref` and `DerefMut`, from `#[derivative(Deref)]`.
+    deref_target: bool,
+    /// The function called on the field when the value is dropped, from `Drop(with="…")`.
+    drop_with: Option<syn::Path>,
+    /// The parameters for `Eq`.
+    eq_bound: Option<Vec<syn::WherePredicate>>,
+    /// The parameters for `Hash`.
+    hash: FieldHash,
+    /// Whether the field is the target of `Extend`, from `Extend="target"`.
+    extend_target: bool,
+    /// Whether the field is the target of `Index` and `IndexMut`, from `Index="target"`.
+    index_target: bool,
+    /// The parameters for `Ord`.
+    ord: FieldOrd,
+    /// The parameters for `Eq`.
+    partial_eq: FieldPartialEq,
+    /// The parameters for `PartialOrd`.
+    partial_ord: FieldPartialOrd,
+    /// Whether the field is the target of `Read`, from `Read="target"`.
+    read_target: bool,
+    /// Whether the field is the target of `Write`, from `Write="target"`.
+    write_target: bool,
+    /// The parameters for the extension traits.
+    extensions: Vec<FieldExtension>,
+    /// The `bound_all` attribute if present and the corresponding bounds.
+    bound_all: Option<Vec<syn::WherePredicate>>,
+    /// How the field is handled if bindgen generated it, from the `bindgen` attribute of the
+    /// type.
+    bindgen: Option<Bindgen>,
+    /// Whether the field is `#[deprecated]`.
+    deprecated: bool,
+    /// The names used in the attributes, in order.
+    traits: Vec<syn::Ident>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Accessors(…))` attributes on an input or a variant.
+pub struct InputAccessors {
+    /// The `rename` attribute of a variant if present and the name used in its accessors.
+    rename: Option<syn::Ident>,
+    /// Whether the variant is marked with `skip`, to generate no accessor for it.
+    skip: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(AsMut(…))` attributes on an input.
+pub struct InputAsMut {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(AsRef(…))` attributes on an input.
+pub struct InputAsRef {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Borrow(…))` attributes on an input.
+pub struct InputBorrow {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(BorrowMut(…))` attributes on an input.
+pub struct InputBorrowMut {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Clone(…))` attributes on an input.
+pub struct InputClone {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the implementation should have an explicit `clone_from`.
+    pub clone_from: bool,
+    /// The `clone_with` attribute if present and the path to the function cloning the value.
+    clone_with: Option<syn::Path>,
+    /// The `is_copy` attribute if present and its value.
+    is_copy: Option<bool>,
+    /// Whether the implementation is a `const` one.
+    const_impl: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Clone(…))` attributes on an input.
+pub struct InputCopy {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether `Clone` is implemented too, by copying the value.
+    and_clone: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Debug(…))` attributes on an input.
+pub struct InputDebug {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the type or variant is marked `transparent`, or a variant is marked
+    /// `transparent="false"`.
+    pub transparent: Option<bool>,
+    /// Whether the variant is marked `ignore`.
+    pub ignore: bool,
+    /// Whether the type is marked `flattenable`.
+    pub flattenable: bool,
+    /// The `format_with` attribute if present and the path to the formatting function.
+    format_with: Option<syn::Path>,
+    /// Whether the type is marked `hide_phantom`, to ignore its `PhantomData` fields.
+    pub hide_phantom: bool,
+    /// The `max_depth` attribute if present and the number of nested values of the type shown.
+    pub max_depth: Option<usize>,
+    /// Whether the type is marked `skip_if_default`.
+    pub skip_if_default: bool,
+    /// Whether the type is marked `skip_if_none`.
+    pub skip_if_none: bool,
+    /// Whether the type is marked `verbose_debug`.
+    pub verbose: bool,
+    /// Whether the type is marked `non_exhaustive_hint`.
+    pub non_exhaustive_hint: bool,
+    /// Whether the type is marked `qualified`, to show its name with its module path.
+    pub qualified: bool,
+    /// The case convention of the field names, from `rename_all="…"`.
+    rename_all: Option<RenameRule>,
+    /// Whether the type is marked `with_discriminant`.
+    pub with_discriminant: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Default(…))` attributes on an input.
+pub struct InputDefault {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the type is marked with `new`.
+    pub new: bool,
+    /// Whether the type is marked with `reset`.
+    pub reset: bool,
+    /// Whether the type is marked with `value_fns`.
+    pub value_fns: bool,
+    /// Whether the type is marked with `const`, to generate a `DEFAULT` associated constant.
+    pub const_value: bool,
+    /// Whether the variant is marked with `fallback`, to only be the default when no other
+    /// variant is.
+    pub fallback: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Binary(…))`, `derivative(LowerHex(…))`, `derivative(Octal(…))` or
+/// `derivative(UpperHex(…))` attributes on an input.
+pub struct InputRadix {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+impl InputRadix {
+    pub fn bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.bounds.as_deref()
+    }
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Read(…))` or `derivative(Write(…))` attributes on an input.
+pub struct InputIo {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Deref(…))` attributes on an input.
+pub struct InputDeref {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(DerefMut(…))` attributes on an input.
+pub struct InputDerefMut {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Builder(…))` attributes on an input.
+pub struct InputBuilder {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Drop(…))` attributes on an input.
+pub struct InputDrop {
+    /// The `with` attribute if present and the function called with `&mut self`.
+    with: Option<syn::Path>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Eq(…))` attributes on an input.
+pub struct InputEq {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(From(…))` attributes on an input.
+pub struct InputFrom {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the type is marked with `into`, to also convert it into its field.
+    pub into: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(FromIterator(…))` or `derivative(Extend(…))` attributes on an input.
+pub struct InputIter {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `item` attribute if present and the only item type.
+    item: Option<syn::Type>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(FromStr(…))` attributes on an input or a variant.
+pub struct InputFromStr {
+    /// The `error` attribute of the type if present and the error type.
+    error: Option<syn::Type>,
+    /// The `rename` attribute of a variant if present and the string it is parsed from.
+    rename: Option<String>,
+    /// The `alias` attributes of a variant, the other strings it is parsed from.
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Hash(…))` attributes on an input.
+pub struct InputHash {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the type is marked with `content_hash`.
+    pub content_hash: bool,
+    /// The `content_hash_seed` attribute if present and the seed expression.
+    pub content_hash_seed: Option<syn::Expr>,
+    /// Whether the type is marked with `type_tag`.
+    pub type_tag: bool,
+    /// The value given to `type_tag` if present.
+    pub type_tag_value: Option<syn::Expr>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Index(…))` or `derivative(IndexMut(…))` attributes on an input.
+pub struct InputIndex {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `index` attribute if present and the only index type.
+    index: Option<syn::Type>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Ord(…))` attributes on an input.
+pub struct InputOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Allow `derivative(Ord)` on enums.
+    on_enum: bool,
+    /// The `order` attribute of a variant if present, its rank among the variants.
+    order: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(PartialEq(…))` attributes on an input.
+pub struct InputPartialEq {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Allow `derivative(PartialEq)` on enums:
+    on_enum: bool,
+    /// Whether the implementation is a `const` one.
+    const_impl: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(PartialOrd(…))` attributes on an input.
+pub struct InputPartialOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Allow `derivative(PartialOrd)` on enums.
+    on_enum: bool,
+    /// The `order` attribute of a variant if present, its rank among the variants.
+    order: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(TryFrom(…))` attributes on an input.
+pub struct InputTryFrom {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `error` attribute if present and the error type.
+    error: Option<syn::Type>,
+    /// The `validate_with` attribute if present and the path of the validation function.
+    validate_with: Option<syn::Path>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(VariantNames(…))` attributes on an input or a variant.
+pub struct InputVariantNames {
+    /// The `rename` attribute of a variant if present and its name.
+    rename: Option<String>,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(AsRef(…))` or `derivative(AsMut(…))` attributes on a field.
+pub struct FieldAsRef {
+    /// Whether the field is marked, to implement the trait for its type.
+    marked: bool,
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+}
+
+#[derive(Debug, Default)]
+/// Represents the `derivarive(Clone(…))` attributes on a field.
+pub struct FieldClone {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `clone_with` attribute if present and the path to the clonning function.
+    clone_with: Option<syn::Path>,
+    /// The `clone_from_with` attribute if present and the path to the in-place cloning function.
+    clone_from_with: Option<syn::Path>,
+    /// Whether the field gets its default value instead of being cloned.
+    ignore: bool,
+    /// Whether the field is a shared pointer, cloned without cloning what it points to.
+    shared: bool,
+    /// Whether the field is a box, cloned by cloning what it points to with a clone-box function.
+    boxed: bool,
+    /// The function given to `boxed` if any, instead of `dyn_clone::clone_box`.
+    clone_box: Option<syn::Path>,
+}
+
+#[derive(Debug, Default)]
+/// Represents the `derivarive(Debug(…))` attributes on a field.
+pub struct FieldDebug {
+    /// Whether the field is shown as the address it points to.
+    as_pointer: bool,
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// Whether the structure or variant is shown as this field alone.
+    delegate: bool,
+    /// Whether the fields of the field are shown as fields of its parent.
+    flatten: bool,
+    /// The `format` attribute if present and the format string of the field.
+    format: Option<syn::LitStr>,
+    /// The `format_with` attribute if present and the path to the formatting function.
+    format_with: Option<syn::Path>,
+    /// Whether the field is to be ignored from output.
+    ignore: bool,
+    /// The `mask` attribute if present and the string shown instead of the field.
+    mask: Option<String>,
+    /// The `max_len` attribute if present and the number of elements shown.
+    max_len: Option<usize>,
+    /// The `name` attribute if present and the label of the tuple field.
+    name: Option<String>,
+    /// Whether the `format_with` function also gets the name of the field.
+    pass_name: bool,
+    /// The `rename` attribute if present and the label of the named field.
+    rename: Option<String>,
+    /// Whether the field is left out when it is equal to its default value.
+    skip_if_default: bool,
+    /// Whether the field is left out when it is `None`.
+    skip_if_none: bool,
+    /// Whether the entries of the field, a map or a set, are sorted before being shown.
+    sort_maps: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivarive(Default(…))` attributes on a field.
+pub struct FieldDefault {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The default value for the field if present.
+    pub value: Option<syn::Expr>,
+    /// The `value(<cfg>="…")` attribute if present and the value for each configuration.
+    value_cfg: Vec<(syn::Meta, syn::Expr)>,
+    /// The `value_env` attribute if present and the name of the environment variable.
+    value_env: Option<String>,
+    /// The `env_fallback` attribute if present and the value to use when the variable is unset.
+    env_fallback: Option<String>,
+    /// The `capacity` attribute if present and the capacity to create the field with.
+    capacity: Option<syn::Expr>,
+    /// Whether the field is a parameter of the `new` function instead of having a default value.
+    required: bool,
+    /// Whether the field has no default value because its type can't implement `Default`, which
+    /// makes it required too.
+    unavailable: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represents the `derivarive(Hash(…))` attributes on a field.
+pub struct FieldHash {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `hash_with` attribute if present and the path to the hashing function.
+    hash_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when hashing.
+    ignore: bool,
+    /// Whether the `Deref` target of the field is hashed instead of the field.
+    deref: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(Ord(…))` attributes on a field.
+pub struct FieldOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `cmp_with` attribute if present and the path to the comparison function.
+    cmp_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when comparing.
+    ignore: bool,
+    /// Whether the field is compared in descending order.
+    reverse: bool,
+    /// The `priority` attribute, the fields with a higher priority are compared first.
+    priority: i32,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivarive(PartialEq(…))` attributes on a field.
+pub struct FieldPartialEq {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `compare_with` attribute if present and the path to the comparison function.
+    compare_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when comparing.
+    ignore: bool,
+    /// Whether the `Deref` target of the field is compared instead of the field.
+    deref: bool,
+}
+
+#[derive(Debug, Default)]
+/// Represent the `derivative(PartialOrd(…))` attributes on a field.
+pub struct FieldPartialOrd {
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `partial_cmp_with` attribute if present and the path to the comparison function.
+    partial_cmp_with: Option<syn::Path>,
+    /// Whether the field is to be ignored when comparing.
+    ignore: bool,
+    /// Whether the field is compared in descending order.
+    reverse: bool,
+    /// The `priority` attribute, the fields with a higher priority are compared first.
+    priority: i32,
+}
+
+#[derive(Debug)]
+/// Represent the `derivative(<Extension>(…))` attributes on an input.
+pub struct InputExtension {
+    /// The name of the extension trait.
+    name: String,
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `epsilon_type` attribute if present and the type of the tolerances, for the
+    /// approximate comparisons of the `approx` feature.
+    epsilon_type: Option<syn::Type>,
+}
+
+#[derive(Debug)]
+/// Represent the `derivative(<Extension>(…))` attributes on a field.
+pub struct FieldExtension {
+    /// The name of the extension trait.
+    name: String,
+    /// The `bound` attribute if present and the corresponding bounds.
+    bounds: Option<Vec<syn::WherePredicate>>,
+    /// The `with` or `<anything>_with` attribute if present and the path to the function.
+    with: Option<syn::Path>,
+    /// Whether the field is to be ignored by the extension.
+    ignore: bool,
+    /// The `epsilon` attribute if present and the tolerance the field is compared with, for the
+    /// approximate comparisons of the `approx` feature.
+    epsilon: Option<syn::Expr>,
+}
+
+macro_rules! for_all_attr {
+    (for ($name:ident, $value:ident) in $attrs:expr, $attribute:expr => $traits:expr; $($body:tt)*) => {
+        for meta_items in $attrs.iter().filter_map(|attr| derivative_attribute(attr, $attribute)) {
+            for metaitem in meta_items?.iter().map(read_items) {
+                let MetaItem($name, $value) = metaitem?;
+                $traits.push($name.clone());
+                match $name.to_string().as_ref() {
+                    $($body)*
+                    _ => return Err(Error::unknown_trait($name)),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! match_attributes {
+    (let Some($name:ident) = $unwraped:expr; for $value:ident in $values:expr; $($body:tt)* ) => {
+        let mut $name = $unwraped.take().unwrap_or_default();
+
+        match_attributes! {
+            for $value in $values;
+            $($body)*
+        }
+
+        $unwraped = Some($name);
+    };
+
+    (for $value:ident in $values:expr; $($body:tt)* ) => {
+        for $value in $values {
+            match $value.name {
+                Some(ident) => {
+                    match ident.to_string().as_ref() {
+                        $($body)*
+                        name => return Err(Error::unknown_attribute(name, ident.span())),
+                    }
+                }
+                None => {
+                    match $value.value.as_ref().map(|v| v.as_ref()).expect("Expected value to be passed") {
+                        $($body)*
+                        name => return Err(Error::unknown_attribute(name, $value.span)),
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl Input {
+    /// Parse the `derivative` attributes on a type.
+    pub fn from_ast(attrs: &[syn::Attribute]) -> Result<Input, Error> {
+        Input::from_ast_with_extensions(attrs, "derivative", &[])
+    }
+
+    /// Parse the `attribute` attributes on a type, also accepting the given extension traits.
+    pub fn from_ast_with_extensions(
+        attrs: &[syn::Attribute],
+        attribute: &str,
+        extensions: &[&str],
+    ) -> Result<Input, Error> {
+        let mut input = Input {
+            derives_copy: attrs.iter().any(derives_copy),
+            repr: attrs.iter().find_map(repr_int),
+            packed: attrs.iter().any(is_repr_packed),
+            deprecated: attrs.iter().any(is_deprecated),
+            ..Input::default()
+        };
+
+        for_all_attr! {
+            for (name, values) in attrs, attribute => input.traits;
+            "strict" => {
+                input.strict = match values.as_slice() {
+                    [] => true,
+                    [value] if value.name.is_none() => parse_boolean_meta_item(value, true, "strict")?,
+                    _ => return Err(Error::malformed("Expected `strict` or `strict=\"true\"`", name.span())),
+                };
+            }
+            "ignore_types" => {
+                for value in values {
+                    parse_ignore_types(&mut input.ignore_types, &value)?;
+                }
+            }
+            "bindgen" => {
+                input.bindgen = Some(match values.as_slice() {
+                    [value] if value.name.is_none() => match value.as_str() {
+                        Some("skip") => Bindgen::Skip,
+                        Some("opaque") => Bindgen::Opaque,
+                        Some(other) => return Err(Error::invalid_value("bindgen", other, value.span)),
+                        None => return Err(Error::missing_value("bindgen", name.span())),
+                    },
+                    _ => return Err(Error::malformed("Expected `bindgen=\"skip\"` or `bindgen=\"opaque\"`", name.span())),
+                });
+            }
+            "rename_all" => {
+                input.rename_all = Some(match values.as_slice() {
+                    [value] if value.name.is_none() => match value.as_str() {
+                        Some(case) => match RenameRule::from_name(case) {
+                            Some(rule) => rule,
+                            None => return Err(Error::invalid_value("rename_all", case, value.span)),
+                        },
+                        None => return Err(Error::missing_value("rename_all", name.span())),
+                    },
+                    _ => return Err(Error::malformed("Expected `rename_all=\"…\"`", name.span())),
+                });
+            }
+            "remote" => {
+                input.remote = Some(match values.as_slice() {
+                    [value] if value.name.is_none() => parse_value(value, "remote")?,
+                    _ => return Err(Error::malformed("Expected `remote=\"…\"`", name.span())),
+                });
+            }
+            "Accessors" => {
+                match_attributes! {
+                    let Some(accessors) = input.accessors;
+                    for value in values;
+                    "rename" => {
+                        accessors.rename = Some(parse_value(&value, "rename")?);
+                    }
+                    "skip" => {
+                        accessors.skip = parse_boolean_meta_item(&value, true, "skip")?;
+                    }
+                }
+            }
+            "AsMut" => {
+                match_attributes! {
+                    let Some(as_mut) = input.as_mut;
+                    for value in values;
+                    "bound" => parse_bound(&mut as_mut.bounds, &value)?,
+                }
+            }
+            "AsRef" => {
+                match_attributes! {
+                    let Some(as_ref) = input.as_ref;
+                    for value in values;
+                    "bound" => parse_bound(&mut as_ref.bounds, &value)?,
+                }
+            }
+            "Binary" => {
+                match_attributes! {
+                    let Some(binary) = input.binary;
+                    for value in values;
+                    "bound" => parse_bound(&mut binary.bounds, &value)?,
+                }
+            }
+            "Borrow" => {
+                match_attributes! {
+                    let Some(borrow) = input.borrow;
+                    for value in values;
+                    "bound" => parse_bound(&mut borrow.bounds, &value)?,
+                }
+            }
+            "BorrowMut" => {
+                match_attributes! {
+                    let Some(borrow_mut) = input.borrow_mut;
+                    for value in values;
+                    "bound" => parse_bound(&mut borrow_mut.bounds, &value)?,
+                }
+            }
+            "Builder" => {
+                match_attributes! {
+                    let Some(builder) = input.builder;
+                    for value in values;
+                    "bound" => parse_bound(&mut builder.bounds, &value)?,
+                }
+            }
+            "Clone" => {
+                match_attributes! {
+                    let Some(clone) = input.clone;
+                    for value in values;
+                    "bound" => parse_bound(&mut clone.bounds, &value)?,
+                    "clone_from" => {
+                        clone.clone_from = parse_boolean_meta_item(&value, true, "clone_from")?;
+                    }
+                    "clone_with" => clone.clone_with = Some(parse_value(&value, "clone_with")?),
+                    "is_copy" => {
+                        clone.is_copy = Some(parse_boolean_meta_item(&value, true, "is_copy")?);
+                    }
+                    "const" => {
+                        clone.const_impl = parse_boolean_meta_item(&value, true, "const")?;
+                    }
+                }
+            }
+            "Copy" => {
+                match_attributes! {
+                    let Some(copy) = input.copy;
+                    for value in values;
+                    "and_clone" => {
+                        copy.and_clone = parse_boolean_meta_item(&value, true, "and_clone")?;
+                    }
+                    "bound" => parse_bound(&mut copy.bounds, &value)?,
+                }
+            }
+            "Debug" => {
+                match_attributes! {
+                    let Some(debug) = input.debug;
+                    for value in values;
+                    "bound" => parse_bound(&mut debug.bounds, &value)?,
+                    "ignore" => {
+                        debug.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "flattenable" => {
+                        debug.flattenable = parse_boolean_meta_item(&value, true, "flattenable")?;
+                    }
+                    "format_with" => debug.format_with = Some(parse_value(&value, "format_with")?),
+                    "hide_phantom" => {
+                        debug.hide_phantom = parse_boolean_meta_item(&value, true, "hide_phantom")?;
+                    }
+                    "max_depth" => debug.max_depth = Some(parse_number(&value, "max_depth")?),
+                    "skip_if_default" => {
+                        debug.skip_if_default = parse_boolean_meta_item(&value, true, "skip_if_default")?;
+                    }
+                    "skip_if_none" => {
+                        debug.skip_if_none = parse_boolean_meta_item(&value, true, "skip_if_none")?;
+                    }
+                    "transparent" => {
+                        debug.transparent = Some(parse_boolean_meta_item(&value, true, "transparent")?);
+                    }
+                    "verbose_debug" => {
+                        debug.verbose = parse_boolean_meta_item(&value, true, "verbose_debug")?;
+                    }
+                    "non_exhaustive_hint" => {
+                        debug.non_exhaustive_hint = parse_boolean_meta_item(&value, true, "non_exhaustive_hint")?;
+                    }
+                    "qualified" => {
+                        debug.qualified = parse_boolean_meta_item(&value, true, "qualified")?;
+                    }
+                    "rename_all" => debug.rename_all = Some(parse_rename_rule(&value, "rename_all")?),
+                    "with_discriminant" => {
+                        debug.with_discriminant = parse_boolean_meta_item(&value, true, "with_discriminant")?;
+                    }
+                }
+            }
+            "Default" => {
+                match_attributes! {
+                    let Some(default) = input.default;
+                    for value in values;
+                    "bound" => parse_bound(&mut default.bounds, &value)?,
+                    "new" => {
+                        default.new = parse_boolean_meta_item(&value, true, "new")?;
+                    }
+                    "reset" => {
+                        default.reset = parse_boolean_meta_item(&value, true, "reset")?;
+                    }
+                    "value_fns" => {
+                        default.value_fns = parse_boolean_meta_item(&value, true, "value_fns")?;
+                    }
+                    "const" => {
+                        default.const_value = parse_boolean_meta_item(&value, true, "const")?;
+                    }
+                    "fallback" => {
+                        default.fallback = parse_boolean_meta_item(&value, true, "fallback")?;
+                    }
+                }
+            }
+            "Deref" => {
+                match_attributes! {
+                    let Some(deref) = input.deref;
+                    for value in values;
+                    "bound" => parse_bound(&mut deref.bounds, &value)?,
+                }
+            }
+            "DerefMut" => {
+                match_attributes! {
+                    let Some(deref_mut) = input.deref_mut;
+                    for value in values;
+                    "bound" => parse_bound(&mut deref_mut.bounds, &value)?,
+                }
+            }
+            "Drop" => {
+                match_attributes! {
+                    let Some(drop) = input.drop;
+                    for value in values;
+                    "with" => {
+                        drop.with = Some(parse_value(&value, "with")?);
+                    }
+                }
+            }
+            "Eq" => {
+                match_attributes! {
+                    let Some(eq) = input.eq;
+                    for value in values;
+                    "bound" => parse_bound(&mut eq.bounds, &value)?,
+                }
+            }
+            "Extend" => {
+                match_attributes! {
+                    let Some(extend) = input.extend;
+                    for value in values;
+                    "bound" => parse_bound(&mut extend.bounds, &value)?,
+                    "item" => {
+                        extend.item = Some(parse_value(&value, "item")?);
+                    }
+                }
+            }
+            "From" => {
+                match_attributes! {
+                    let Some(from) = input.from;
+                    for value in values;
+                    "bound" => parse_bound(&mut from.bounds, &value)?,
+                    "into" => {
+                        from.into = parse_boolean_meta_item(&value, true, "into")?;
+                    }
+                }
+            }
+            "FromIterator" => {
+                match_attributes! {
+                    let Some(from_iterator) = input.from_iterator;
+                    for value in values;
+                    "bound" => parse_bound(&mut from_iterator.bounds, &value)?,
+                    "item" => {
+                        from_iterator.item = Some(parse_value(&value, "item")?);
+                    }
+                }
+            }
+            "FromStr" => {
+                match_attributes! {
+                    let Some(from_str) = input.from_str;
+                    for value in values;
+                    "error" => {
+                        from_str.error = Some(parse_value(&value, "error")?);
+                    }
+                    "rename" => {
+                        let rename = value.as_str().ok_or_else(|| Error::missing_value("rename", value.span))?;
+                        from_str.rename = Some(rename.to_string());
+                    }
+                    "alias" => {
+                        let alias = value.as_str().ok_or_else(|| Error::missing_value("alias", value.span))?;
+                        from_str.aliases.push(alias.to_string());
+                    }
+                }
+            }
+            "Hash" => {
+                match_attributes! {
+                    let Some(hash) = input.hash;
+                    for value in values;
+                    "bound" => parse_bound(&mut hash.bounds, &value)?,
+                    "content_hash" => {
+                        hash.content_hash = parse_boolean_meta_item(&value, true, "content_hash")?;
+                    }
+                    "content_hash_seed" => {
+                        hash.content_hash_seed = Some(parse_value(&value, "content_hash_seed")?);
+                        hash.content_hash = true;
+                    }
+                    "type_tag" => {
+                        if value.as_str().is_some() {
+                            hash.type_tag_value = Some(parse_value(&value, "type_tag")?);
+                        }
+                        hash.type_tag = true;
+                    }
+                }
+            }
+            "eq_hash" => {
+                expand_group(&mut input.traits, EQ_HASH);
+                let mut partial_eq = input.partial_eq.take().unwrap_or_default();
+                let mut eq = input.eq.take().unwrap_or_default();
+                let mut hash = input.hash.take().unwrap_or_default();
+
+                match_attributes! {
+                    for value in values;
+                    "bound" => {
+                        parse_bound(&mut partial_eq.bounds, &value)?;
+                        parse_bound(&mut eq.bounds, &value)?;
+                        parse_bound(&mut hash.bounds, &value)?;
+                    }
+                    "feature_allow_slow_enum" => {
+                        partial_eq.on_enum = parse_boolean_meta_item(&value, true, "feature_allow_slow_enum")?;
+                    }
+                }
+
+                input.partial_eq = Some(partial_eq);
+                input.eq = Some(eq);
+                input.hash = Some(hash);
+            }
+            "PartialEq" => {
+                match_attributes! {
+                    let Some(partial_eq) = input.partial_eq;
+                    for value in values;
+                    "bound" => parse_bound(&mut partial_eq.bounds, &value)?,
+                    "feature_allow_slow_enum" => {
+                        partial_eq.on_enum = parse_boolean_meta_item(&value, true, "feature_allow_slow_enum")?;
+                    }
+                    "const" => {
+                        partial_eq.const_impl = parse_boolean_meta_item(&value, true, "const")?;
+                    }
+                }
+            }
+            "Index" => {
+                match_attributes! {
+                    let Some(index) = input.index;
+                    for value in values;
+                    "bound" => parse_bound(&mut index.bounds, &value)?,
+                    "index" => {
+                        index.index = Some(parse_value(&value, "index")?);
+                    }
+                }
+            }
+            "IndexMut" => {
+                match_attributes! {
+                    let Some(index_mut) = input.index_mut;
+                    for value in values;
+                    "bound" => parse_bound(&mut index_mut.bounds, &value)?,
+                    "index" => {
+                        index_mut.index = Some(parse_value(&value, "index")?);
+                    }
+                }
+            }
+            "LowerHex" => {
+                match_attributes! {
+                    let Some(lower_hex) = input.lower_hex;
+                    for value in values;
+                    "bound" => parse_bound(&mut lower_hex.bounds, &value)?,
+                }
+            }
+            "Octal" => {
+                match_attributes! {
+                    let Some(octal) = input.octal;
+                    for value in values;
+                    "bound" => parse_bound(&mut octal.bounds, &value)?,
+                }
+            }
+            "Ord" => {
+                match_attributes! {
+                    let Some(ord) = input.ord;
+                    for value in values;
+                    "bound" => parse_bound(&mut ord.bounds, &value)?,
+                    "feature_allow_slow_enum" => {
+                        ord.on_enum = parse_boolean_meta_item(&value, true, "feature_allow_slow_enum")?;
+                    }
+                    "order" => {
+                        ord.order = Some(parse_number(&value, "order")?);
+                    }
+                }
+            }
+            "PartialOrd" => {
+                match_attributes! {
+                    let Some(partial_ord) = input.partial_ord;
+                    for value in values;
+                    "bound" => parse_bound(&mut partial_ord.bounds, &value)?,
+                    "feature_allow_slow_enum" => {
+                        partial_ord.on_enum = parse_boolean_meta_item(&value, true, "feature_allow_slow_enum")?;
+                    }
+                    "order" => {
+                        partial_ord.order = Some(parse_number(&value, "order")?);
+                    }
+                }
+            }
+            "Read" => {
+                match_attributes! {
+                    let Some(read) = input.read;
+                    for value in values;
+                    "bound" => parse_bound(&mut read.bounds, &value)?,
+                }
+            }
+            "TryFrom" => {
+                match_attributes! {
+                    let Some(try_from) = input.try_from;
+                    for value in values;
+                    "bound" => parse_bound(&mut try_from.bounds, &value)?,
+                    "error" => {
+                        try_from.error = Some(parse_value(&value, "error")?);
+                    }
+                    "validate_with" => {
+                        try_from.validate_with = Some(parse_value(&value, "validate_with")?);
+                    }
+                }
+            }
+            "UpperHex" => {
+                match_attributes! {
+                    let Some(upper_hex) = input.upper_hex;
+                    for value in values;
+                    "bound" => parse_bound(&mut upper_hex.bounds, &value)?,
+                }
+            }
+            "VariantNames" => {
+                match_attributes! {
+                    let Some(variant_names) = input.variant_names;
+                    for value in values;
+                    "rename" => {
+                        let rename = value.as_str().ok_or_else(|| Error::missing_value("rename", value.span))?;
+                        variant_names.rename = Some(rename.to_string());
+                    }
+                }
+            }
+            "Write" => {
+                match_attributes! {
+                    let Some(write) = input.write;
+                    for value in values;
+                    "bound" => parse_bound(&mut write.bounds, &value)?,
+                }
+            }
+            extension if extensions.contains(&extension) => {
+                let mut extension = InputExtension {
+                    name: extension.to_string(),
+                    bounds: None,
+                    epsilon_type: None,
+                };
+
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut extension.bounds, &value)?,
+                    "epsilon_type" => {
+                        extension.epsilon_type = Some(parse_value(&value, "epsilon_type")?);
+                    }
+                }
+
+                input.extensions.push(extension);
+            }
+        }
+
+        Ok(input)
+    }
+
+    /// Return the attributes of the extension trait `name` if it is present.
+    pub fn extension(&self, name: &str) -> Option<&InputExtension> {
+        self.extensions.iter().find(|extension| extension.name == name)
+    }
+
+    /// The names used in the attributes (eg. `Debug` or `strict`), in order.
+    pub fn traits(&self) -> &[syn::Ident] {
+        &self.traits
+    }
+
+    /// The types whose fields are ignored by `Debug`, `Hash`, `PartialEq`, `PartialOrd` and
+    /// `Ord`.
+    pub fn ignore_types(&self) -> &[syn::Type] {
+        &self.ignore_types
+    }
+
+    /// How the fields generated by bindgen are handled, from `bindgen="…"`.
+    pub fn bindgen(&self) -> Option<Bindgen> {
+        self.bindgen
+    }
+
+    /// The case convention of the variant names, from `rename_all="…"`.
+    pub fn rename_all(&self) -> Option<RenameRule> {
+        self.rename_all
+    }
+
+    /// The foreign type whose implementations are generated as functions, from `remote="…"`.
+    pub fn remote(&self) -> Option<&syn::Path> {
+        self.remote.as_ref()
+    }
+
+    /// The name used in the accessors of a variant instead of its name in `snake_case`, from
+    /// `Accessors(rename="…")`.
+    pub fn accessors_rename(&self) -> Option<&syn::Ident> {
+        self.accessors.as_ref().and_then(|d| d.rename.as_ref())
+    }
+
+    /// Whether a variant has no accessors, from `Accessors="skip"`.
+    pub fn accessors_skip(&self) -> bool {
+        self.accessors.as_ref().is_some_and(|d| d.skip)
+    }
+
+    pub fn as_mut_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.as_mut
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn as_ref_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.as_ref
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn binary_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.binary
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn borrow_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.borrow
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn builder_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.builder
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn borrow_mut_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.borrow_mut
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn clone_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.clone
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn clone_from(&self) -> bool {
+        self.clone.as_ref().is_some_and(|d| d.clone_from)
+    }
+
+    /// The function cloning the whole value, from `Clone(clone_with="…")` on the type.
+    pub fn clone_with(&self) -> Option<&syn::Path> {
+        self.clone.as_ref().and_then(|d| d.clone_with.as_ref())
+    }
+
+    /// Whether the type was explicitly marked as `Copy` or not with `Clone(is_copy)`.
+    pub fn clone_is_copy(&self) -> Option<bool> {
+        self.clone.as_ref().and_then(|d| d.is_copy)
+    }
+
+    /// Whether `Clone` is implemented with `impl const`, from `Clone="const"`.
+    pub fn clone_const(&self) -> bool {
+        self.clone.as_ref().is_some_and(|d| d.const_impl)
+    }
+
+    pub fn copy_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.copy
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// Whether `Clone` is implemented along `Copy`, from `Copy(and_clone)`.
+    pub fn copy_and_clone(&self) -> bool {
+        self.copy.as_ref().is_some_and(|d| d.and_clone)
+    }
+
+    pub fn debug_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.debug
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// Whether the variant is marked `Debug="ignore"`, its fields are then ignored.
+    pub fn debug_ignore(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.ignore)
+    }
+
+    /// Whether the fields of the type can be inlined in another type, from `Debug="flattenable"`.
+    pub fn debug_flattenable(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.flattenable)
+    }
+
+    /// The function formatting the whole value, from `Debug(format_with="…")` on the type.
+    pub fn debug_format_with(&self) -> Option<&syn::Path> {
+        self.debug.as_ref().and_then(|d| d.format_with.as_ref())
+    }
+
+    /// Whether the named fields equal to their default value are left out, from
+    /// `Debug(skip_if_default)` on the type.
+    pub fn debug_skip_if_default(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.skip_if_default)
+    }
+
+    /// Whether the named `Option` fields that are `None` are left out, from `Debug(skip_if_none)`
+    /// on the type.
+    pub fn debug_skip_if_none(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.skip_if_none)
+    }
+
+    /// How many values of the type can be nested in the output, from `Debug(max_depth="…")`.
+    pub fn debug_max_depth(&self) -> Option<usize> {
+        self.debug.as_ref().and_then(|d| d.max_depth)
+    }
+
+    pub fn debug_transparent(&self) -> bool {
+        self.debug_transparent_setting() == Some(true)
+    }
+
+    /// Whether `Debug(transparent)` is given, or `Debug(transparent="false")` to opt a variant out
+    /// of the transparency of its enumeration.
+    pub fn debug_transparent_setting(&self) -> Option<bool> {
+        self.debug.as_ref().and_then(|d| d.transparent)
+    }
+
+    pub fn debug_verbose(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.verbose)
+    }
+
+    pub fn debug_non_exhaustive_hint(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.non_exhaustive_hint)
+    }
+
+    /// Whether the `PhantomData` fields are ignored, from `Debug(hide_phantom)`.
+    pub fn debug_hide_phantom(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.hide_phantom)
+    }
+
+    /// Whether the name of the type is shown with its module path, from `Debug(qualified)`.
+    pub fn debug_qualified(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.qualified)
+    }
+
+    /// The case convention of the field names shown by `Debug`, from `Debug(rename_all="…")`.
+    pub fn debug_rename_all(&self) -> Option<RenameRule> {
+        self.debug.as_ref().and_then(|d| d.rename_all)
+    }
+
+    pub fn debug_with_discriminant(&self) -> bool {
+        self.debug.as_ref().is_some_and(|d| d.with_discriminant)
+    }
+
+    pub fn default_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.default
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The function called with `&mut self` when the value is dropped, from `Drop(with="…")`.
+    pub fn drop_with(&self) -> Option<&syn::Path> {
+        self.drop.as_ref().and_then(|d| d.with.as_ref())
+    }
+
+    pub fn deref_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.deref
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn deref_mut_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.deref_mut
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn eq_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.eq
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn extend_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.extend.as_ref().and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The only item type of `Extend`, from `Extend(item="…")`.
+    pub fn extend_item(&self) -> Option<&syn::Type> {
+        self.extend.as_ref().and_then(|d| d.item.as_ref())
+    }
+
+    pub fn from_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.from
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// Whether the field can also be obtained with `From`, from `From="into"`.
+    pub fn from_into(&self) -> bool {
+        self.from.as_ref().is_some_and(|d| d.into)
+    }
+
+    pub fn from_iterator_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.from_iterator
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The only item type of `FromIterator`, from `FromIterator(item="…")`.
+    pub fn from_iterator_item(&self) -> Option<&syn::Type> {
+        self.from_iterator.as_ref().and_then(|d| d.item.as_ref())
+    }
+
+    /// The error type of `FromStr`, from `FromStr(error="…")`.
+    pub fn from_str_error(&self) -> Option<&syn::Type> {
+        self.from_str.as_ref().and_then(|d| d.error.as_ref())
+    }
+
+    /// The string a variant is parsed from instead of its name, from `FromStr(rename="…")`.
+    pub fn from_str_rename(&self) -> Option<&str> {
+        self.from_str.as_ref().and_then(|d| d.rename.as_deref())
+    }
+
+    /// The other strings a variant is parsed from, from `FromStr(alias="…")`.
+    pub fn from_str_aliases(&self) -> &[String] {
+        self.from_str.as_ref().map_or(&[], |d| &d.aliases)
+    }
+
+    pub fn hash_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.hash
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn hash_content_hash(&self) -> bool {
+        self.hash.as_ref().is_some_and(|d| d.content_hash)
+    }
+
+    pub fn hash_content_hash_seed(&self) -> Option<&syn::Expr> {
+        self.hash.as_ref().and_then(|d| d.content_hash_seed.as_ref())
+    }
+
+    pub fn hash_type_tag(&self) -> bool {
+        self.hash.as_ref().is_some_and(|d| d.type_tag)
+    }
+
+    /// The tag given to `Hash(type_tag="…")`, if any.
+    pub fn hash_type_tag_value(&self) -> Option<&syn::Expr> {
+        self.hash.as_ref().and_then(|d| d.type_tag_value.as_ref())
+    }
+
+    pub fn index_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.index
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The only index type of `Index`, from `Index(index="…")`.
+    pub fn index_type(&self) -> Option<&syn::Type> {
+        self.index.as_ref().and_then(|d| d.index.as_ref())
+    }
+
+    pub fn index_mut_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.index_mut
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The only index type of `IndexMut`, from `IndexMut(index="…")`.
+    pub fn index_mut_type(&self) -> Option<&syn::Type> {
+        self.index_mut.as_ref().and_then(|d| d.index.as_ref())
+    }
+
+    pub fn lower_hex_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.lower_hex
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn octal_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.octal
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.ord
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn ord_on_enum(&self) -> bool {
+        self.ord.as_ref().is_some_and(|d| d.on_enum)
+    }
+
+    /// The rank of the variant for `Ord`, from `Ord(order="…")`.
+    pub fn ord_order(&self) -> Option<usize> {
+        self.ord.as_ref().and_then(|d| d.order)
+    }
+
+    pub fn partial_eq_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_eq
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn partial_eq_on_enum(&self) -> bool {
+        self.partial_eq.as_ref().is_some_and(|d| d.on_enum)
+    }
+
+    /// Whether `PartialEq` is implemented with `impl const`, from `PartialEq="const"`.
+    pub fn partial_eq_const(&self) -> bool {
+        self.partial_eq.as_ref().is_some_and(|d| d.const_impl)
+    }
+
+    pub fn partial_ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_ord
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn partial_ord_on_enum(&self) -> bool {
+        self.partial_ord.as_ref().is_some_and(|d| d.on_enum)
+    }
+
+    /// The rank of the variant for `PartialOrd`, from `PartialOrd(order="…")`.
+    pub fn partial_ord_order(&self) -> Option<usize> {
+        self.partial_ord.as_ref().and_then(|d| d.order)
+    }
+
+    pub fn read_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.read
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn try_from_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.try_from
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    /// The error type of `TryFrom`, from `TryFrom(error="…")`.
+    pub fn try_from_error(&self) -> Option<&syn::Type> {
+        self.try_from.as_ref().and_then(|d| d.error.as_ref())
+    }
+
+    /// The function checking the value converted by `TryFrom`, from `TryFrom(validate_with="…")`.
+    pub fn try_from_validate_with(&self) -> Option<&syn::Path> {
+        self.try_from.as_ref().and_then(|d| d.validate_with.as_ref())
+    }
+
+    /// The name of a variant instead of its name, from `VariantNames(rename="…")`.
+    pub fn variant_names_rename(&self) -> Option<&str> {
+        self.variant_names.as_ref().and_then(|d| d.rename.as_deref())
+    }
+
+    pub fn upper_hex_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.upper_hex
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+
+    pub fn write_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.write
+            .as_ref()
+            .and_then(|d| d.bounds.as_deref())
+    }
+}
+
+impl Field {
+    /// Parse the `derivative` attributes on a field.
+    pub fn from_ast(field: &syn::Field) -> Result<Field, Error> {
+        Field::from_ast_with_extensions(field, "derivative", &[])
+    }
+
+    /// Parse the `attribute` attributes on a field, also accepting the given extension traits.
+    pub fn from_ast_with_extensions(
+        field: &syn::Field,
+        attribute: &str,
+        extensions: &[&str],
+    ) -> Result<Field, Error> {
+        let mut out = Field {
+            deprecated: field.attrs.iter().any(is_deprecated),
+            ..Field::default()
+        };
+
+        for_all_attr! {
+            for (name, values) in field.attrs, attribute => out.traits;
+            "bound_all" => {
+                match values.as_slice() {
+                    [value] if value.name.is_none() => parse_bound(&mut out.bound_all, value)?,
+                    _ => return Err(Error::malformed("Expected `bound_all=\"…\"`", name.span())),
+                }
+            }
+            "AsMut" => {
+                out.as_mut.marked = true;
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.as_mut.bounds, &value)?,
+                }
+            }
+            "AsRef" => {
+                out.as_ref.marked = true;
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.as_ref.bounds, &value)?,
+                }
+            }
+            "Builder" => {
+                match_attributes! {
+                    for value in values;
+                    "ignore" => {
+                        out.ignore_builder = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                }
+            }
+            "Clone" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.clone.bounds, &value)?,
+                    "clone_with" => {
+                        out.clone.clone_with = Some(parse_value(&value, "clone_with")?);
+                    }
+                    "clone_from_with" => {
+                        out.clone.clone_from_with = Some(parse_value(&value, "clone_from_with")?);
+                    }
+                    "ignore" => {
+                        out.clone.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "shared" => {
+                        out.clone.shared = parse_boolean_meta_item(&value, true, "shared")?;
+                    }
+                    "boxed" => {
+                        out.clone.boxed = true;
+                        if value.as_str().is_some_and(|v| v != "boxed") {
+                            out.clone.clone_box = Some(parse_value(&value, "boxed")?);
+                        }
+                    }
+                }
+            }
+            "Debug" => {
+                match_attributes! {
+                    for value in values;
+                    "as_pointer" => {
+                        out.debug.as_pointer = parse_boolean_meta_item(&value, true, "as_pointer")?;
+                    }
+                    "bound" => parse_bound(&mut out.debug.bounds, &value)?,
+                    "delegate" => {
+                        out.debug.delegate = parse_boolean_meta_item(&value, true, "delegate")?;
+                    }
+                    "flatten" => {
+                        out.debug.flatten = parse_boolean_meta_item(&value, true, "flatten")?;
+                    }
+                    "format" => {
+                        let format = value.as_str().ok_or_else(|| Error::missing_value("format", value.span))?;
+                        out.debug.format = Some(syn::LitStr::new(format, value.span));
+                    }
+                    "format_with" => {
+                        out.debug.format_with = Some(parse_value(&value, "format_with")?);
+                    }
+                    "ignore" => {
+                        out.debug.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "mask" => {
+                        let mask = match value.as_str() {
+                            None | Some("mask") => "***",
+                            Some(mask) => mask,
+                        };
+                        out.debug.mask = Some(mask.to_string());
+                    }
+                    "max_len" => out.debug.max_len = Some(parse_number(&value, "max_len")?),
+                    "name" => {
+                        let name = value.as_str().ok_or_else(|| Error::missing_value("name", value.span))?;
+                        out.debug.name = Some(name.to_string());
+                    }
+                    "pass_name" => {
+                        out.debug.pass_name = parse_boolean_meta_item(&value, true, "pass_name")?;
+                    }
+                    "rename" => {
+                        let rename = value.as_str().ok_or_else(|| Error::missing_value("rename", value.span))?;
+                        out.debug.rename = Some(rename.to_string());
+                    }
+                    "skip_if_default" => {
+                        out.debug.skip_if_default = parse_boolean_meta_item(&value, true, "skip_if_default")?;
+                    }
+                    "skip_if_none" => {
+                        out.debug.skip_if_none = parse_boolean_meta_item(&value, true, "skip_if_none")?;
+                    }
+                    "sort_maps" => {
+                        out.debug.sort_maps = parse_boolean_meta_item(&value, true, "sort_maps")?;
+                    }
+                }
+            }
+            "Default" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.default.bounds, &value)?,
+                    "value" if !value.list.is_empty() => {
+                        let mut cfgs = value.list.iter();
+                        while let Some(cfg) = cfgs.next() {
+                            let name = cfg.name.expect("list values are always named");
+                            // A full predicate is given as `cfg="…"`, followed by its value.
+                            let (predicate, value) = if name == "cfg" {
+                                let value = cfgs
+                                    .next()
+                                    .filter(|value| value.name.is_some_and(|name| name == "value"))
+                                    .ok_or_else(|| {
+                                        Error::malformed(
+                                            "Expected `value=\"…\"` after `cfg=\"…\"`",
+                                            cfg.span,
+                                        )
+                                    })?;
+                                (parse_value(cfg, "cfg")?, value)
+                            } else {
+                                (parse_quote!(#name), cfg)
+                            };
+                            out.default.value_cfg.push((predicate, parse_value(value, "value")?));
+                        }
+                    }
+                    "value" => {
+                        out.default.value = Some(parse_value(&value, "value")?);
+                    }
+                    "value_env" => {
+                        let var = value.as_str().ok_or_else(|| Error::missing_value("value_env", value.span))?;
+                        out.default.value_env = Some(var.to_string());
+                    }
+                    "env_fallback" => {
+                        let fallback = value.as_str().ok_or_else(|| Error::missing_value("env_fallback", value.span))?;
+                        out.default.env_fallback = Some(fallback.to_string());
+                    }
+                    "capacity" => {
+                        out.default.capacity = Some(parse_value(&value, "capacity")?);
+                    }
+                    "required" => {
+                        out.default.required = parse_boolean_meta_item(&value, true, "required")?;
+                    }
+                }
+            }
+            "Deref" => {
+                if !values.is_empty() {
+                    return Err(Error::malformed("Expected `Deref` without value", name.span()));
+                }
+                out.deref_target = true;
+            }
+            "Drop" => {
+                match_attributes! {
+                    for value in values;
+                    "with" => {
+                        out.drop_with = Some(parse_value(&value, "with")?);
+                    }
+                }
+            }
+            "Eq" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.eq_bound, &value)?,
+                }
+            }
+            "Extend" => {
+                match_attributes! {
+                    for value in values;
+                    "target" => {
+                        out.extend_target = parse_boolean_meta_item(&value, true, "target")?;
+                    }
+                }
+            }
+            "Hash" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.hash.bounds, &value)?,
+                    "hash_with" => {
+                        out.hash.hash_with = Some(parse_value(&value, "hash_with")?);
+                    }
+                    "ignore" => {
+                        out.hash.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "deref" => {
+                        out.hash.deref = parse_boolean_meta_item(&value, true, "deref")?;
+                    }
+                }
+            }
+            "eq_hash" => {
+                expand_group(&mut out.traits, EQ_HASH);
+
+                match_attributes! {
+                    for value in values;
+                    "bound" => {
+                        parse_bound(&mut out.partial_eq.bounds, &value)?;
+                        parse_bound(&mut out.eq_bound, &value)?;
+                        parse_bound(&mut out.hash.bounds, &value)?;
+                    }
+                    "compare_with" => {
+                        out.partial_eq.compare_with = Some(parse_value(&value, "compare_with")?);
+                    }
+                    "hash_with" => {
+                        out.hash.hash_with = Some(parse_value(&value, "hash_with")?);
+                    }
+                    "ignore" => {
+                        let ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                        out.partial_eq.ignore = ignore;
+                        out.hash.ignore = ignore;
+                    }
+                    "deref" => {
+                        let deref = parse_boolean_meta_item(&value, true, "deref")?;
+                        out.partial_eq.deref = deref;
+                        out.hash.deref = deref;
+                    }
+                }
+
+                if out.partial_eq.compare_with.is_some() != out.hash.hash_with.is_some() {
+                    return Err(Error::malformed(
+                        "`eq_hash` needs both `compare_with` and `hash_with`, so that equal values \
+                         have the same hash",
+                        name.span(),
+                    ));
+                }
+            }
+            "PartialEq" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.partial_eq.bounds, &value)?,
+                    "compare_with" => {
+                        out.partial_eq.compare_with = Some(parse_value(&value, "compare_with")?);
+                    }
+                    "ignore" => {
+                        out.partial_eq.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "deref" => {
+                        out.partial_eq.deref = parse_boolean_meta_item(&value, true, "deref")?;
+                    }
+                }
+            }
+            "Index" => {
+                match_attributes! {
+                    for value in values;
+                    "target" => {
+                        out.index_target = parse_boolean_meta_item(&value, true, "target")?;
+                    }
+                }
+            }
+            "Ord" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.ord.bounds, &value)?,
+                    "cmp_with" => {
+                        out.ord.cmp_with = Some(parse_value(&value, "cmp_with")?);
+                    }
+                    "ignore" => {
+                        out.ord.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "reverse" => {
+                        out.ord.reverse = parse_boolean_meta_item(&value, true, "reverse")?;
+                    }
+                    "priority" => {
+                        out.ord.priority = parse_number(&value, "priority")?;
+                    }
+                }
+            }
+            "PartialOrd" => {
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut out.partial_ord.bounds, &value)?,
+                    "partial_cmp_with" => {
+                        out.partial_ord.partial_cmp_with = Some(parse_value(&value, "partial_cmp_with")?);
+                    }
+                    "ignore" => {
+                        out.partial_ord.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    "reverse" => {
+                        out.partial_ord.reverse = parse_boolean_meta_item(&value, true, "reverse")?;
+                    }
+                    "priority" => {
+                        out.partial_ord.priority = parse_number(&value, "priority")?;
+                    }
+                }
+            }
+            "Read" => {
+                match_attributes! {
+                    for value in values;
+                    "target" => {
+                        out.read_target = parse_boolean_meta_item(&value, true, "target")?;
+                    }
+                }
+            }
+            "Write" => {
+                match_attributes! {
+                    for value in values;
+                    "target" => {
+                        out.write_target = parse_boolean_meta_item(&value, true, "target")?;
+                    }
+                }
+            }
+            extension if extensions.contains(&extension) => {
+                let mut extension = FieldExtension {
+                    name: extension.to_string(),
+                    bounds: None,
+                    with: None,
+                    ignore: false,
+                    epsilon: None,
+                };
+
+                match_attributes! {
+                    for value in values;
+                    "bound" => parse_bound(&mut extension.bounds, &value)?,
+                    "epsilon" => extension.epsilon = Some(parse_value(&value, "epsilon")?),
+                    "ignore" => {
+                        extension.ignore = parse_boolean_meta_item(&value, true, "ignore")?;
+                    }
+                    with if with == "with" || with.ends_with("_with") => {
+                        extension.with = Some(parse_value(&value, with)?);
+                    }
+                }
+
+                out.extensions.push(extension);
+            }
+        }
+
+        let default_values = [
+            out.default.value.is_some(),
+            !out.default.value_cfg.is_empty(),
+            out.default.value_env.is_some(),
+            out.default.capacity.is_some(),
+        ];
+        if out.default.required && default_values.iter().any(|&set| set) {
+            return Err(Error::conflict(
+                "A `Default(required)` field can't have a default value",
+                field.span(),
+            ));
+        }
+        out.default.unavailable = !out.default.required
+            && !default_values.iter().any(|&set| set)
+            && cannot_default(&field.ty);
+        if default_values.iter().filter(|&&set| set).count() > 1 {
+            return Err(Error::conflict(
+                "Only one of `Default(value)`, `Default(value(…))`, `Default(value_env)` and \
+                 `Default(capacity)` can be used",
+                field.span(),
+            ));
+        }
+        if out.default.env_fallback.is_some() && out.default.value_env.is_none() {
+            return Err(Error::malformed(
+                "`Default(env_fallback)` needs `Default(value_env)`",
+                field.span(),
+            ));
+        }
+
+        // The attributes may come from different `derivative` attributes, so this can only be
+        // checked once they are all parsed.
+        let ignored_with = [
+            ("Debug", "format_with", out.debug.ignore && out.debug.format_with.is_some()),
+            ("Hash", "hash_with", out.hash.ignore && out.hash.hash_with.is_some()),
+            (
+                "PartialEq",
+                "compare_with",
+                out.partial_eq.ignore && out.partial_eq.compare_with.is_some(),
+            ),
+            (
+                "PartialOrd",
+                "partial_cmp_with",
+                out.partial_ord.ignore && out.partial_ord.partial_cmp_with.is_some(),
+            ),
+            ("Ord", "cmp_with", out.ord.ignore && out.ord.cmp_with.is_some()),
+        ];
+        let ignored_with = ignored_with
+            .iter()
+            .filter(|&&(_, _, conflict)| conflict)
+            .map(|&(name, with, _)| (name, with))
+            .chain(
+                out.extensions
+                    .iter()
+                    .filter(|e| e.ignore && e.with.is_some())
+                    .map(|e| (e.name.as_str(), "with")),
+            )
+            .next();
+        if let Some((name, with)) = ignored_with {
+            let span = out
+                .traits
+                .iter()
+                .rev()
+                .find(|ident| *ident == name)
+                .map_or_else(|| field.span(), syn::Ident::span);
+            return Err(Error::conflict(
+                format!(
+                    "`{0}(ignore)` conflicts with `{0}({1})`: the function is never called on an \
+                     ignored field",
+                    name, with
+                ),
+                span,
+            ));
+        }
+
+        // A field can be shown in only one of these ways, and not at all when it is ignored, in
+        // which case it can't be left out either.
+        let shown_as: Vec<_> = [
+            ("as_pointer", out.debug.as_pointer),
+            ("flatten", out.debug.flatten),
+            ("format", out.debug.format.is_some()),
+            ("format_with", out.debug.format_with.is_some()),
+            ("mask", out.debug.mask.is_some()),
+            ("max_len", out.debug.max_len.is_some()),
+            ("sort_maps", out.debug.sort_maps),
+        ]
+        .iter()
+        .filter(|&&(_, given)| given)
+        .map(|&(option, _)| option)
+        .collect();
+        let skip = if out.debug.skip_if_default {
+            Some("skip_if_default")
+        } else if out.debug.skip_if_none {
+            Some("skip_if_none")
+        } else {
+            None
+        };
+        let conflict = match (shown_as.as_slice(), skip) {
+            (&[option, other, ..], _) => Some((option, other)),
+            (&[option], _) if out.debug.ignore => Some((option, "ignore")),
+            (&["flatten"], _) if out.debug.rename.is_some() => Some(("flatten", "rename")),
+            (&["flatten"], Some(skip)) => Some(("flatten", skip)),
+            (&[], Some(skip)) if out.debug.ignore => Some((skip, "ignore")),
+            _ if out.debug.delegate && out.debug.ignore => Some(("delegate", "ignore")),
+            (&["flatten"], _) if out.debug.delegate => Some(("delegate", "flatten")),
+            (_, Some(skip)) if out.debug.delegate => Some(("delegate", skip)),
+            _ => None,
+        };
+        if let Some((option, other)) = conflict {
+            return Err(Error::conflict(
+                format!("`Debug({})` conflicts with `Debug({})`", option, other),
+                field.span(),
+            ));
+        }
+        if out.debug.pass_name && out.debug.format_with.is_none() {
+            return Err(Error::malformed(
+                "`Debug(pass_name)` can only be used with `Debug(format_with)`",
+                field.span(),
+            ));
+        }
+
+        // A field is cloned in only one of these ways. An ignored field is not cloned at all, and
+        // a shared field is cloned by cloning the pointer, even in `clone_from`.
+        let cloned_as: Vec<_> = [
+            ("ignore", out.clone.ignore),
+            ("shared", out.clone.shared),
+            ("boxed", out.clone.boxed),
+            ("clone_with", out.clone.clone_with.is_some()),
+        ]
+        .iter()
+        .filter(|&&(_, given)| given)
+        .map(|&(option, _)| option)
+        .collect();
+        let conflict = match cloned_as.as_slice() {
+            &[option, other, ..] => Some((other, option)),
+            &[option @ "ignore", ..] | &[option @ "shared", ..]
+                if out.clone.clone_from_with.is_some() =>
+            {
+                Some(("clone_from_with", option))
+            }
+            _ => None,
+        };
+        if let Some((option, other)) = conflict {
+            return Err(Error::conflict(
+                format!("`Clone({})` conflicts with `Clone({})`", option, other),
+                field.span(),
+            ));
+        }
+
+        if let Some(ref bound_all) = out.bound_all {
+            let extensions = out.extensions.iter_mut().map(|e| &mut e.bounds);
+            for bounds in vec![
+                &mut out.as_mut.bounds,
+                &mut out.as_ref.bounds,
+                &mut out.clone.bounds,
+                &mut out.copy_bound,
+                &mut out.debug.bounds,
+                &mut out.default.bounds,
+                &mut out.eq_bound,
+                &mut out.hash.bounds,
+                &mut out.ord.bounds,
+                &mut out.partial_eq.bounds,
+                &mut out.partial_ord.bounds,
+            ]
+            .into_iter()
+            .chain(extensions)
+            {
+                bounds.get_or_insert_with(Vec::new).extend(bound_all.iter().cloned());
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Return the attributes of the extension trait `name` if present on this field.
+    pub fn extension(&self, name: &str) -> Option<&FieldExtension> {
+        self.extensions.iter().find(|extension| extension.name == name)
+    }
+
+    /// The names of the traits used in the attributes of this field, in order.
+    pub fn traits(&self) -> &[syn::Ident] {
+        &self.traits
+    }
+
+    /// Whether the field is `#[deprecated]`.
+    pub fn deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    /// The bounds given to `bound_all`, which are also part of the bounds of every trait.
+    pub fn bound_all(&self) -> Option<&[syn::WherePredicate]> {
+        self.bound_all.as_deref()
+    }
+
+    pub fn as_mut_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.as_mut.bounds.as_deref()
+    }
+
+    /// Whether `AsMut` is implemented for the type of the field, from `#[derivative(AsMut)]`.
+    pub fn as_mut_target(&self) -> bool {
+        self.as_mut.marked
+    }
+
+    pub fn as_ref_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.as_ref.bounds.as_deref()
+    }
+
+    /// Whether `AsRef` is implemented for the type of the field, from `#[derivative(AsRef)]`.
+    pub fn as_ref_target(&self) -> bool {
+        self.as_ref.marked
+    }
+
+    pub fn clone_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.clone.bounds.as_deref()
+    }
+
+    pub fn clone_with(&self) -> Option<&syn::Path> {
+        self.clone.clone_with.as_ref()
+    }
+
+    /// The function cloning the field in place in `clone_from`, from `Clone(clone_from_with)`.
+    pub fn clone_from_with(&self) -> Option<&syn::Path> {
+        self.clone.clone_from_with.as_ref()
+    }
+
+    /// Whether the field gets its default value instead of being cloned, from `Clone="ignore"`.
+    pub fn ignore_clone(&self) -> bool {
+        self.clone.ignore
+    }
+
+    /// Whether the field is a shared pointer whose target does not need to be `Clone`, from
+    /// `Clone="shared"`.
+    pub fn clone_shared(&self) -> bool {
+        self.clone.shared
+    }
+
+    /// Whether the field is a box cloned with a clone-box function, from `Clone="boxed"`.
+    pub fn clone_boxed(&self) -> bool {
+        self.clone.boxed
+    }
+
+    /// The clone-box function of the field, from `Clone(boxed="…")`.
+    pub fn clone_box(&self) -> Option<&syn::Path> {
+        self.clone.clone_box.as_ref()
+    }
+
+    pub fn copy_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.copy_bound.as_deref()
+    }
+
+    pub fn debug_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.debug.bounds.as_deref()
+    }
+
+    /// Whether the structure or variant is shown as this field alone, from `Debug(delegate)`.
+    pub fn debug_delegate(&self) -> bool {
+        self.debug.delegate
+    }
+
+    /// Whether the fields of the field are shown as fields of its parent, from `Debug="flatten"`.
+    pub fn debug_flatten(&self) -> bool {
+        self.debug.flatten
+    }
+
+    /// The format string of the field, from `Debug(format="…")`.
+    pub fn debug_format(&self) -> Option<&syn::LitStr> {
+        self.debug.format.as_ref()
+    }
+
+    /// Whether the field is shown as the address it points to, from `Debug(as_pointer)`.
+    pub fn debug_as_pointer(&self) -> bool {
+        self.debug.as_pointer
+    }
+
+    /// Whether the `format_with` function also gets the name of the field, from
+    /// `Debug(pass_name)`.
+    pub fn debug_pass_name(&self) -> bool {
+        self.debug.pass_name
+    }
+
+    /// The number of elements of the field that are shown, from `Debug(max_len="…")`.
+    pub fn debug_max_len(&self) -> Option<usize> {
+        self.debug.max_len
+    }
+
+    /// Whether the field is left out when it is equal to its default value, from
+    /// `Debug(skip_if_default)`.
+    pub fn debug_skip_if_default(&self) -> bool {
+        self.debug.skip_if_default
+    }
+
+    /// Whether the field is left out when it is `None`, from `Debug(skip_if_none)`.
+    pub fn debug_skip_if_none(&self) -> bool {
+        self.debug.skip_if_none
+    }
+
+    /// Whether the entries of the field are sorted before being shown, from `Debug(sort_maps)`.
+    pub fn debug_sort_maps(&self) -> bool {
+        self.debug.sort_maps
+    }
+
+    /// The string shown instead of the field, from `Debug(mask)` or `Debug(mask="…")`.
+    pub fn debug_mask(&self) -> Option<&str> {
+        self.debug.mask.as_deref()
+    }
+
+    pub fn debug_format_with(&self) -> Option<&syn::Path> {
+        self.debug.format_with.as_ref()
+    }
+
+    /// Whether the field is left out of the builder and always gets its default value, from
+    /// `Builder="ignore"`.
+    pub fn ignore_builder(&self) -> bool {
+        self.ignore_builder
+    }
+
+    pub fn ignore_debug(&self) -> bool {
+        self.debug.ignore
+    }
+
+    /// The label of the tuple field in the `Debug` output, from `Debug(name="…")`.
+    pub fn debug_name(&self) -> Option<&str> {
+        self.debug.name.as_ref().map(|n| n.as_ref())
+    }
+
+    /// The label of the named field in the `Debug` output instead of its name, from
+    /// `Debug(rename="…")`.
+    pub fn debug_rename(&self) -> Option<&str> {
+        self.debug.rename.as_deref()
+    }
+
+    pub fn ignore_hash(&self) -> bool {
+        self.hash.ignore
+    }
+
+    pub fn default_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.default.bounds.as_deref()
+    }
+
+    pub fn default_value(&self) -> Option<&syn::Expr> {
+        self.default.value.as_ref()
+    }
+
+    /// The default values of the field for each configuration, from `Default(value(<cfg>="…"))`.
+    pub fn default_value_cfg(&self) -> &[(syn::Meta, syn::Expr)] {
+        &self.default.value_cfg
+    }
+
+    pub fn default_value_env(&self) -> Option<&str> {
+        self.default.value_env.as_ref().map(|v| v.as_ref())
+    }
+
+    pub fn default_env_fallback(&self) -> Option<&str> {
+        self.default.env_fallback.as_ref().map(|v| v.as_ref())
+    }
+
+    /// The capacity to create the field with, from `Default(capacity="…")`.
+    pub fn default_capacity(&self) -> Option<&syn::Expr> {
+        self.default.capacity.as_ref()
+    }
+
+    /// Whether the field is a parameter of the `new` function, from `Default(required)` or because
+    /// its type can't implement `Default`.
+    pub fn default_required(&self) -> bool {
+        self.default.required || self.default.unavailable
+    }
+
+    /// Whether the field is required only because its type can't implement `Default`.
+    pub fn default_unavailable(&self) -> bool {
+        self.default.unavailable
+    }
+
+    /// Whether the field is the target of `Deref` and `DerefMut`, from `#[derivative(Deref)]`.
+    pub fn deref_target(&self) -> bool {
+        self.deref_target
+    }
+
+    /// The function called on the field when the value is dropped, from `Drop(with="…")`.
+    pub fn drop_with(&self) -> Option<&syn::Path> {
+        self.drop_with.as_ref()
+    }
+
+    pub fn eq_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.eq_bound.as_deref()
+    }
+
+    pub fn hash_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.hash.bounds.as_deref()
+    }
+
+    pub fn hash_with(&self) -> Option<&syn::Path> {
+        self.hash.hash_with.as_ref()
+    }
+
+    /// Whether the `Deref` target of the field is hashed, from `Hash(deref)`.
+    pub fn hash_deref(&self) -> bool {
+        self.hash.deref
+    }
+
+    /// Whether the field is the target of `Extend`, from `Extend="target"`.
+    pub fn extend_target(&self) -> bool {
+        self.extend_target
+    }
+
+    /// Whether the field is the target of `Index` and `IndexMut`, from `Index="target"`.
+    pub fn index_target(&self) -> bool {
+        self.index_target
+    }
+
+    pub fn ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.ord.bounds.as_deref()
+    }
+
+    pub fn ord_cmp_with(&self) -> Option<&syn::Path> {
+        self.ord.cmp_with.as_ref()
+    }
+
+    pub fn ignore_ord(&self) -> bool {
+        self.ord.ignore
+    }
+
+    /// Whether the field is compared in descending order by `Ord`, from `Ord(reverse)`.
+    pub fn ord_reverse(&self) -> bool {
+        self.ord.reverse
+    }
+
+    /// The priority of the field in `Ord`, from `Ord(priority="…")`.
+    pub fn ord_priority(&self) -> i32 {
+        self.ord.priority
+    }
+
+    pub fn partial_eq_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_eq.bounds.as_deref()
+    }
+
+    pub fn partial_eq_compare_with(&self) -> Option<&syn::Path> {
+        self.partial_eq.compare_with.as_ref()
+    }
+
+    pub fn ignore_partial_eq(&self) -> bool {
+        self.partial_eq.ignore
+    }
+
+    /// Whether the `Deref` target of the field is compared, from `PartialEq(deref)`.
+    pub fn partial_eq_deref(&self) -> bool {
+        self.partial_eq.deref
+    }
+
+    pub fn partial_ord_bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.partial_ord.bounds.as_deref()
+    }
+
+    pub fn partial_ord_partial_cmp_with(&self) -> Option<&syn::Path> {
+        self.partial_ord.partial_cmp_with.as_ref()
+    }
+
+    pub fn ignore_partial_ord(&self) -> bool {
+        self.partial_ord.ignore
+    }
+
+    /// Whether the field is compared in descending order by `PartialOrd`, from
+    /// `PartialOrd(reverse)`.
+    pub fn partial_ord_reverse(&self) -> bool {
+        self.partial_ord.reverse
+    }
+
+    /// The priority of the field in `PartialOrd`, from `PartialOrd(priority="…")`.
+    pub fn partial_ord_priority(&self) -> i32 {
+        self.partial_ord.priority
+    }
+
+    /// Whether the field is the target of `Read`, from `Read="target"`.
+    pub fn read_target(&self) -> bool {
+        self.read_target
+    }
+
+    /// Whether the field is the target of `Write`, from `Write="target"`.
+    pub fn write_target(&self) -> bool {
+        self.write_target
+    }
+
+    /// Ignore the field for `Debug`, `Hash`, `PartialEq`, `PartialOrd` and `Ord`, because its
+    /// type is listed in `ignore_types`.
+    pub(crate) fn ignore_type(&mut self) {
+        self.debug.ignore = true;
+        self.hash.ignore = true;
+        self.partial_eq.ignore = true;
+        self.partial_ord.ignore = true;
+        self.ord.ignore = true;
+    }
+
+    /// Ignore the field for `Debug`, because its variant is marked `Debug="ignore"`, another
+    /// field is marked `Debug(delegate)`, or it is a `PhantomData` and the type is marked
+    /// `Debug(hide_phantom)`.
+    pub(crate) fn ignore_debug_implicitly(&mut self) {
+        self.debug.ignore = true;
+    }
+
+    /// How the field is handled, if bindgen generated it and the type has a `bindgen` attribute.
+    pub fn bindgen(&self) -> Option<Bindgen> {
+        self.bindgen
+    }
+
+    /// Handle the field, which bindgen generated, with `policy`. A skipped field is ignored by
+    /// `Hash`, `PartialEq`, `PartialOrd` and `Ord`.
+    pub(crate) fn set_bindgen(&mut self, policy: Bindgen) {
+        self.bindgen = Some(policy);
+        if policy == Bindgen::Skip {
+            self.hash.ignore = true;
+            self.partial_eq.ignore = true;
+            self.partial_ord.ignore = true;
+            self.ord.ignore = true;
+        }
+    }
+}
+
+impl InputExtension {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.bounds.as_deref()
+    }
+
+    pub fn epsilon_type(&self) -> Option<&syn::Type> {
+        self.epsilon_type.as_ref()
+    }
+}
+
+impl FieldExtension {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bound(&self) -> Option<&[syn::WherePredicate]> {
+        self.bounds.as_deref()
+    }
+
+    pub fn with(&self) -> Option<&syn::Path> {
+        self.with.as_ref()
+    }
+
+    pub fn ignore(&self) -> bool {
+        self.ignore
+    }
+
+    pub fn epsilon(&self) -> Option<&syn::Expr> {
+        self.epsilon.as_ref()
+    }
+}
+
+/// Whether `ty` can't implement `Default`, whatever its type parameters are: references other
+/// than `&str` and slices, raw and function pointers, trait objects, `NonNull` and the `NonZero`
+/// integers, and the tuples and arrays of those.
+fn cannot_default(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Reference(ref reference) => match *reference.elem {
+            syn::Type::Slice(_) => false,
+            syn::Type::Path(ref path) => !path.path.is_ident("str"),
+            _ => true,
+        },
+        syn::Type::Ptr(_) | syn::Type::BareFn(_) | syn::Type::TraitObject(_) => true,
+        syn::Type::Path(syn::TypePath { qself: None, ref path }) => {
+            path.segments.iter().last().is_some_and(|segment| {
+                segment.ident == "NonNull" || segment.ident.to_string().starts_with("NonZero")
+            })
+        }
+        syn::Type::Tuple(ref tuple) => tuple.elems.iter().any(cannot_default),
+        syn::Type::Array(ref array) => cannot_default(&array.elem),
+        syn::Type::Group(ref group) => cannot_default(&group.elem),
+        syn::Type::Paren(ref paren) => cannot_default(&paren.elem),
+        _ => false,
+    }
+}
+
+/// The traits derived by the `eq_hash` group.
+const EQ_HASH: &[&str] = &["PartialEq", "Eq", "Hash"];
+
+/// Record the traits of a group instead of its name, which was just added to `traits`, so that
+/// the group is handled as if its traits were used directly.
+fn expand_group(traits: &mut Vec<syn::Ident>, group: &[&str]) {
+    let name = traits.pop().expect("the group was recorded");
+    traits.extend(group.iter().map(|t| syn::Ident::new(t, name.span())));
+}
+
+/// Represent an attribute.
+///
+/// We only have a limited set of possible attributes:
+///
+/// * `#[derivative(Debug)]` is represented as `(Debug, [])`;
+/// * `#[derivative(Debug="foo")]` is represented as `(Debug, [(None, Some("foo"))])`;
+/// * `#[derivative(Debug(foo="bar")]` is represented as `(Debug, [(Some(foo), Some("bar"))])`;
+/// * `#[derivative(Debug(foo)]` is represented as `(Debug, [(Some(foo), None)])`;
+/// * `#[derivative(Debug(foo(bar="baz"))]` is represented as `(Debug, [(Some(foo), None)])` with
+///   `[(Some(bar), Some("baz"))]` as the `list` of the value.
+struct MetaItem<'a>(&'a syn::Ident, Vec<MetaValue<'a>>);
+
+/// Represent one of the `(name, value)` pairs of a `MetaItem`, along with the span to use when
+/// reporting an error about it.
+struct MetaValue<'a> {
+    name: Option<&'a syn::Ident>,
+    value: Option<String>,
+    /// The `name = "value"` pairs if the value is a list.
+    list: Vec<MetaValue<'a>>,
+    span: proc_macro2::Span,
+}
+
+impl<'a> MetaValue<'a> {
+    fn as_str(&self) -> Option<&str> {
+        self.value.as_ref().map(|v| v.as_ref())
+    }
+}
+
+/// Parse an arbitrary item for our limited `MetaItem` subset.
+fn read_items(item: &syn::NestedMeta) -> Result<MetaItem<'_>, Error> {
+    let item = compat::nested_meta(item)
+        .map_err(|lit| Error::malformed("Expected meta-item but found literal", lit.span()))?;
+
+    match compat::meta(item)? {
+        compat::Meta::Word(name) => Ok(MetaItem(name, Vec::new())),
+        compat::Meta::List(name, values) => {
+            let values = values
+                .iter()
+                .map(|value| read_value(value, true))
+                .collect::<Result<_, _>>()?;
+
+            Ok(MetaItem(name, values))
+        }
+        compat::Meta::NameValue(name, value) => Ok(MetaItem(
+            name,
+            vec![MetaValue {
+                name: None,
+                value: Some(string_or_err(value)?),
+                list: Vec::new(),
+                span: value.span(),
+            }],
+        )),
+    }
+}
+
+/// Parse one of the values in the list of a `MetaItem`. Only a `name = "value"` pair is accepted,
+/// unless `nested` is set, in which case words and lists of pairs are accepted too.
+fn read_value(value: &syn::NestedMeta, nested: bool) -> Result<MetaValue<'_>, Error> {
+    let meta = compat::nested_meta(value).map(compat::meta);
+    match meta {
+        Ok(Ok(compat::Meta::NameValue(name, value))) => Ok(MetaValue {
+            name: Some(name),
+            value: Some(string_or_err(value)?),
+            list: Vec::new(),
+            span: value.span(),
+        }),
+        Ok(Ok(compat::Meta::Word(name))) if nested => Ok(MetaValue {
+            name: Some(name),
+            value: None,
+            list: Vec::new(),
+            span: name.span(),
+        }),
+        Ok(Ok(compat::Meta::List(name, values))) if nested => Ok(MetaValue {
+            name: Some(name),
+            value: None,
+            list: values
+                .iter()
+                .map(|value| read_value(value, false))
+                .collect::<Result<_, _>>()?,
+            span: name.span(),
+        }),
+        Ok(Err(e)) => Err(e),
+        _ => Err(Error::malformed("Expected named value", value.span())),
+    }
+}
+
+/// Filter the `derivative` items from an attribute.
+///
+/// Only the path of the attribute is looked at to decide whether it is ours, so that a malformed
+/// `derivative` attribute is reported instead of being silently ignored.
+fn derivative_attribute(
+    attr: &syn::Attribute,
+    attribute: &str,
+) -> Option<Result<syn::punctuated::Punctuated<syn::NestedMeta, syn::token::Comma>, Error>> {
+    let is_derivative = attr.path.leading_colon.is_none()
+        && attr.path.segments.len() == 1
+        && attr.path.segments[0].ident == attribute;
+    if !is_derivative {
+        return None;
+    }
+
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(syn::MetaList { nested: mis, .. })) => Some(Ok(mis)),
+        Ok(_) => Some(Err(Error::malformed(
+            format!("Expected `#[{}(…)]`", attribute),
+            attr.path.segments[0].ident.span(),
+        ))),
+        Err(e) => Some(Err(Error::malformed(
+            format!("invalid `{}` attribute: {}", attribute, e),
+            e.span(),
+        ))),
+    }
+}
+
+/// Whether `attr` is a `#[derive(…)]` attribute containing `Copy`.
+fn derives_copy(attr: &syn::Attribute) -> bool {
+    let is_derive = attr.path.segments.len() == 1 && attr.path.segments[0].ident == "derive";
+
+    // The traits are parsed as paths, eg. `std::marker::Copy`, which `syn` 0.15 doesn't accept in
+    // meta-items.
+    let traits = |input: syn::parse::ParseStream| {
+        let content;
+        parenthesized!(content in input);
+        content.parse_terminated::<_, Token![,]>(syn::Path::parse_mod_style)
+    };
+
+    is_derive
+        && traits
+            .parse2(compat::attr_tokens(attr).clone())
+            .map(|traits| {
+                traits.iter().any(|path| {
+                    path.segments
+                        .iter()
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Copy")
+                })
+            })
+            .unwrap_or(false)
+}
+
+/// Return the integer type of `attr` if it is a `#[repr(…)]` attribute containing one.
+fn repr_int(attr: &syn::Attribute) -> Option<syn::Ident> {
+    const INTS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    let is_repr = attr.path.segments.len() == 1 && attr.path.segments[0].ident == "repr";
+
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(ref list)) if is_repr => list
+            .nested
+            .iter()
+            .filter_map(|nested| compat::nested_meta(nested).ok())
+            .filter_map(|meta| match compat::meta(meta) {
+                Ok(compat::Meta::Word(word)) => Some(word),
+                _ => None,
+            })
+            .find(|word| INTS.iter().any(|int| *word == int))
+            .cloned(),
+        _ => None,
+    }
+}
+
+/// Whether `attr` is a `#[deprecated]` attribute, with or without a note.
+fn is_deprecated(attr: &syn::Attribute) -> bool {
+    attr.path.segments.len() == 1 && attr.path.segments[0].ident == "deprecated"
+}
+
+/// Whether `attr` is a `#[repr(…)]` attribute containing `packed` or `packed(…)`.
+fn is_repr_packed(attr: &syn::Attribute) -> bool {
+    let is_repr = attr.path.segments.len() == 1 && attr.path.segments[0].ident == "repr";
+
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(ref list)) if is_repr => list.nested.iter().any(|nested| {
+            let meta = compat::nested_meta(nested).ok().and_then(|meta| compat::meta(meta).ok());
+            match meta {
+                Some(compat::Meta::Word(name)) | Some(compat::Meta::List(name, _)) => {
+                    name == "packed"
+                }
+                _ => false,
+            }
+        }),
+        _ => false,
+    }
+}
+
+/// Parse an item value as a boolean. Accepted values are the string literal `"true"` and
+/// `"false"`. The `default` parameter specifies what the value of the boolean is when only its
+/// name is specified (eg. `Debug="ignore"` is equivalent to `Debug(ignore="true")`). The `name`
+/// parameter is used for error reporting.
+fn parse_boolean_meta_item(item: &MetaValue, default: bool, name: &str) -> Result<bool, Error> {
+    if !item.list.is_empty() {
+        return Err(Error::malformed(
+            format!("Expected `{}` or `{}=\"true\"`", name, name),
+            item.span,
+        ));
+    }
+
+    match item.as_str() {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(val) => {
+            if val == name {
+                Ok(true)
+            } else {
+                Err(Error::invalid_value(name, val, item.span))
+            }
+        }
+        None => Ok(default),
+    }
+}
+
+/// Parse an `ignore_types` item: either a type name in the list, or a string containing a
+/// comma-separated list of types, which is needed for paths.
+fn parse_ignore_types(types: &mut Vec<syn::Type>, value: &MetaValue) -> Result<(), Error> {
+    match (value.name, value.as_str()) {
+        (Some(name), None) if value.list.is_empty() => {
+            types.push(parse_quote!(#name));
+            Ok(())
+        }
+        (None, Some(list)) => {
+            let parser = syn::punctuated::Punctuated::<syn::Type, syn::token::Comma>::parse_terminated;
+            let list = parser
+                .parse_str(list)
+                .map_err(|e| Error::parse(e.to_string(), value.span))?;
+            types.extend(list);
+            Ok(())
+        }
+        _ => Err(Error::malformed(
+            "Expected `ignore_types(Type, …)` or `ignore_types=\"path::Type, …\"`",
+            value.span,
+        )),
+    }
+}
+
+/// Parse a `bound` item.
+fn parse_bound(
+    opt_bounds: &mut Option<Vec<syn::WherePredicate>>,
+    value: &MetaValue,
+) -> Result<(), Error> {
+    let mut bounds = opt_bounds.take().unwrap_or_default();
+    let bound = value
+        .as_str()
+        .ok_or_else(|| Error::missing_value("bound", value.span))?;
+
+    if !bound.is_empty() {
+        let mut stream = proc_macro2::TokenStream::new();
+        quote!(where).to_tokens(&mut stream);
+        let constraints = proc_macro2::TokenStream::from_str(bound)
+            .map_err(|e| Error::parse(format!("{:?}", e), value.span))?;
+        stream.extend(constraints);
+
+        let where_clause = syn::parse2::<syn::WhereClause>(stream)
+            .map_err(|e| Error::parse(e.to_string(), value.span))?;
+        bounds.extend(where_clause.predicates);
+    }
+
+    *opt_bounds = Some(bounds);
+
+    Ok(())
+}
+
+/// Get the string out of a string literal or report an error for other literals.
+fn string_or_err(lit: &syn::Lit) -> Result<String, Error> {
+    if let syn::Lit::Str(ref lit) = *lit {
+        Ok(lit.value())
+    } else {
+        Err(Error::malformed("Expected string", lit.span()))
+    }
+}
+
+/// Parse the number given to the attribute `name`, eg. `priority="2"`.
+fn parse_number<T: FromStr>(value: &MetaValue, name: &str) -> Result<T, Error> {
+    let string = value
+        .as_str()
+        .ok_or_else(|| Error::missing_value(name, value.span))?;
+
+    string
+        .parse()
+        .map_err(|_| Error::invalid_value(name, string, value.span))
+}
+
+/// Parse the value of the `name` item, the name of a case convention, eg. `kebab-case`.
+fn parse_rename_rule(value: &MetaValue, name: &str) -> Result<RenameRule, Error> {
+    let string = value
+        .as_str()
+        .ok_or_else(|| Error::missing_value(name, value.span))?;
+
+    RenameRule::from_name(string).ok_or_else(|| Error::invalid_value(name, string, value.span))
+}
+
+/// Parse the value of the `name` item, which is required.
+fn parse_value<T>(value: &MetaValue, name: &str) -> Result<T, Error>
+where
+    T: syn::parse::Parse,
+{
+    let string = value
+        .as_str()
+        .ok_or_else(|| Error::missing_value(name, value.span))?;
+
+    syn::parse_str::<T>(string).map_err(|e| Error::parse(e.to_string(), value.span))
+}
@@ -0,0 +1,227 @@
+/* This file incorporates work covered by the following copyright and
+ * permission notice:
+ *   Copyright 2016 The serde Developers. See
+ *   https://github.com/serde-rs/serde/blob/3f28a9324042950afa80354722aeeee1a55cbfa3/README.md#license.
+ *
+ *   Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+ *   http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+ *   <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+ *   option. This file may not be copied, modified, or distributed
+ *   except according to those terms.
+ */
+
+use ast;
+use attr;
+use std::collections::HashSet;
+use syn::{self, visit, GenericParam};
+
+// use internals::ast::Item;
+// use internals::attr;
+
+/// Remove the default from every type and const parameter because in the generated `impl`s
+/// they look like associated types: "error: associated type bindings are not
+/// allowed here". The defaults are not needed to name the type either.
+pub fn without_defaults(generics: &syn::Generics) -> syn::Generics {
+    syn::Generics {
+        params: generics
+            .params
+            .iter()
+            .map(|generic_param| match *generic_param {
+                GenericParam::Type(ref ty_param) => syn::GenericParam::Type(syn::TypeParam {
+                    eq_token: None,
+                    default: None,
+                    ..ty_param.clone()
+                }),
+                GenericParam::Const(ref const_param) => syn::GenericParam::Const(syn::ConstParam {
+                    eq_token: None,
+                    default: None,
+                    ..const_param.clone()
+                }),
+                ref param => param.clone(),
+            })
+            .collect(),
+        ..generics.clone()
+    }
+}
+
+pub fn with_where_predicates(
+    generics: &syn::Generics,
+    predicates: &[syn::WherePredicate],
+) -> syn::Generics {
+    let mut cloned = generics.clone();
+    cloned.make_where_clause().predicates.extend(predicates.iter().cloned());
+    cloned
+}
+
+pub fn with_where_predicates_from_fields<F>(
+    item: &ast::Input,
+    generics: &syn::Generics,
+    from_field: F,
+) -> syn::Generics
+where
+    F: Fn(&attr::Field) -> Option<&[syn::WherePredicate]>,
+{
+    let mut cloned = generics.clone();
+    {
+        let fields = item.body.all_fields();
+        let field_where_predicates = fields
+            .iter()
+            .flat_map(|field| from_field(&field.attrs))
+            .flat_map(|predicates| predicates.to_vec());
+
+        cloned.make_where_clause().predicates.extend(field_where_predicates);
+    }
+    cloned
+}
+
+/// Puts the given bound on any generic type parameters that are used in fields
+/// for which filter returns true.
+///
+/// For example, the following structure needs the bound `A: Debug, B: Debug`.
+///
+/// ```ignore
+/// struct S<'b, A, B: 'b, C> {
+///     a: A,
+///     b: Option<&'b B>
+///     #[derivative(Debug="ignore")]
+///     c: C,
+/// }
+/// ```
+pub fn with_bound<F>(
+    item: &ast::Input,
+    generics: &syn::Generics,
+    filter: F,
+    bound: &syn::Path,
+) -> syn::Generics
+where
+    F: Fn(&attr::Field) -> bool,
+{
+    let relevant_ty_params = relevant_ty_params(item, generics, filter);
+
+    let mut cloned = generics.clone();
+    {
+        let relevant_where_predicates = generics
+            .type_params()
+            .map(|ty_param| &ty_param.ident)
+            .filter(|id| relevant_ty_params.contains(id))
+            .map(|id| -> syn::WherePredicate { parse_quote!( #id : #bound ) });
+
+        cloned.make_where_clause().predicates.extend(relevant_where_predicates);
+    }
+    cloned
+}
+
+/// Puts the bound `<F as Deref>::Target: <bound>` on the type `F` of each field for which filter
+/// returns true and that uses generic type parameters. These fields are used through their
+/// `Deref` target, so it is the target that needs the bound rather than the type parameters.
+pub fn with_deref_bound<F>(
+    item: &ast::Input,
+    generics: &syn::Generics,
+    filter: F,
+    deref_path: &syn::Path,
+    bound: &syn::Path,
+) -> syn::Generics
+where
+    F: Fn(&attr::Field) -> bool,
+{
+    let mut cloned = generics.clone();
+    {
+        let deref_where_predicates = item
+            .body
+            .all_fields()
+            .into_iter()
+            .filter(|field| filter(&field.attrs))
+            .filter(|field| uses_ty_params(generics, field.ty))
+            .map(|field| -> syn::WherePredicate {
+                let ty = field.ty;
+                parse_quote!( <#ty as #deref_path>::Target : #bound )
+            });
+
+        cloned.make_where_clause().predicates.extend(deref_where_predicates);
+    }
+    cloned
+}
+
+/// Whether the type `ty` uses the generic type parameters of `generics`, ignoring `PhantomData`.
+pub fn uses_ty_params(generics: &syn::Generics, ty: &syn::Type) -> bool {
+    let mut visitor = FindTyParams::new(generics);
+    visit::visit_type(&mut visitor, ty);
+    !visitor.relevant_ty_params.is_empty()
+}
+
+#[derive(Debug)]
+struct FindTyParams {
+    /// Set of all generic type parameters on the current struct (A, B, C in
+    /// the example). Initialized up front.
+    all_ty_params: HashSet<syn::Ident>,
+    /// Set of generic type parameters used in fields for which filter
+    /// returns true (A and B in the example). Filled in as the visitor sees
+    /// them.
+    relevant_ty_params: HashSet<syn::Ident>,
+}
+
+impl FindTyParams {
+    fn new(generics: &syn::Generics) -> Self {
+        FindTyParams {
+            all_ty_params: generics
+                .type_params()
+                .map(|ty_param| ty_param.ident.clone())
+                .collect(),
+            relevant_ty_params: HashSet::new(),
+        }
+    }
+}
+
+impl<'ast> visit::Visit<'ast> for FindTyParams {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if is_phantom_data(path) {
+            // Hardcoded exception, because `PhantomData<T>` implements
+            // most traits whether or not `T` implements it.
+            return;
+        }
+        if path.leading_colon.is_none() && path.segments.len() == 1 {
+            let id = &path.segments[0].ident;
+            if self.all_ty_params.contains(id) {
+                self.relevant_ty_params.insert(id.clone());
+            }
+        }
+        visit::visit_path(self, path);
+    }
+}
+
+/// Return the generic type parameters that are used in fields for which filter returns true,
+/// ignoring `PhantomData`. These are the type parameters that get a bound in `with_bound`.
+pub fn relevant_ty_params<F>(
+    item: &ast::Input,
+    generics: &syn::Generics,
+    filter: F,
+) -> HashSet<syn::Ident>
+where
+    F: Fn(&attr::Field) -> bool,
+{
+
+    let relevant_tys = item
+        .body
+        .all_fields()
+        .into_iter()
+        .filter(|field| {
+            if let syn::Type::Path(syn::TypePath { ref path, .. }) = *field.ty {
+                !is_phantom_data(path)
+            } else {
+                true
+            }
+        })
+        .filter(|field| filter(&field.attrs))
+        .map(|field| &field.ty);
+
+    let mut visitor = FindTyParams::new(generics);
+    for ty in relevant_tys {
+        visit::visit_type(&mut visitor, ty);
+    }
+
+    visitor.relevant_ty_params
+}
+
+fn is_phantom_data(path: &syn::Path) -> bool {
+    matches!(path.segments.iter().last(), Some(seg) if seg.ident == "PhantomData")
+}
@@ -0,0 +1,110 @@
+//! The case conventions of `rename_all`, eg. `rename_all="kebab-case"`.
+
+/// A case convention, used to rename the variants or the fields of a type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`
+    LowerCase,
+    /// `UPPERCASE`
+    UpperCase,
+    /// `PascalCase`, the convention of the variants, which are kept as is.
+    PascalCase,
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+    /// `kebab-case`
+    KebabCase,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebabCase,
+}
+
+/// The names of the conventions, as given to `rename_all`.
+const RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::LowerCase),
+    ("UPPERCASE", RenameRule::UpperCase),
+    ("PascalCase", RenameRule::PascalCase),
+    ("camelCase", RenameRule::CamelCase),
+    ("snake_case", RenameRule::SnakeCase),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+    ("kebab-case", RenameRule::KebabCase),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebabCase),
+];
+
+impl RenameRule {
+    /// Return the convention named `name`, eg. `kebab-case`.
+    pub fn from_name(name: &str) -> Option<RenameRule> {
+        RULES
+            .iter()
+            .find(|&&(n, _)| n == name)
+            .map(|&(_, rule)| rule)
+    }
+
+    /// Rename `variant`, which is in `PascalCase`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::PascalCase => variant.to_string(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Rename `field`, which is in `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::LowerCase | RenameRule::SnakeCase => field.to_string(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply_to_field(field);
+                RenameRule::CamelCase.apply_to_variant(&pascal)
+            }
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_field(field)
+                .replace('_', "-"),
+        }
+    }
+}
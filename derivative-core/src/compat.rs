@@ -0,0 +1,73 @@
+//! The differences between the supported major versions of `syn`, so that the rest of the crate
+//! is written once. The version is selected by the `syn-0_15` and `syn-1` features.
+
+use proc_macro2;
+use syn;
+use syn::punctuated::Punctuated;
+#[cfg(feature = "syn-1")]
+use syn::spanned::Spanned;
+
+use error::Error;
+
+/// A `syn::Meta` whose names are identifiers.
+pub enum Meta<'a> {
+    /// `name`
+    Word(&'a syn::Ident),
+    /// `name(…)`
+    List(&'a syn::Ident, &'a Punctuated<syn::NestedMeta, Token![,]>),
+    /// `name = "value"`
+    NameValue(&'a syn::Ident, &'a syn::Lit),
+}
+
+/// Return the meta-item of `nested`, or the literal it is if it's not one.
+#[cfg(not(feature = "syn-1"))]
+pub fn nested_meta(nested: &syn::NestedMeta) -> Result<&syn::Meta, &syn::Lit> {
+    match *nested {
+        syn::NestedMeta::Meta(ref meta) => Ok(meta),
+        syn::NestedMeta::Literal(ref lit) => Err(lit),
+    }
+}
+
+/// Return the meta-item of `nested`, or the literal it is if it's not one.
+#[cfg(feature = "syn-1")]
+pub fn nested_meta(nested: &syn::NestedMeta) -> Result<&syn::Meta, &syn::Lit> {
+    match *nested {
+        syn::NestedMeta::Meta(ref meta) => Ok(meta),
+        syn::NestedMeta::Lit(ref lit) => Err(lit),
+    }
+}
+
+/// Return the tokens of `attr` after its path, eg. `(Clone, Copy)` in `#[derive(Clone, Copy)]`.
+#[cfg(not(feature = "syn-1"))]
+pub fn attr_tokens(attr: &syn::Attribute) -> &proc_macro2::TokenStream {
+    &attr.tts
+}
+
+/// Return the tokens of `attr` after its path, eg. `(Clone, Copy)` in `#[derive(Clone, Copy)]`.
+#[cfg(feature = "syn-1")]
+pub fn attr_tokens(attr: &syn::Attribute) -> &proc_macro2::TokenStream {
+    &attr.tokens
+}
+
+#[cfg(not(feature = "syn-1"))]
+pub fn meta(meta: &syn::Meta) -> Result<Meta<'_>, Error> {
+    Ok(match *meta {
+        syn::Meta::Word(ref name) => Meta::Word(name),
+        syn::Meta::List(ref list) => Meta::List(&list.ident, &list.nested),
+        syn::Meta::NameValue(ref name_value) => Meta::NameValue(&name_value.ident, &name_value.lit),
+    })
+}
+
+#[cfg(feature = "syn-1")]
+pub fn meta(meta: &syn::Meta) -> Result<Meta<'_>, Error> {
+    let name = meta
+        .path()
+        .get_ident()
+        .ok_or_else(|| Error::malformed("Expected identifier", meta.path().span()))?;
+
+    Ok(match *meta {
+        syn::Meta::Path(_) => Meta::Word(name),
+        syn::Meta::List(ref list) => Meta::List(name, &list.nested),
+        syn::Meta::NameValue(ref name_value) => Meta::NameValue(name, &name_value.lit),
+    })
+}
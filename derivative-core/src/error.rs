@@ -0,0 +1,211 @@
+use std::error;
+use std::fmt;
+
+use proc_macro2;
+use syn;
+
+/// An error reported while parsing the input of *derivative* or generating its output.
+///
+/// Every error carries the span of the offending tokens and has a stable code, see
+/// [`Error::code`]. The codes are documented in the *Errors* chapter of the documentation.
+/// New kinds of errors may be added, with new codes.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A trait that is not supported, eg. `#[derivative(Foo)]`.
+    UnknownTrait {
+        name: String,
+        span: proc_macro2::Span,
+    },
+    /// An attribute that is not supported by a trait, eg. `#[derivative(Debug(foo="bar"))]`.
+    UnknownAttribute {
+        name: String,
+        span: proc_macro2::Span,
+    },
+    /// An attribute that needs a value was given none, eg. `#[derivative(Debug(bound))]`.
+    MissingValue {
+        name: String,
+        span: proc_macro2::Span,
+    },
+    /// An attribute was given a value it does not accept, eg. `#[derivative(Debug(ignore="no"))]`.
+    InvalidValue {
+        name: String,
+        value: String,
+        span: proc_macro2::Span,
+    },
+    /// An attribute does not have the expected shape, eg. `#[derivative(Debug(bound=42))]`.
+    Malformed {
+        message: String,
+        span: proc_macro2::Span,
+    },
+    /// A value could not be parsed as Rust code, eg. `#[derivative(Default(value="1 +"))]`.
+    Parse {
+        message: String,
+        span: proc_macro2::Span,
+    },
+    /// The input is valid but can't be derived, eg. `#[derivative(Debug)]` on a `union`.
+    Unsupported {
+        message: String,
+        span: proc_macro2::Span,
+    },
+    /// A file used by *derivative* could not be accessed, eg. the report file.
+    Io {
+        message: String,
+        span: proc_macro2::Span,
+    },
+    /// Attributes that can't be used together, eg. `Debug(ignore)` and `Debug(format_with)`.
+    Conflict {
+        message: String,
+        span: proc_macro2::Span,
+    },
+    /// An attribute that has no effect, only reported with `#[derivative(strict)]`, eg.
+    /// `Hash(hash_with)` on a field of a type that does not derive `Hash`.
+    NoEffect {
+        message: String,
+        span: proc_macro2::Span,
+    },
+}
+
+impl Error {
+    pub fn unknown_trait(name: &syn::Ident) -> Self {
+        Error::UnknownTrait {
+            name: name.to_string(),
+            span: name.span(),
+        }
+    }
+
+    pub fn unknown_attribute<S: Into<String>>(name: S, span: proc_macro2::Span) -> Self {
+        Error::UnknownAttribute {
+            name: name.into(),
+            span,
+        }
+    }
+
+    pub fn missing_value<S: Into<String>>(name: S, span: proc_macro2::Span) -> Self {
+        Error::MissingValue {
+            name: name.into(),
+            span,
+        }
+    }
+
+    pub fn invalid_value<S: Into<String>, V: Into<String>>(
+        name: S,
+        value: V,
+        span: proc_macro2::Span,
+    ) -> Self {
+        Error::InvalidValue {
+            name: name.into(),
+            value: value.into(),
+            span,
+        }
+    }
+
+    pub fn malformed<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::Malformed {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn parse<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::Parse {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn unsupported<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::Unsupported {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn io<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::Io {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn conflict<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::Conflict {
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn no_effect<S: Into<String>>(message: S, span: proc_macro2::Span) -> Self {
+        Error::NoEffect {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// The stable code of this error, eg. `D0001`.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Error::UnknownTrait { .. } => "D0001",
+            Error::UnknownAttribute { .. } => "D0002",
+            Error::MissingValue { .. } => "D0003",
+            Error::InvalidValue { .. } => "D0004",
+            Error::Malformed { .. } => "D0005",
+            Error::Parse { .. } => "D0006",
+            Error::Unsupported { .. } => "D0007",
+            Error::Io { .. } => "D0008",
+            Error::Conflict { .. } => "D0009",
+            Error::NoEffect { .. } => "D0010",
+        }
+    }
+
+    /// The span of the tokens this error is about.
+    pub fn span(&self) -> proc_macro2::Span {
+        match *self {
+            Error::UnknownTrait { span, .. }
+            | Error::UnknownAttribute { span, .. }
+            | Error::MissingValue { span, .. }
+            | Error::InvalidValue { span, .. }
+            | Error::Malformed { span, .. }
+            | Error::Parse { span, .. }
+            | Error::Unsupported { span, .. }
+            | Error::Io { span, .. }
+            | Error::Conflict { span, .. }
+            | Error::NoEffect { span, .. } => span,
+        }
+    }
+
+    /// Render this error as a `compile_error!` invocation pointing at its span.
+    pub fn to_compile_error(&self) -> proc_macro2::TokenStream {
+        let message = format!("{} [{}]", self, self.code());
+        quote_spanned!(self.span()=> compile_error!(#message);)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnknownTrait { ref name, .. } => write!(f, "unknown trait `{}`", name),
+            Error::UnknownAttribute { ref name, .. } => write!(f, "unknown attribute `{}`", name),
+            Error::MissingValue { ref name, .. } => write!(f, "`{}` needs a value", name),
+            Error::InvalidValue {
+                ref name,
+                ref value,
+                ..
+            } => write!(f, "Invalid value for `{}`: `{}`", name, value),
+            Error::Malformed { ref message, .. }
+            | Error::Parse { ref message, .. }
+            | Error::Unsupported { ref message, .. }
+            | Error::Io { ref message, .. }
+            | Error::Conflict { ref message, .. }
+            | Error::NoEffect { ref message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<syn::Error> for Error {
+    fn from(e: syn::Error) -> Self {
+        Error::parse(e.to_string(), e.span())
+    }
+}
@@ -0,0 +1,38 @@
+use proc_macro2;
+
+use ast;
+use error::Error;
+
+/// The code generator for one trait.
+///
+/// Each trait supported by *derivative* has a generator working on the shared model in the
+/// `ast` module. Extensions can implement this trait for their own traits and use
+/// `generate_all` to expand them.
+pub trait TraitGenerator {
+    /// The name of the trait, as written in `#[derivative(…)]`.
+    fn name(&self) -> &'static str;
+
+    /// Whether the trait was requested on `input`.
+    fn is_requested(&self, input: &ast::Input) -> bool;
+
+    /// Generate the implementation of the trait for `input`, and any inherent items that come
+    /// with it.
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error>;
+}
+
+/// Run every generator in `generators` that was requested on `input` and concatenate their
+/// outputs.
+pub fn generate_all(
+    input: &ast::Input,
+    generators: &[&dyn TraitGenerator],
+) -> Result<proc_macro2::TokenStream, Error> {
+    let mut tokens = proc_macro2::TokenStream::new();
+
+    for generator in generators {
+        if generator.is_requested(input) {
+            tokens.extend(generator.generate(input)?);
+        }
+    }
+
+    Ok(tokens)
+}
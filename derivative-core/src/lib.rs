@@ -0,0 +1,49 @@
+//! The building blocks of [`derivative`]: its attribute parsing, its model of the annotated type
+//! and its bound inference.
+//!
+//! This crate is meant for procedural macros that derive their own traits but want to accept
+//! the same `#[derivative(…)]` vocabulary (`bound`, `ignore`, `…_with`) as *derivative*. See
+//! [`ast::Input::from_ast_with_extensions`].
+//!
+//! The types of the API come from `syn`, `quote` and `proc-macro2`, whose major version is
+//! selected by the `syn-0_15` (default) or `syn-1` feature. These features are not additive:
+//! `syn-1` wins when both are enabled, which changes the API for every user of this crate.
+//!
+//! [`derivative`]: https://mcarton.github.io/rust-derivative/
+
+#[cfg(not(any(feature = "syn-0_15", feature = "syn-1")))]
+compile_error!("one of the `syn-0_15` or `syn-1` features must be enabled");
+
+#[cfg(not(feature = "syn-1"))]
+extern crate proc_macro2;
+#[cfg(feature = "syn-1")]
+extern crate proc_macro2_1 as proc_macro2;
+#[cfg(not(feature = "syn-1"))]
+#[macro_use]
+extern crate syn;
+#[cfg(feature = "syn-1")]
+#[macro_use]
+extern crate syn_1 as syn;
+
+#[cfg(not(feature = "syn-1"))]
+#[macro_use]
+extern crate quote;
+#[cfg(feature = "syn-1")]
+#[macro_use]
+extern crate quote_1 as quote;
+
+pub mod ast;
+pub mod attr;
+pub mod bound;
+pub mod case;
+mod compat;
+pub mod error;
+pub mod generator;
+pub mod lint;
+pub mod matcher;
+pub mod report;
+mod strict;
+pub mod utils;
+
+pub use error::Error;
+pub use generator::TraitGenerator;
@@ -0,0 +1,399 @@
+//! Warnings about how *derivative* is used. Unlike errors, they don't prevent the generation of
+//! the implementations. *derivative* only reports the warnings of a `Lint` when its feature is
+//! enabled, see `Lint`.
+
+use std::collections::HashSet;
+
+use proc_macro2;
+use syn;
+use syn::visit::{self, Visit};
+
+use ast;
+use attr;
+use bound;
+
+/// The kinds of warnings, which *derivative* enables separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lint {
+    /// A trait is derived as `#[derive]` would, from the `lint_std_derive` feature.
+    StdDerive,
+    /// An inferred bound makes an implementation unavailable for no reason, from the
+    /// `lint_inferred_bounds` feature.
+    InferredBounds,
+}
+
+/// A warning about the input of *derivative*.
+#[derive(Clone, Debug)]
+pub struct Warning {
+    lint: Lint,
+    message: String,
+    span: proc_macro2::Span,
+}
+
+impl Warning {
+    pub fn new<S: Into<String>>(lint: Lint, message: S, span: proc_macro2::Span) -> Self {
+        Warning {
+            lint,
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// The kind of this warning.
+    pub fn lint(&self) -> Lint {
+        self.lint
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The span of the tokens this warning is about.
+    pub fn span(&self) -> proc_macro2::Span {
+        self.span
+    }
+}
+
+/// Return all the warnings about `input`.
+pub fn check(input: &ast::Input) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    for name in input.attrs.traits() {
+        if is_std_derive(input, &name.to_string()) {
+            warnings.push(Warning::new(
+                Lint::StdDerive,
+                format!(
+                    "`derivative({0})` does the same as `#[derive({0})]`, which should be used \
+                     instead",
+                    name
+                ),
+                name.span(),
+            ));
+        }
+
+        check_inferred_bounds(input, name, &mut warnings);
+    }
+
+    warnings
+}
+
+/// The field types that implement a trait whatever their type parameters are, so that a type
+/// parameter only used in them does not need a bound.
+struct Unbounded {
+    /// `&T`
+    references: bool,
+    /// `*const T`, `*mut T` and `fn(T) -> T`.
+    pointers: bool,
+    /// Paths whose last segment is one of these, eg. `Vec<T>` for `Default`.
+    paths: &'static [&'static str],
+}
+
+/// Warn about the bounds inferred for the trait `name` that make its implementation unusable for
+/// the instantiations of the type that are likely intended.
+fn check_inferred_bounds(input: &ast::Input, name: &syn::Ident, warnings: &mut Vec<Warning>) {
+    let attrs = &input.attrs;
+    let (needs_bound, unbounded): (fn(&attr::Field) -> bool, _) = match name.to_string().as_ref() {
+        "Clone" if attrs.clone_bound().is_none() && attrs.clone_is_copy() != Some(true) => (
+            |f| {
+                !f.ignore_clone()
+                    && !f.clone_shared()
+                    && !f.clone_boxed()
+                    && f.clone_bound().is_none()
+            },
+            Unbounded {
+                references: true,
+                pointers: true,
+                paths: &["Arc", "Rc", "Weak"],
+            },
+        ),
+        "Copy" if attrs.copy_bound().is_none() => (
+            |f| f.copy_bound().is_none(),
+            Unbounded {
+                references: true,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "Debug" if attrs.debug_bound().is_none() => (
+            |f| {
+                !f.ignore_debug()
+                    && f.debug_mask().is_none()
+                    && !f.debug_as_pointer()
+                    && f.debug_bound().is_none()
+            },
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "Default" if attrs.default_bound().is_none() => (
+            |f| f.default_bound().is_none() && !f.default_required(),
+            Unbounded {
+                references: false,
+                pointers: false,
+                paths: &[
+                    "BTreeMap",
+                    "BTreeSet",
+                    "HashMap",
+                    "HashSet",
+                    "LinkedList",
+                    "Option",
+                    "Vec",
+                    "VecDeque",
+                ],
+            },
+        ),
+        "Eq" if attrs.eq_bound().is_none() => (
+            |f| !f.ignore_partial_eq() && f.eq_bound().is_none() && !f.partial_eq_deref(),
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "Hash" if attrs.hash_bound().is_none() => (
+            |f| !f.ignore_hash() && f.hash_bound().is_none() && !f.hash_deref(),
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "PartialEq" if attrs.partial_eq_bound().is_none() => (
+            |f| {
+                !f.ignore_partial_eq() && f.partial_eq_bound().is_none() && !f.partial_eq_deref()
+            },
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "PartialOrd" if attrs.partial_ord_bound().is_none() => (
+            |f| !f.ignore_partial_ord() && f.partial_ord_bound().is_none(),
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        "Ord" if attrs.ord_bound().is_none() => (
+            |f| !f.ignore_ord() && f.ord_bound().is_none(),
+            Unbounded {
+                references: false,
+                pointers: true,
+                paths: &[],
+            },
+        ),
+        _ => return,
+    };
+
+    let bounded = bound::relevant_ty_params(input, input.generics, needs_bound);
+
+    let mut visitor = NeededTyParams {
+        ty_params: &bounded,
+        unbounded: &unbounded,
+        needed: HashSet::new(),
+    };
+    for field in input.body.all_fields() {
+        if needs_bound(&field.attrs) {
+            visitor.visit_type(field.ty);
+        }
+    }
+
+    let requires_sized = matches!(name.to_string().as_ref(), "Clone" | "Copy" | "Default");
+
+    for ty_param in input.generics.type_params() {
+        let ident = &ty_param.ident;
+        if !bounded.contains(ident) {
+            continue;
+        }
+
+        if requires_sized && is_maybe_sized(input.generics, ty_param) {
+            warnings.push(Warning::new(
+                Lint::InferredBounds,
+                format!(
+                    "`{1}` is `?Sized` but the inferred bound `{1}: {0}` requires it to be sized, \
+                     so `{0}` is only implemented for sized `{1}`; use `{0}(bound=\"…\")` to \
+                     give the bounds",
+                    name, ident
+                ),
+                name.span(),
+            ));
+        } else if !visitor.needed.contains(ident) {
+            warnings.push(Warning::new(
+                Lint::InferredBounds,
+                format!(
+                    "the inferred bound `{1}: {0}` is not needed by the fields, but `{0}` is only \
+                     implemented when `{1}: {0}`; use `{0}(bound=\"\")` to remove it",
+                    name, ident
+                ),
+                name.span(),
+            ));
+        }
+    }
+}
+
+/// Whether `ty_param` is declared `?Sized`, either in its bounds or in the where clause.
+fn is_maybe_sized(generics: &syn::Generics, ty_param: &syn::TypeParam) -> bool {
+    let is_maybe = |bound: &syn::TypeParamBound| match *bound {
+        syn::TypeParamBound::Trait(ref bound) => match bound.modifier {
+            syn::TraitBoundModifier::Maybe(_) => true,
+            syn::TraitBoundModifier::None => false,
+        },
+        syn::TypeParamBound::Lifetime(_) => false,
+    };
+
+    let is_ty_param = |ty: &syn::Type| match *ty {
+        syn::Type::Path(ref ty) => {
+            ty.qself.is_none()
+                && ty.path.leading_colon.is_none()
+                && ty.path.segments.len() == 1
+                && ty.path.segments[0].ident == ty_param.ident
+        }
+        _ => false,
+    };
+
+    let in_where_clause = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .any(|predicate| match *predicate {
+            syn::WherePredicate::Type(ref predicate) => {
+                is_ty_param(&predicate.bounded_ty) && predicate.bounds.iter().any(is_maybe)
+            }
+            _ => false,
+        });
+
+    ty_param.bounds.iter().any(is_maybe) || in_where_clause
+}
+
+/// Find the type parameters that are used outside of the `Unbounded` types, and thus need a
+/// bound.
+struct NeededTyParams<'a> {
+    ty_params: &'a HashSet<syn::Ident>,
+    unbounded: &'a Unbounded,
+    needed: HashSet<syn::Ident>,
+}
+
+impl<'a, 'ast> Visit<'ast> for NeededTyParams<'a> {
+    fn visit_type_reference(&mut self, reference: &'ast syn::TypeReference) {
+        if !self.unbounded.references || reference.mutability.is_some() {
+            visit::visit_type_reference(self, reference);
+        }
+    }
+
+    fn visit_type_ptr(&mut self, ptr: &'ast syn::TypePtr) {
+        if !self.unbounded.pointers {
+            visit::visit_type_ptr(self, ptr);
+        }
+    }
+
+    fn visit_type_bare_fn(&mut self, bare_fn: &'ast syn::TypeBareFn) {
+        if !self.unbounded.pointers {
+            visit::visit_type_bare_fn(self, bare_fn);
+        }
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(last) = path.segments.iter().last() {
+            if last.ident == "PhantomData" || self.unbounded.paths.iter().any(|p| last.ident == p) {
+                return;
+            }
+        }
+
+        if path.leading_colon.is_none() && path.segments.len() == 1 {
+            let ident = &path.segments[0].ident;
+            if self.ty_params.contains(ident) {
+                self.needed.insert(ident.clone());
+            }
+        }
+
+        visit::visit_path(self, path);
+    }
+}
+
+/// Whether *derivative* generates the same implementation of `name` as the built-in derive would:
+/// no attribute is used, and all the type parameters get a bound.
+fn is_std_derive(input: &ast::Input, name: &str) -> bool {
+    let attrs = &input.attrs;
+    let no_options = match name {
+        "Clone" => {
+            attrs.clone_bound().is_none()
+                && !attrs.clone_from()
+                && !attrs.clone_const()
+                && attrs.clone_with().is_none()
+                && attrs.clone_is_copy().is_none()
+        }
+        "Copy" => attrs.copy_bound().is_none() && !attrs.copy_and_clone(),
+        "Debug" => {
+            attrs.debug_bound().is_none()
+                && !attrs.debug_transparent()
+                && !attrs.debug_verbose()
+                && !attrs.debug_non_exhaustive_hint()
+                && !attrs.debug_qualified()
+                && !attrs.debug_hide_phantom()
+                && attrs.debug_rename_all().is_none()
+                && !attrs.debug_flattenable()
+                && attrs.debug_max_depth().is_none()
+                && attrs.debug_format_with().is_none()
+                && !attrs.debug_skip_if_default()
+                && !attrs.debug_skip_if_none()
+                && !attrs.debug_with_discriminant()
+                && attrs.rename_all().is_none()
+        }
+        "Default" => {
+            let default = attrs.default.as_ref();
+            attrs.default_bound().is_none()
+                && default.is_some_and(|d| !d.new && !d.reset && !d.value_fns && !d.const_value)
+                && matches!(input.body, ast::Body::Struct(..))
+        }
+        "Eq" => attrs.eq_bound().is_none(),
+        "Hash" => {
+            attrs.hash_bound().is_none() && !attrs.hash_content_hash() && !attrs.hash_type_tag()
+        }
+        "PartialEq" => {
+            attrs.partial_eq_bound().is_none()
+                && !attrs.partial_eq_on_enum()
+                && !attrs.partial_eq_const()
+        }
+        "PartialOrd" => attrs.partial_ord_bound().is_none() && !attrs.partial_ord_on_enum(),
+        "Ord" => attrs.ord_bound().is_none() && !attrs.ord_on_enum(),
+        _ => false,
+    };
+    if !no_options || attrs.remote().is_some() {
+        return false;
+    }
+
+    let ignores_types = matches!(name, "Debug" | "Hash" | "PartialEq" | "PartialOrd" | "Ord");
+    let mentions = |traits: &[syn::Ident]| {
+        traits
+            .iter()
+            .any(|ident| {
+                ident == name || ident == "bound_all" || (ignores_types && ident == "ignore_types")
+            })
+    };
+    if ignores_types && !attrs.ignore_types().is_empty() {
+        return false;
+    }
+    if ignores_types && input.body.all_fields().iter().any(|f| f.attrs.bindgen().is_some()) {
+        return false;
+    }
+
+    let used_on_variant = match input.body {
+        ast::Body::Enum(ref variants) => variants.iter().any(|v| mentions(v.attrs.traits())),
+        ast::Body::Struct(..) => false,
+    };
+    let used_on_field = input
+        .body
+        .all_fields()
+        .iter()
+        .any(|field| mentions(field.attrs.traits()));
+    if used_on_variant || used_on_field {
+        return false;
+    }
+
+    let bounded = bound::relevant_ty_params(input, input.generics, |_| true);
+    input.generics.type_params().count() == bounded.len()
+}
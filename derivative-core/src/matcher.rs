@@ -42,6 +42,7 @@ impl quote::ToTokens for BindingStyle {
 
 #[derive(Debug)]
 pub struct BindingInfo<'a> {
+    /// The binding of the field, with the span of the field.
     pub ident: syn::Ident,
     pub field: &'a ast::Field<'a>,
 }
@@ -72,13 +73,14 @@ impl Matcher {
             -> proc_macro2::TokenStream,
     {
         let ident = &input.ident;
+        let type_path = type_path(input);
         // Generate patterns for matching against all of the variants
         let variants = match input.body {
             ast::Body::Enum(ref variants) => variants
                 .iter()
                 .map(|variant| {
                     let variant_ident = &variant.ident;
-                    let variant_path = parse_quote!(#ident::#variant_ident);
+                    let variant_path = parse_quote!(#type_path::#variant_ident);
 
                     let pat =
                         self.build_match_pattern(&variant_path, variant.style, &variant.fields);
@@ -93,14 +95,8 @@ impl Matcher {
                 })
                 .collect(),
             ast::Body::Struct(style, ref vd) => {
-                let path = parse_quote!(#ident);
-                vec![(
-                    path,
-                    ident,
-                    style,
-                    &input.attrs,
-                    self.build_match_pattern(ident, style, vd),
-                )]
+                let pat = self.build_match_pattern(&type_path, style, vd);
+                vec![(type_path, ident, style, &input.attrs, pat)]
             }
         };
 
@@ -108,7 +104,17 @@ impl Matcher {
         // expression
         let mut t = proc_macro2::TokenStream::new();
         for (path, name, style, attrs, (pat, bindings)) in variants {
-            let body = f(path, name, style, attrs, bindings);
+            // A value with a field of type `!` can't exist, matching on the field proves it.
+            let body = match bindings.iter().find(|bi| is_never(bi.field.ty)) {
+                Some(bi) => {
+                    let ident = &bi.ident;
+                    match self.binding_style {
+                        BindingStyle::Move | BindingStyle::MoveMut => quote!(match #ident {}),
+                        BindingStyle::Ref | BindingStyle::RefMut => quote!(match *#ident {}),
+                    }
+                }
+                None => f(path, name, style, attrs, bindings),
+            };
             quote!(#pat => { #body }).to_tokens(&mut t);
         }
 
@@ -133,10 +139,10 @@ impl Matcher {
                     |(mut stream, mut matches), (i, field)| {
                         let ident: syn::Ident = syn::Ident::new(
                             &format!("{}_{}", self.binding_name, i),
-                            proc_macro2::Span::call_site(),
+                            field.span(),
                         );
                         quote!(#binding #ident ,).to_tokens(&mut stream);
-                        matches.push(BindingInfo { ident: ident, field: field });
+                        matches.push(BindingInfo { ident, field });
 
                         (stream, matches)
                     },
@@ -150,15 +156,15 @@ impl Matcher {
                     |(mut stream, mut matches), (i, field)| {
                         let ident: syn::Ident = syn::Ident::new(
                             &format!("{}_{}", self.binding_name, i),
-                            proc_macro2::Span::call_site(),
+                            field.span(),
                         );
                         {
                             let field_name = field.ident.as_ref().unwrap();
                             quote!(#field_name : #binding #ident ,).to_tokens(&mut stream);
                         }
                         matches.push(BindingInfo {
-                            ident: ident,
-                            field: field,
+                            ident,
+                            field,
                         });
 
                         (stream, matches)
@@ -176,3 +182,25 @@ impl Matcher {
         (all_tokens, matches)
     }
 }
+
+/// Return the path of the type matched by the patterns: the foreign type given to `remote` if
+/// any, or else the type itself.
+pub fn type_path(input: &ast::Input) -> syn::Path {
+    match input.attrs.remote() {
+        Some(remote) => remote.clone(),
+        None => {
+            let ident = &input.ident;
+            parse_quote!(#ident)
+        }
+    }
+}
+
+/// Whether `ty` is the never type `!`.
+fn is_never(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Never(_) => true,
+        syn::Type::Group(ref ty) => is_never(&ty.elem),
+        syn::Type::Paren(ref ty) => is_never(&ty.elem),
+        _ => false,
+    }
+}
@@ -0,0 +1,321 @@
+//! A machine-readable summary of what was derived for a type.
+//!
+//! The report of a type is a single line of JSON:
+//!
+//! ```json
+//! {"crate":"foo","type":"Credentials","traits":["Debug"],"bounds":{"Debug":null},
+//!     "with":{},"fields":[
+//!     {"variant":null,"field":"user","ignored":[],"exposed":[],"masked":{},"with":{},
+//!         "bounds":{}},
+//!     {"variant":null,"field":"password","ignored":["Debug"],"exposed":[],"masked":{},"with":{},
+//!         "bounds":{}}]}
+//! ```
+//!
+//! (shown on several lines for readability). `traits` lists the traits that were generated.
+//! `bounds` has the explicit `bound` of each of them, or `null` when the bounds are inferred.
+//! `with` has the function used for the whole value by the traits that have one, eg.
+//! `Debug(format_with)` on the type; the attributes of the fields for these traits have no
+//! effect, so the fields don't list them.
+//! `exposed` lists the traits that ignore the field but still show it on demand, eg. `Debug`
+//! through the `verbose_debug` method. `masked` has the string shown instead of the field by each
+//! trait which redacts it, eg. `Debug(mask)`. Tuple fields are named by their index.
+
+use std::fmt::Write;
+
+use quote::ToTokens;
+use syn;
+
+use ast;
+use attr;
+use generator::TraitGenerator;
+
+/// Build the report of `input` for the `generators` that were requested on it.
+pub fn to_json(
+    krate: Option<&str>,
+    input: &ast::Input,
+    generators: &[&dyn TraitGenerator],
+) -> String {
+    let traits: Vec<_> = generators
+        .iter()
+        .filter(|generator| generator.is_requested(input))
+        .map(|generator| generator.name())
+        .collect();
+
+    let mut out = String::new();
+
+    out.push_str("{\"crate\":");
+    push_option_str(&mut out, krate);
+    out.push_str(",\"type\":");
+    push_str(&mut out, &input.ident.to_string());
+
+    out.push_str(",\"traits\":[");
+    for (i, name) in traits.iter().enumerate() {
+        push_separator(&mut out, i);
+        push_str(&mut out, name);
+    }
+
+    out.push_str("],\"bounds\":{");
+    for (i, name) in traits.iter().enumerate() {
+        push_separator(&mut out, i);
+        push_str(&mut out, name);
+        out.push(':');
+        push_bounds(&mut out, input_bound(&input.attrs, name));
+    }
+
+    out.push_str("},\"with\":{");
+    let with = traits
+        .iter()
+        .filter_map(|name| input_with(&input.attrs, name).map(|path| (name, path)));
+    for (i, (name, path)) in with.enumerate() {
+        push_separator(&mut out, i);
+        push_str(&mut out, name);
+        out.push(':');
+        push_str(&mut out, &path.into_token_stream().to_string());
+    }
+    out.push_str("},\"fields\":[");
+
+    let fields: Vec<(Option<&syn::Ident>, &ast::Field)> = match input.body {
+        ast::Body::Enum(ref variants) => variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(move |f| (Some(&variant.ident), f)))
+            .collect(),
+        ast::Body::Struct(_, ref fields) => fields.iter().map(|f| (None, f)).collect(),
+    };
+
+    // The traits using a function for the whole value don't look at the fields.
+    let field_traits: Vec<_> = traits
+        .iter()
+        .cloned()
+        .filter(|name| input_with(&input.attrs, name).is_none())
+        .collect();
+
+    let mut index = 0;
+    let mut last_variant = None;
+    for (i, &(variant, field)) in fields.iter().enumerate() {
+        if variant != last_variant {
+            index = 0;
+            last_variant = variant;
+        }
+
+        push_separator(&mut out, i);
+        push_field(&mut out, &field_traits, &input.attrs, variant, field, index);
+        index += 1;
+    }
+
+    out.push_str("]}");
+    out
+}
+
+fn push_field(
+    out: &mut String,
+    traits: &[&str],
+    input: &attr::Input,
+    variant: Option<&syn::Ident>,
+    field: &ast::Field,
+    index: usize,
+) {
+    let name = match field.ident {
+        Some(ref ident) => ident.to_string(),
+        None => index.to_string(),
+    };
+
+    out.push_str("{\"variant\":");
+    push_option_str(out, variant.map(ToString::to_string).as_deref());
+    out.push_str(",\"field\":");
+    push_str(out, &name);
+
+    out.push_str(",\"ignored\":[");
+    let ignored = traits.iter().filter(|name| field_ignore(&field.attrs, name));
+    for (i, name) in ignored.enumerate() {
+        push_separator(out, i);
+        push_str(out, name);
+    }
+
+    out.push_str("],\"exposed\":[");
+    let exposed = traits.iter().filter(|name| field_exposed(input, &field.attrs, name));
+    for (i, name) in exposed.enumerate() {
+        push_separator(out, i);
+        push_str(out, name);
+    }
+
+    out.push_str("],\"masked\":{");
+    let masked = traits
+        .iter()
+        .filter_map(|name| field_mask(&field.attrs, name).map(|mask| (name, mask)));
+    for (i, (name, mask)) in masked.enumerate() {
+        push_separator(out, i);
+        push_str(out, name);
+        out.push(':');
+        push_str(out, mask);
+    }
+
+    out.push_str("},\"with\":{");
+    let with = traits
+        .iter()
+        .filter_map(|name| field_with(&field.attrs, name).map(|path| (name, path)));
+    for (i, (name, path)) in with.enumerate() {
+        push_separator(out, i);
+        push_str(out, name);
+        out.push(':');
+        push_str(out, &path.into_token_stream().to_string());
+    }
+
+    out.push_str("},\"bounds\":{");
+    let bounds = traits
+        .iter()
+        .filter_map(|name| field_bound(&field.attrs, name).map(|bounds| (name, bounds)));
+    for (i, (name, bounds)) in bounds.enumerate() {
+        push_separator(out, i);
+        push_str(out, name);
+        out.push(':');
+        push_bounds(out, Some(bounds));
+    }
+    out.push_str("}}");
+}
+
+fn input_bound<'a>(attrs: &'a attr::Input, name: &str) -> Option<&'a [syn::WherePredicate]> {
+    match name {
+        "AsMut" => attrs.as_mut_bound(),
+        "AsRef" => attrs.as_ref_bound(),
+        "Binary" => attrs.binary_bound(),
+        "Borrow" => attrs.borrow_bound(),
+        "BorrowMut" => attrs.borrow_mut_bound(),
+        "Builder" => attrs.builder_bound(),
+        "Clone" => attrs.clone_bound(),
+        "Copy" => attrs.copy_bound(),
+        "Debug" => attrs.debug_bound(),
+        "Default" => attrs.default_bound(),
+        "Deref" => attrs.deref_bound(),
+        "DerefMut" => attrs.deref_mut_bound(),
+        "Eq" => attrs.eq_bound(),
+        "Extend" => attrs.extend_bound(),
+        "From" => attrs.from_bound(),
+        "FromIterator" => attrs.from_iterator_bound(),
+        "Hash" => attrs.hash_bound(),
+        "Index" => attrs.index_bound(),
+        "IndexMut" => attrs.index_mut_bound(),
+        "LowerHex" => attrs.lower_hex_bound(),
+        "Octal" => attrs.octal_bound(),
+        "Ord" => attrs.ord_bound(),
+        "PartialEq" => attrs.partial_eq_bound(),
+        "PartialOrd" => attrs.partial_ord_bound(),
+        "Read" => attrs.read_bound(),
+        "TryFrom" => attrs.try_from_bound(),
+        "UpperHex" => attrs.upper_hex_bound(),
+        "Write" => attrs.write_bound(),
+        _ => attrs.extension(name).and_then(attr::InputExtension::bound),
+    }
+}
+
+/// The function used for the whole value by the trait `name`, if any.
+fn input_with<'a>(attrs: &'a attr::Input, name: &str) -> Option<&'a syn::Path> {
+    match name {
+        "Clone" => attrs.clone_with(),
+        "Debug" => attrs.debug_format_with(),
+        _ => None,
+    }
+}
+
+fn field_bound<'a>(attrs: &'a attr::Field, name: &str) -> Option<&'a [syn::WherePredicate]> {
+    match name {
+        "AsMut" => attrs.as_mut_bound(),
+        "AsRef" => attrs.as_ref_bound(),
+        "Clone" => attrs.clone_bound(),
+        "Copy" => attrs.copy_bound(),
+        "Debug" => attrs.debug_bound(),
+        "Default" => attrs.default_bound(),
+        "Eq" => attrs.eq_bound(),
+        "Hash" => attrs.hash_bound(),
+        "Ord" => attrs.ord_bound(),
+        "PartialEq" => attrs.partial_eq_bound(),
+        "PartialOrd" => attrs.partial_ord_bound(),
+        _ => attrs.extension(name).and_then(attr::FieldExtension::bound),
+    }
+}
+
+fn field_ignore(attrs: &attr::Field, name: &str) -> bool {
+    match name {
+        "Builder" => attrs.ignore_builder(),
+        "Debug" => attrs.ignore_debug(),
+        "Hash" => attrs.ignore_hash(),
+        "Ord" => attrs.ignore_ord(),
+        "PartialEq" => attrs.ignore_partial_eq(),
+        "PartialOrd" => attrs.ignore_partial_ord(),
+        "Clone" | "Copy" | "Default" | "Eq" => false,
+        _ => attrs.extension(name).is_some_and(attr::FieldExtension::ignore),
+    }
+}
+
+/// Whether the field, ignored by the trait `name`, is still shown on demand.
+fn field_exposed(input: &attr::Input, attrs: &attr::Field, name: &str) -> bool {
+    match name {
+        "Debug" => input.debug_verbose() && attrs.ignore_debug(),
+        _ => false,
+    }
+}
+
+/// The string shown instead of the field by the trait `name`, if it redacts it.
+fn field_mask<'a>(attrs: &'a attr::Field, name: &str) -> Option<&'a str> {
+    match name {
+        "Debug" => attrs.debug_mask(),
+        _ => None,
+    }
+}
+
+fn field_with<'a>(attrs: &'a attr::Field, name: &str) -> Option<&'a syn::Path> {
+    match name {
+        "Clone" => attrs.clone_with(),
+        "Debug" => attrs.debug_format_with(),
+        "Hash" => attrs.hash_with(),
+        "Ord" => attrs.ord_cmp_with(),
+        "PartialEq" => attrs.partial_eq_compare_with(),
+        "PartialOrd" => attrs.partial_ord_partial_cmp_with(),
+        "Builder" | "Copy" | "Default" | "Eq" => None,
+        _ => attrs.extension(name).and_then(attr::FieldExtension::with),
+    }
+}
+
+fn push_bounds(out: &mut String, bounds: Option<&[syn::WherePredicate]>) {
+    match bounds {
+        Some(bounds) => {
+            out.push('[');
+            for (i, bound) in bounds.iter().enumerate() {
+                push_separator(out, i);
+                push_str(out, &bound.into_token_stream().to_string());
+            }
+            out.push(']');
+        }
+        None => out.push_str("null"),
+    }
+}
+
+fn push_separator(out: &mut String, i: usize) {
+    if i != 0 {
+        out.push(',');
+    }
+}
+
+fn push_option_str(out: &mut String, s: Option<&str>) {
+    match s {
+        Some(s) => push_str(out, s),
+        None => out.push_str("null"),
+    }
+}
+
+fn push_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
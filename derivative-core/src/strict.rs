@@ -0,0 +1,331 @@
+//! The checks done on types marked `#[derivative(strict)]`: attributes that have no effect are
+//! reported as errors.
+
+use syn;
+
+use ast;
+use attr;
+use error::Error;
+
+/// Report the first attribute of `input` that has no effect.
+pub fn check(input: &ast::Input) -> Result<(), Error> {
+    if let Some(debug) = find(input.attrs.traits(), "Debug") {
+        let message = match input.body {
+            ast::Body::Struct(_, ref fields) if fields.len() != 1 => {
+                "`Debug(transparent)` has no effect on a type that does not have exactly one field"
+            }
+            ast::Body::Enum(ref variants) if variants.iter().all(|v| v.fields.len() != 1) => {
+                "`Debug(transparent)` has no effect on an enumeration without variants that have \
+                 exactly one field"
+            }
+            _ => "",
+        };
+        if input.attrs.debug_transparent() && !message.is_empty() {
+            return Err(Error::no_effect(message, debug.span()));
+        }
+        let ignores = input.body.all_fields().iter().any(|f| f.attrs.ignore_debug());
+        if input.attrs.debug_verbose() && !ignores {
+            return Err(Error::no_effect(
+                "`Debug(verbose_debug)` has no effect on a type that does not have ignored fields",
+                debug.span(),
+            ));
+        }
+        let overridden = [
+            ("transparent", input.attrs.debug_transparent()),
+            ("with_discriminant", input.attrs.debug_with_discriminant()),
+            ("non_exhaustive_hint", input.attrs.debug_non_exhaustive_hint()),
+            ("qualified", input.attrs.debug_qualified()),
+        ];
+        if let Some(&(option, _)) = overridden.iter().find(|&&(_, given)| given) {
+            if input.attrs.debug_format_with().is_some() {
+                return Err(Error::no_effect(
+                    format!(
+                        "`Debug({})` has no effect with `Debug(format_with)`, which formats the \
+                         whole value",
+                        option
+                    ),
+                    debug.span(),
+                ));
+            }
+        }
+        let has_phantom = input.body.all_fields().iter().any(|f| ast::is_phantom_data(f.ty));
+        if input.attrs.debug_hide_phantom() && !has_phantom {
+            return Err(Error::no_effect(
+                "`Debug(hide_phantom)` has no effect on a type that does not have `PhantomData` \
+                 fields",
+                debug.span(),
+            ));
+        }
+        if input.attrs.debug_non_exhaustive_hint() && !ignores {
+            return Err(Error::no_effect(
+                "`Debug(non_exhaustive_hint)` has no effect on a type that does not have ignored \
+                 fields",
+                debug.span(),
+            ));
+        }
+    }
+
+    for name in input.attrs.traits() {
+        let attrs = &input.attrs;
+        let variant_option = match name.to_string().as_ref() {
+            "PartialOrd" if attrs.partial_ord_order().is_some() => Some("order"),
+            "Ord" if attrs.ord_order().is_some() => Some("order"),
+            "Accessors" if attrs.accessors_rename().is_some() => Some("rename"),
+            "Debug" if attrs.debug_ignore() => Some("ignore"),
+            "Accessors" if attrs.accessors_skip() => Some("skip"),
+            "FromStr" if attrs.from_str_rename().is_some() => Some("rename"),
+            "FromStr" if !attrs.from_str_aliases().is_empty() => Some("alias"),
+            "VariantNames" if attrs.variant_names_rename().is_some() => Some("rename"),
+            _ => None,
+        };
+        if let Some(option) = variant_option {
+            return Err(Error::no_effect(
+                format!("`{}({})` has no effect on a type, use it on its variants", name, option),
+                name.span(),
+            ));
+        }
+    }
+
+    if let Some(ignore_types) = find(input.attrs.traits(), "ignore_types") {
+        ignores_types(input, ignore_types)?;
+    }
+
+    if let Some(bindgen) = find(input.attrs.traits(), "bindgen") {
+        if !input.body.all_fields().iter().any(|f| f.attrs.bindgen().is_some()) {
+            return Err(Error::no_effect(
+                "`bindgen` has no effect because no field is a bitfield or an anonymous union or \
+                 structure generated by bindgen",
+                bindgen.span(),
+            ));
+        }
+    }
+
+    if let Some(rename_all) = find(input.attrs.traits(), "rename_all") {
+        let message = match input.body {
+            ast::Body::Struct(..) => Some("`rename_all` has no effect on a structure"),
+            ast::Body::Enum(_)
+                if input.attrs.debug.is_none()
+                    && input.attrs.from_str.is_none()
+                    && input.attrs.variant_names.is_none() =>
+            {
+                Some(
+                    "`rename_all` has no effect because none of `Debug`, `FromStr` and \
+                     `VariantNames` is derived",
+                )
+            }
+            ast::Body::Enum(_) => None,
+        };
+        if let Some(message) = message {
+            return Err(Error::no_effect(message, rename_all.span()));
+        }
+    }
+
+    match input.body {
+        ast::Body::Enum(ref variants) => {
+            for variant in variants {
+                check_variant(input, variant)?;
+                check_fields(input, &variant.fields)?;
+            }
+        }
+        ast::Body::Struct(_, ref fields) => check_fields(input, fields)?,
+    }
+
+    Ok(())
+}
+
+fn check_variant(input: &ast::Input, variant: &ast::Variant) -> Result<(), Error> {
+    for name in variant.attrs.traits() {
+        if ["strict", "rename_all", "remote", "From", "TryFrom"].iter().any(|n| name == n) {
+            return Err(Error::no_effect(
+                format!("`{}` has no effect on a variant, use it on the type", name),
+                name.span(),
+            ));
+        }
+        if name == "ignore_types" {
+            ignores_types(input, name)?;
+            continue;
+        }
+        not_derived(input, name)?;
+        if name == "FromStr" && variant.attrs.from_str_error().is_some() {
+            return Err(Error::no_effect(
+                "`FromStr(error)` has no effect on a variant, use it on the type",
+                name.span(),
+            ));
+        }
+        if name == "Debug" && variant.attrs.debug_ignore() && variant.fields.is_empty() {
+            return Err(Error::no_effect(
+                "`Debug(ignore)` has no effect on a variant that does not have fields",
+                name.span(),
+            ));
+        }
+        if name == "Debug" && variant.attrs.debug_ignore() && variant.attrs.debug_transparent() {
+            return Err(Error::conflict(
+                "`Debug(ignore)` conflicts with `Debug(transparent)`: an ignored variant is \
+                 shown as its name",
+                name.span(),
+            ));
+        }
+        if name == "Debug" && variant.attrs.debug_transparent() && variant.fields.len() != 1 {
+            return Err(Error::no_effect(
+                "`Debug(transparent)` has no effect on a variant that does not have exactly one \
+                 field",
+                name.span(),
+            ));
+        }
+        let opts_out = variant.attrs.debug_transparent_setting() == Some(false);
+        if name == "Debug"
+            && opts_out
+            && (!input.attrs.debug_transparent()
+                || variant.fields.len() != 1
+                || variant.attrs.debug_ignore())
+        {
+            return Err(Error::no_effect(
+                "`Debug(transparent=\"false\")` has no effect on a variant that is not shown \
+                 transparently by its enumeration",
+                name.span(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_fields(input: &ast::Input, fields: &[ast::Field]) -> Result<(), Error> {
+    // The `clone_with` functions and the `ignore` of the fields are only used by `clone_from` then.
+    let clones_whole_value = input.attrs.clone_with().is_some()
+        && !input.attrs.clone_from()
+        && !input.body.all_fields().iter().any(|f| f.attrs.clone_from_with().is_some());
+
+    for field in fields {
+        let attrs = &field.attrs;
+
+        for name in attrs.traits() {
+            if name == "bound_all" {
+                continue;
+            }
+            // The default value of a field is also the one it is reset to by `Clone(ignore)`.
+            if name != "Default" || !attrs.ignore_clone() {
+                not_derived(input, name)?;
+            }
+
+            let clone_option = if attrs.clone_with().is_some() {
+                Some("clone_with")
+            } else if attrs.ignore_clone() {
+                Some("ignore")
+            } else {
+                None
+            };
+            if let Some(option) = clone_option.filter(|_| name == "Clone" && clones_whole_value) {
+                return Err(Error::no_effect(
+                    format!(
+                        "`Clone({})` has no effect on a field with `Clone(clone_with)` on the \
+                         type, which clones the whole value",
+                        option
+                    ),
+                    name.span(),
+                ));
+            }
+
+            let (ignored, options) = match name.to_string().as_ref() {
+                "Hash" => (attrs.ignore_hash(), vec![("deref", attrs.hash_deref())]),
+                "PartialEq" => (attrs.ignore_partial_eq(), vec![("deref", attrs.partial_eq_deref())]),
+                "PartialOrd" => (
+                    attrs.ignore_partial_ord(),
+                    vec![
+                        ("reverse", attrs.partial_ord_reverse()),
+                        ("priority", attrs.partial_ord_priority() != 0),
+                    ],
+                ),
+                "Ord" => (
+                    attrs.ignore_ord(),
+                    vec![("reverse", attrs.ord_reverse()), ("priority", attrs.ord_priority() != 0)],
+                ),
+                _ => continue,
+            };
+            if let Some(&(option, _)) = options.iter().find(|&&(_, set)| ignored && set) {
+                return Err(Error::no_effect(
+                    format!("`{}({})` has no effect on an ignored field", name, option),
+                    name.span(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report `ignore_types` if none of the traits it applies to is derived on `input`.
+fn ignores_types(input: &ast::Input, name: &syn::Ident) -> Result<(), Error> {
+    let attrs = &input.attrs;
+    if attrs.debug.is_some()
+        || attrs.hash.is_some()
+        || attrs.partial_eq.is_some()
+        || attrs.partial_ord.is_some()
+        || attrs.ord.is_some()
+    {
+        Ok(())
+    } else {
+        Err(Error::no_effect(
+            "`ignore_types` has no effect because none of `Debug`, `Hash`, `PartialEq`, \
+             `PartialOrd` and `Ord` is derived",
+            name.span(),
+        ))
+    }
+}
+
+/// Report `name` if the corresponding trait is not derived on `input`.
+fn not_derived(input: &ast::Input, name: &syn::Ident) -> Result<(), Error> {
+    if is_derived(&input.attrs, &name.to_string()) {
+        Ok(())
+    } else {
+        Err(Error::no_effect(
+            format!("this attribute has no effect because `{}` is not derived", name),
+            name.span(),
+        ))
+    }
+}
+
+fn is_derived(attrs: &attr::Input, name: &str) -> bool {
+    match name {
+        "Accessors" => attrs.accessors.is_some(),
+        "AsMut" => attrs.as_mut.is_some(),
+        "AsRef" => attrs.as_ref.is_some(),
+        "Borrow" => attrs.borrow.is_some(),
+        "BorrowMut" => attrs.borrow_mut.is_some(),
+        "Builder" => attrs.builder.is_some(),
+        "Binary" => attrs.binary.is_some(),
+        "Clone" => attrs.clone.is_some(),
+        "Copy" => attrs.copy.is_some(),
+        "Debug" => attrs.debug.is_some(),
+        // On a field, the default value is also the one of the builder.
+        "Default" => attrs.default.is_some() || attrs.builder.is_some(),
+        // On a field, `Deref` marks the target of both traits.
+        "Deref" => attrs.deref.is_some() || attrs.deref_mut.is_some(),
+        "DerefMut" => attrs.deref_mut.is_some(),
+        "Drop" => attrs.drop.is_some(),
+        "Eq" => attrs.eq.is_some(),
+        "Extend" => attrs.extend.is_some(),
+        "From" => attrs.from.is_some(),
+        "FromIterator" => attrs.from_iterator.is_some(),
+        "FromStr" => attrs.from_str.is_some(),
+        "Hash" => attrs.hash.is_some(),
+        // On a field, `Index="target"` marks the target of both traits.
+        "Index" => attrs.index.is_some() || attrs.index_mut.is_some(),
+        "IndexMut" => attrs.index_mut.is_some(),
+        "LowerHex" => attrs.lower_hex.is_some(),
+        "Octal" => attrs.octal.is_some(),
+        "Ord" => attrs.ord.is_some(),
+        "PartialEq" => attrs.partial_eq.is_some(),
+        "PartialOrd" => attrs.partial_ord.is_some(),
+        "Read" => attrs.read.is_some(),
+        "TryFrom" => attrs.try_from.is_some(),
+        "UpperHex" => attrs.upper_hex.is_some(),
+        "VariantNames" => attrs.variant_names.is_some(),
+        "Write" => attrs.write.is_some(),
+        _ => attrs.extension(name).is_some(),
+    }
+}
+
+fn find<'a>(traits: &'a [syn::Ident], name: &str) -> Option<&'a syn::Ident> {
+    traits.iter().find(|ident| *ident == name)
+}
@@ -0,0 +1,93 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use bound;
+use syn;
+
+/// Make generic with all the generics in the input, plus a bound `T: <trait_path>` for each
+/// generic field type that will be shown.
+pub fn build_impl_generics<F, G, H>(
+    item: &ast::Input,
+    trait_path: &syn::Path,
+    needs_debug_bound: F,
+    field_bound: G,
+    input_bound: H,
+) -> syn::Generics
+where
+    F: Fn(&attr::Field) -> bool,
+    G: Fn(&attr::Field) -> Option<&[syn::WherePredicate]>,
+    H: Fn(&attr::Input) -> Option<&[syn::WherePredicate]>,
+{
+    let generics = bound::without_defaults(item.generics);
+    let generics = bound::with_where_predicates_from_fields(item, &generics, field_bound);
+
+    match input_bound(&item.attrs) {
+        Some(predicates) => bound::with_where_predicates(&generics, predicates),
+        None => bound::with_bound(item, &generics, needs_debug_bound, trait_path),
+    }
+}
+
+/// Add to `generics` the bound `<F as Deref>::Target: <trait_path>` for the type `F` of each
+/// generic field used through its `Deref` target, that is for which `needs_deref_bound` returns
+/// true. Like the other inferred bounds, they are not added when the input has a custom bound.
+pub fn with_deref_bounds<F>(
+    item: &ast::Input,
+    generics: syn::Generics,
+    trait_path: &syn::Path,
+    deref_path: &syn::Path,
+    needs_deref_bound: F,
+    input_bound: Option<&[syn::WherePredicate]>,
+) -> syn::Generics
+where
+    F: Fn(&attr::Field) -> bool,
+{
+    match input_bound {
+        Some(_) => generics,
+        None => bound::with_deref_bound(item, &generics, needs_deref_bound, deref_path, trait_path),
+    }
+}
+
+/// Render `where_clause` for a `const` implementation of `trait_path`: its bounds on
+/// `trait_path` become `[const]` bounds, so that the implementation can call the trait's methods
+/// on the fields in a const context.
+pub fn const_where_clause(
+    where_clause: Option<&syn::WhereClause>,
+    trait_path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let where_clause = match where_clause {
+        Some(where_clause) if !where_clause.predicates.is_empty() => where_clause,
+        _ => return proc_macro2::TokenStream::new(),
+    };
+
+    let predicates = where_clause.predicates.iter().map(|predicate| match *predicate {
+        syn::WherePredicate::Type(ref predicate) => {
+            let lifetimes = &predicate.lifetimes;
+            let bounded_ty = &predicate.bounded_ty;
+            let bounds = predicate.bounds.iter().map(|bound| match *bound {
+                syn::TypeParamBound::Trait(ref bound) if bound.path == *trait_path => {
+                    quote!([const] #bound)
+                }
+                ref bound => quote!(#bound),
+            });
+            quote!(#lifetimes #bounded_ty: #(#bounds)+*)
+        }
+        ref predicate => quote!(#predicate),
+    });
+
+    quote!(where #(#predicates),*)
+}
+
+/// Construct a name for the inner type parameter that can't collide with any
+/// type parameters of the item. This is achieved by starting with a base and
+/// then concatenating the names of all other type parameters.
+pub fn hygienic_type_parameter(item: &ast::Input, base: &str) -> syn::Ident {
+    let mut typaram = String::with_capacity(150);
+    typaram.push_str(base);
+    let typaram = item.generics.type_params().fold(typaram, |mut acc, ty| {
+        acc.push_str(&format!("{}", &ty.ident));
+        acc
+    });
+
+    syn::Ident::new(&typaram, proc_macro2::Span::call_site())
+}
@@ -0,0 +1,43 @@
+extern crate derivative_core;
+
+use derivative_core::case::RenameRule;
+
+#[test]
+fn rename_variants() {
+    let renamed = |name, variant| {
+        RenameRule::from_name(name)
+            .unwrap()
+            .apply_to_variant(variant)
+    };
+
+    assert_eq!(renamed("lowercase", "ReadWrite"), "readwrite");
+    assert_eq!(renamed("UPPERCASE", "ReadWrite"), "READWRITE");
+    assert_eq!(renamed("PascalCase", "ReadWrite"), "ReadWrite");
+    assert_eq!(renamed("camelCase", "ReadWrite"), "readWrite");
+    assert_eq!(renamed("snake_case", "ReadWrite"), "read_write");
+    assert_eq!(renamed("SCREAMING_SNAKE_CASE", "ReadWrite"), "READ_WRITE");
+    assert_eq!(renamed("kebab-case", "ReadWrite"), "read-write");
+    assert_eq!(renamed("SCREAMING-KEBAB-CASE", "ReadWrite"), "READ-WRITE");
+    assert_eq!(renamed("snake_case", "A"), "a");
+}
+
+#[test]
+fn rename_fields() {
+    let renamed = |name, field| RenameRule::from_name(name).unwrap().apply_to_field(field);
+
+    assert_eq!(renamed("lowercase", "read_write"), "read_write");
+    assert_eq!(renamed("UPPERCASE", "read_write"), "READ_WRITE");
+    assert_eq!(renamed("PascalCase", "read_write"), "ReadWrite");
+    assert_eq!(renamed("camelCase", "read_write"), "readWrite");
+    assert_eq!(renamed("snake_case", "read_write"), "read_write");
+    assert_eq!(renamed("SCREAMING_SNAKE_CASE", "read_write"), "READ_WRITE");
+    assert_eq!(renamed("kebab-case", "read_write"), "read-write");
+    assert_eq!(renamed("SCREAMING-KEBAB-CASE", "read_write"), "READ-WRITE");
+    assert_eq!(renamed("camelCase", "a"), "a");
+}
+
+#[test]
+fn unknown_rule() {
+    assert_eq!(RenameRule::from_name("kebab"), None);
+    assert_eq!(RenameRule::from_name("Snake_Case"), None);
+}
@@ -0,0 +1,103 @@
+extern crate derivative_core;
+extern crate proc_macro2;
+extern crate syn;
+
+use derivative_core::{ast, Error};
+
+fn parse(input: &str) -> Result<(), Error> {
+    let item = syn::parse_str::<syn::DeriveInput>(input)?;
+    ast::Input::from_ast(&item).map(|_| ())
+}
+
+fn code(input: &str) -> &'static str {
+    parse(input).unwrap_err().code()
+}
+
+#[test]
+fn codes() {
+    assert_eq!(code("#[derivative(Foo)] struct Foo;"), "D0001");
+    assert_eq!(code("#[derivative(Debug(foo=\"bar\"))] struct Foo;"), "D0002");
+    assert_eq!(code("#[derivative(Debug=\"foo\")] struct Foo;"), "D0002");
+    assert_eq!(code("#[derivative(Debug(bound))] struct Foo;"), "D0003");
+    assert_eq!(code("struct Foo(#[derivative(Debug(ignore=\"no\"))] u8);"), "D0004");
+    assert_eq!(code("#[derivative(Debug(bound=42))] struct Foo;"), "D0005");
+    assert_eq!(code("#[derivative=\"Debug\"] struct Foo;"), "D0005");
+    assert_eq!(code("struct Foo(#[derivative(Default(value=\"1 +\"))] u8);"), "D0006");
+    assert_eq!(code("#[derivative(Debug)] union Foo { foo: u8 }"), "D0007");
+    assert_eq!(Error::io("foo", proc_macro2::Span::call_site()).code(), "D0008");
+    assert_eq!(code("struct Foo(#[derivative(Debug(ignore, format_with=\"f\"))] u8);"), "D0009");
+    assert_eq!(
+        code("#[derivative(strict)] struct Foo(#[derivative(Hash=\"ignore\")] u8);"),
+        "D0010"
+    );
+}
+
+#[test]
+fn messages() {
+    assert_eq!(
+        parse("#[derivative(Foo)] struct Foo;").unwrap_err().to_string(),
+        "unknown trait `Foo`"
+    );
+    assert_eq!(
+        parse("struct Foo(#[derivative(Debug(ignore=\"no\"))] u8);").unwrap_err().to_string(),
+        "Invalid value for `ignore`: `no`"
+    );
+    assert_eq!(
+        parse("struct Foo(#[derivative(Ord(priority=\"high\"))] u8);").unwrap_err().to_string(),
+        "Invalid value for `priority`: `high`"
+    );
+    assert_eq!(
+        parse("#[derivative(rename_all=\"kebab\")] enum Foo {}").unwrap_err().to_string(),
+        "Invalid value for `rename_all`: `kebab`"
+    );
+    assert_eq!(
+        parse("#[derivative(Debug(bound))] struct Foo;").unwrap_err().to_string(),
+        "`bound` needs a value"
+    );
+    assert_eq!(
+        parse("struct Foo(#[derivative(Default(required, value=\"1\"))] u8);")
+            .unwrap_err()
+            .to_string(),
+        "A `Default(required)` field can't have a default value"
+    );
+    assert_eq!(
+        parse("struct Foo(#[derivative(Default(value(cfg=\"unix\", windows=\"1\")))] u8);")
+            .unwrap_err()
+            .to_string(),
+        "Expected `value=\"…\"` after `cfg=\"…\"`"
+    );
+    assert_eq!(
+        parse("struct Foo(#[derivative(eq_hash(compare_with=\"eq\"))] u8);")
+            .unwrap_err()
+            .to_string(),
+        "`eq_hash` needs both `compare_with` and `hash_with`, so that equal values have the same \
+         hash"
+    );
+}
+
+#[test]
+fn ignored_with() {
+    assert_eq!(
+        parse("struct Foo(#[derivative(Debug(ignore, format_with=\"f\"))] u8);")
+            .unwrap_err()
+            .to_string(),
+        "`Debug(ignore)` conflicts with `Debug(format_with)`: the function is never called on an \
+         ignored field"
+    );
+    // The attributes can be split, and are checked whether the trait is derived or not.
+    assert_eq!(
+        code(
+            "struct Foo {
+                #[derivative(Hash=\"ignore\")]
+                #[derivative(Hash(hash_with=\"f\"))]
+                foo: u8,
+            }"
+        ),
+        "D0009"
+    );
+    assert!(parse(
+        "struct Foo(#[derivative(eq_hash(ignore, compare_with=\"f\", hash_with=\"g\"))] u8);"
+    )
+    .is_err());
+    assert!(parse("struct Foo(#[derivative(Debug(ignore), Hash(hash_with=\"f\"))] u8);").is_ok());
+}
@@ -0,0 +1,148 @@
+//! This tests that an external derive can reuse derivative's parsing and bound inference.
+
+extern crate derivative_core;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+use derivative_core::{ast, generator, matcher, utils, Error, TraitGenerator};
+
+/// A toy `Describe` derive, listing the fields that are not ignored.
+struct DescribeGenerator;
+
+impl TraitGenerator for DescribeGenerator {
+    fn name(&self) -> &'static str {
+        "Describe"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("Describe").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_describe(input)
+    }
+}
+
+fn derive_describe(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let trait_path: syn::Path = parse_quote!(Describe);
+
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
+        input,
+        |_, _, _, _, bis| {
+            let fields = bis.iter().filter_map(|bi| {
+                let extension = bi.field.attrs.extension("Describe");
+                if extension.is_some_and(|e| e.ignore()) {
+                    return None;
+                }
+
+                let arg = &bi.ident;
+                Some(match extension.and_then(|e| e.with()) {
+                    Some(with) => quote!(#with(#arg)),
+                    None => quote!(Describe::describe(#arg)),
+                })
+            });
+
+            quote!(vec![#(#fields),*])
+        },
+    );
+
+    let generics = utils::build_impl_generics(
+        input,
+        &trait_path,
+        |field| {
+            field
+                .extension("Describe")
+                .map_or(true, |e| !e.ignore() && e.bound().is_none())
+        },
+        |field| field.extension("Describe").and_then(|e| e.bound()),
+        |input| input.extension("Describe").and_then(|e| e.bound()),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            fn describe(&self) -> Vec<String> {
+                match *self {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+fn expand(input: &str) -> Result<String, Error> {
+    let item = syn::parse_str::<syn::DeriveInput>(input)?;
+    let input = ast::Input::from_ast_with_extensions(&item, "derivative", &["Describe"])?;
+    generator::generate_all(&input, &[&DescribeGenerator]).map(|tokens| tokens.to_string())
+}
+
+fn tokens(tokens: proc_macro2::TokenStream) -> String {
+    tokens.to_string()
+}
+
+#[test]
+fn field_attributes() {
+    let output = expand(
+        "#[derivative(Describe)]
+         struct Foo<T, U> {
+             foo: T,
+             #[derivative(Describe=\"ignore\")]
+             bar: U,
+             #[derivative(Describe(describe_with=\"describe_u8\"))]
+             baz: u8,
+         }",
+    ).unwrap();
+
+    let expected = tokens(quote! {
+        impl<T, U> Describe for Foo<T, U> where T: Describe {
+            fn describe(&self) -> Vec<String> {
+                match *self {
+                    Foo { foo: ref __arg_0, bar: ref __arg_1, baz: ref __arg_2, } => {
+                        vec![Describe::describe(__arg_0), describe_u8(__arg_2)]
+                    }
+                }
+            }
+        }
+    });
+
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn container_bound() {
+    let output = expand(
+        "#[derivative(Describe(bound=\"T: Clone\"))]
+         struct Foo<T>(T);",
+    ).unwrap();
+
+    assert!(output.contains(&tokens(quote!(where T: Clone))));
+}
+
+#[test]
+fn builtin_traits_still_parsed() {
+    let item = syn::parse_str::<syn::DeriveInput>(
+        "#[derivative(Debug, Describe)] struct Foo(#[derivative(Debug=\"ignore\")] u8);",
+    ).unwrap();
+    let input = ast::Input::from_ast_with_extensions(&item, "derivative", &["Describe"]).unwrap();
+
+    assert!(input.attrs.debug.is_some());
+    assert!(input.attrs.extension("Describe").is_some());
+    assert!(input.body.all_fields()[0].attrs.ignore_debug());
+}
+
+#[test]
+fn not_requested() {
+    assert_eq!(expand("#[derivative(Debug)] struct Foo;").unwrap(), "");
+}
+
+#[test]
+fn unknown_extension() {
+    match expand("#[derivative(Other)] struct Foo;") {
+        Err(Error::UnknownTrait { ref name, .. }) if name == "Other" => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
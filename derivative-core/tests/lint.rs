@@ -0,0 +1,84 @@
+extern crate derivative_core;
+extern crate syn;
+
+use derivative_core::{ast, lint};
+
+fn warnings(input: &str) -> Vec<String> {
+    let item = syn::parse_str::<syn::DeriveInput>(input).unwrap();
+    let input = ast::Input::from_ast(&item).unwrap();
+    lint::check(&input)
+        .iter()
+        .map(|warning| warning.message().to_string())
+        .collect()
+}
+
+#[test]
+fn std_derive() {
+    assert_eq!(
+        warnings("#[derivative(Clone, Debug(bound=\"\"))] struct Foo<T> { foo: T }"),
+        vec!["`derivative(Clone)` does the same as `#[derive(Clone)]`, which should be used instead"]
+    );
+
+    assert!(warnings("#[derivative(Debug)] struct Foo<T> { foo: PhantomData<T> }").is_empty());
+    assert!(warnings("#[derivative(Debug)] struct Foo(#[derivative(Debug=\"ignore\")] u8);").is_empty());
+    assert!(warnings("#[derivative(Default)] enum Foo { #[derivative(Default)] A }").is_empty());
+    assert!(warnings("#[derivative(Default(new))] struct Foo;").is_empty());
+    assert!(warnings("#[derivative(Clone(clone_from))] struct Foo;").is_empty());
+    assert!(warnings("#[derivative(Debug, rename_all=\"snake_case\")] enum Foo { A }").is_empty());
+}
+
+#[test]
+fn unneeded_bound() {
+    assert_eq!(
+        warnings("#[derivative(Default(new))] struct Stack<T> { items: Vec<T> }"),
+        vec![
+            "the inferred bound `T: Default` is not needed by the fields, but `Default` is only \
+             implemented when `T: Default`; use `Default(bound=\"\")` to remove it"
+        ]
+    );
+    assert_eq!(
+        warnings("#[derivative(Clone(clone_from))] struct Foo<'a, T> { foo: &'a T, bar: Rc<T> }")
+            .len(),
+        1
+    );
+
+    assert!(warnings("#[derivative(Clone(clone_from))] struct Foo<'a, T> { foo: &'a mut T }")
+        .is_empty());
+    assert!(warnings("#[derivative(Default(new))] struct Foo<T> { foo: Vec<T>, bar: T }")
+        .is_empty());
+    assert!(warnings("#[derivative(Default(bound=\"\"))] struct Stack<T> { items: Vec<T> }")
+        .is_empty());
+}
+
+#[test]
+fn unsized_bound() {
+    let expected = vec![
+        "`T` is `?Sized` but the inferred bound `T: Clone` requires it to be sized, so `Clone` is \
+         only implemented for sized `T`; use `Clone(bound=\"…\")` to give the bounds",
+    ];
+
+    assert_eq!(
+        warnings("#[derivative(Clone(clone_from))] struct Foo<T: ?Sized> { foo: Box<T> }"),
+        expected
+    );
+    assert_eq!(
+        warnings(
+            "#[derivative(Clone(clone_from))] struct Foo<T> where T: ?Sized { foo: Box<T> }"
+        ),
+        expected
+    );
+    assert!(warnings("#[derivative(Debug(bound=\"\"))] struct Foo<T: ?Sized> { foo: Box<T> }")
+        .is_empty());
+}
+
+#[test]
+fn kinds() {
+    let item = syn::parse_str::<syn::DeriveInput>(
+        "#[derivative(Clone, Default(new))] struct Stack<T> { items: Vec<T> }",
+    )
+    .unwrap();
+    let input = ast::Input::from_ast(&item).unwrap();
+    let lints: Vec<_> = lint::check(&input).iter().map(lint::Warning::lint).collect();
+
+    assert_eq!(lints, vec![lint::Lint::StdDerive, lint::Lint::InferredBounds]);
+}
@@ -0,0 +1,130 @@
+extern crate derivative_core;
+extern crate proc_macro2;
+extern crate syn;
+
+use derivative_core::{ast, report, Error, TraitGenerator};
+
+/// Stands for the `Debug` and `Hash` generators of *derivative*, only their names matter here.
+struct Stub(&'static str);
+
+impl TraitGenerator for Stub {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        match self.0 {
+            "Debug" => input.attrs.debug.is_some(),
+            "Hash" => input.attrs.hash.is_some(),
+            _ => false,
+        }
+    }
+
+    fn generate(&self, _: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        Ok(proc_macro2::TokenStream::new())
+    }
+}
+
+fn report(input: &str) -> String {
+    let item = syn::parse_str::<syn::DeriveInput>(input).unwrap();
+    let input = ast::Input::from_ast(&item).unwrap();
+    report::to_json(Some("foo"), &input, &[&Stub("Debug"), &Stub("Hash"), &Stub("Clone")])
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        report(
+            "#[derivative(Debug(bound=\"T: Debug\"), Hash)]
+            struct Credentials<T> {
+                user: T,
+                #[derivative(Debug=\"ignore\", Hash(hash_with=\"hash_secret\"))]
+                password: String,
+            }"
+        ),
+        concat!(
+            r#"{"crate":"foo","type":"Credentials","traits":["Debug","Hash"],"#,
+            r#""bounds":{"Debug":["T : Debug"],"Hash":null},"with":{},"#,
+            r#""fields":["#,
+            r#"{"variant":null,"field":"user","ignored":[],"exposed":[],"masked":{},"with":{},"#,
+            r#""bounds":{}},"#,
+            r#"{"variant":null,"field":"password","ignored":["Debug"],"#,
+            r#""exposed":[],"masked":{},"with":{"Hash":"hash_secret"},"bounds":{}}]}"#,
+        )
+    );
+
+    assert_eq!(
+        report(
+            "#[derivative(Debug)]
+            enum Foo {
+                A(u8, #[derivative(Debug(bound=\"\"))] u8),
+                B(u8),
+            }"
+        ),
+        concat!(
+            r#"{"crate":"foo","type":"Foo","traits":["Debug"],"bounds":{"Debug":null},"#,
+            r#""with":{},"fields":["#,
+            r#"{"variant":"A","field":"0","ignored":[],"exposed":[],"masked":{},"with":{},"#,
+            r#""bounds":{}},"#,
+            r#"{"variant":"A","field":"1","ignored":[],"exposed":[],"masked":{},"with":{},"#,
+            r#""bounds":{"Debug":[]}},"#,
+            r#"{"variant":"B","field":"0","ignored":[],"exposed":[],"masked":{},"with":{},"#,
+            r#""bounds":{}}]}"#,
+        )
+    );
+
+    assert_eq!(
+        report(
+            "#[derivative(Debug=\"verbose_debug\")]
+            struct Credentials {
+                #[derivative(Debug=\"ignore\")]
+                password: String,
+            }"
+        ),
+        concat!(
+            r#"{"crate":"foo","type":"Credentials","traits":["Debug"],"#,
+            r#""bounds":{"Debug":null},"with":{},"fields":["#,
+            r#"{"variant":null,"field":"password","ignored":["Debug"],"exposed":["Debug"],"#,
+            r#""masked":{},"with":{},"bounds":{}}]}"#,
+        )
+    );
+    assert_eq!(
+        report(
+            "#[derivative(Debug)]
+            struct Credentials {
+                #[derivative(Debug(mask))]
+                password: String,
+                #[derivative(Debug(mask=\"<token>\"))]
+                token: String,
+            }"
+        ),
+        concat!(
+            r#"{"crate":"foo","type":"Credentials","traits":["Debug"],"#,
+            r#""bounds":{"Debug":null},"with":{},"fields":["#,
+            r#"{"variant":null,"field":"password","ignored":[],"exposed":[],"#,
+            r#""masked":{"Debug":"***"},"with":{},"bounds":{}},"#,
+            r#"{"variant":null,"field":"token","ignored":[],"exposed":[],"#,
+            r#""masked":{"Debug":"<token>"},"with":{},"bounds":{}}]}"#,
+        )
+    );
+
+    assert_eq!(
+        report(
+            "#[derivative(Debug(format_with=\"show_credentials\"), Hash)]
+            struct Credentials {
+                user: String,
+                #[derivative(Debug=\"ignore\", Hash=\"ignore\")]
+                password: String,
+            }"
+        ),
+        concat!(
+            r#"{"crate":"foo","type":"Credentials","traits":["Debug","Hash"],"#,
+            r#""bounds":{"Debug":null,"Hash":null},"with":{"Debug":"show_credentials"},"#,
+            r#""fields":["#,
+            r#"{"variant":null,"field":"user","ignored":[],"exposed":[],"masked":{},"with":{},"#,
+            r#""bounds":{}},"#,
+            r#"{"variant":null,"field":"password","ignored":["Hash"],"exposed":[],"masked":{},"#,
+            r#""with":{},"bounds":{}}]}"#,
+        )
+    );
+}
@@ -0,0 +1,324 @@
+extern crate derivative_core;
+extern crate syn;
+
+use derivative_core::ast;
+
+fn check(input: &str) -> Result<(), String> {
+    let item = syn::parse_str::<syn::DeriveInput>(input).unwrap();
+    ast::Input::from_ast(&item)
+        .map(|_| ())
+        .map_err(|e| format!("{} [{}]", e, e.code()))
+}
+
+#[test]
+fn lenient_by_default() {
+    assert!(check("struct Foo(#[derivative(Debug=\"ignore\")] u8);").is_ok());
+}
+
+#[test]
+fn not_derived() {
+    assert_eq!(
+        check("#[derivative(strict, Debug)] struct Foo(#[derivative(Hash=\"ignore\")] u8);"),
+        Err("this attribute has no effect because `Hash` is not derived [D0010]".to_string())
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug)] enum Foo { #[derivative(Default)] A }"),
+        Err("this attribute has no effect because `Default` is not derived [D0010]".to_string())
+    );
+    assert!(check(
+        "#[derivative(strict=\"false\", Debug)] struct Foo(#[derivative(Hash=\"ignore\")] u8);"
+    )
+    .is_ok());
+}
+
+#[test]
+fn transparent() {
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"transparent\")] struct Foo(u8, u8);"),
+        Err(
+            "`Debug(transparent)` has no effect on a type that does not have exactly one field \
+             [D0010]"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug)] enum Foo { #[derivative(Debug=\"transparent\")] A }"),
+        Err(
+            "`Debug(transparent)` has no effect on a variant that does not have exactly one field \
+             [D0010]"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"transparent\")] enum Foo { A, B(u8, u8) }"),
+        Err(
+            "`Debug(transparent)` has no effect on an enumeration without variants that have \
+             exactly one field [D0010]"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        check(
+            "#[derivative(strict, Debug)] enum Foo { \
+             #[derivative(Debug(transparent=\"false\"))] A(u8) }"
+        ),
+        Err(
+            "`Debug(transparent=\"false\")` has no effect on a variant that is not shown \
+             transparently by its enumeration [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug=\"transparent\")] enum Foo { \
+         A(u8), #[derivative(Debug(transparent=\"false\"))] B(u8), C }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn ignore_variant() {
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"ignore\")] enum Foo { A(u8) }"),
+        Err("`Debug(ignore)` has no effect on a type, use it on its variants [D0010]".to_string())
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug)] enum Foo { #[derivative(Debug=\"ignore\")] A }"),
+        Err(
+            "`Debug(ignore)` has no effect on a variant that does not have fields [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug=\"non_exhaustive_hint\")] enum Foo { \
+         #[derivative(Debug=\"ignore\")] A(u8) }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn container_format_with() {
+    assert_eq!(
+        check("#[derivative(strict, Debug(format_with=\"f\", transparent))] struct Foo(u8);"),
+        Err(
+            "`Debug(transparent)` has no effect with `Debug(format_with)`, which formats the \
+             whole value [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check("#[derivative(strict, Debug(format_with=\"f\"))] struct Foo(u8);").is_ok());
+}
+
+#[test]
+fn container_clone_with() {
+    assert_eq!(
+        check(
+            "#[derivative(strict, Clone(clone_with=\"f\"))]
+            struct Foo(#[derivative(Clone(clone_with=\"g\"))] u8);"
+        ),
+        Err(
+            "`Clone(clone_with)` has no effect on a field with `Clone(clone_with)` on the type, \
+             which clones the whole value [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Clone(clone_with=\"f\", clone_from))]
+        struct Foo(#[derivative(Clone(clone_with=\"g\"))] u8);"
+    )
+    .is_ok());
+    assert_eq!(
+        check(
+            "#[derivative(strict, Clone(clone_with=\"f\"))]
+            struct Foo(#[derivative(Clone=\"ignore\")] u8);"
+        ),
+        Err(
+            "`Clone(ignore)` has no effect on a field with `Clone(clone_with)` on the type, \
+             which clones the whole value [D0010]"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn clone_ignore_default() {
+    assert!(check(
+        "#[derivative(strict, Clone)]
+        struct Foo(#[derivative(Clone=\"ignore\", Default(value=\"1\"))] u8);"
+    )
+    .is_ok());
+    assert_eq!(
+        check(
+            "#[derivative(strict, Clone)]
+            struct Foo(#[derivative(Default(value=\"1\"))] u8);"
+        ),
+        Err("this attribute has no effect because `Default` is not derived [D0010]".to_string())
+    );
+}
+
+#[test]
+fn non_exhaustive_hint() {
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"non_exhaustive_hint\")] struct Foo(u8);"),
+        Err(
+            "`Debug(non_exhaustive_hint)` has no effect on a type that does not have ignored \
+             fields [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug=\"non_exhaustive_hint\")] \
+         struct Foo(u8, #[derivative(Debug=\"ignore\")] u8);"
+    )
+    .is_ok());
+}
+
+#[test]
+fn verbose_debug() {
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"verbose_debug\")] struct Foo(u8);"),
+        Err(
+            "`Debug(verbose_debug)` has no effect on a type that does not have ignored fields \
+             [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug=\"verbose_debug\")]
+        struct Foo(u8, #[derivative(Debug=\"ignore\")] u8);"
+    )
+    .is_ok());
+}
+
+#[test]
+fn hide_phantom() {
+    assert_eq!(
+        check("#[derivative(strict, Debug=\"hide_phantom\")] struct Foo<T>(T);"),
+        Err(
+            "`Debug(hide_phantom)` has no effect on a type that does not have `PhantomData` \
+             fields [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug=\"hide_phantom\")]
+        struct Foo<T>(u8, std::marker::PhantomData<T>);"
+    )
+    .is_ok());
+}
+
+#[test]
+fn ignored_deref() {
+    assert_eq!(
+        check(
+            "#[derivative(strict, PartialEq)]
+            struct Foo(#[derivative(PartialEq(ignore, deref))] Box<str>);"
+        ),
+        Err("`PartialEq(deref)` has no effect on an ignored field [D0010]".to_string())
+    );
+    assert_eq!(
+        check(
+            "#[derivative(strict, Ord)]
+            struct Foo(#[derivative(Ord(ignore, priority=\"1\"))] u8);"
+        ),
+        Err("`Ord(priority)` has no effect on an ignored field [D0010]".to_string())
+    );
+}
+
+#[test]
+fn order_on_type() {
+    assert_eq!(
+        check("#[derivative(strict, Ord(order=\"1\"))] struct Foo;"),
+        Err("`Ord(order)` has no effect on a type, use it on its variants [D0010]".to_string())
+    );
+    assert!(check(
+        "#[derivative(strict, Ord=\"feature_allow_slow_enum\")]
+        enum Foo { #[derivative(Ord(order=\"1\"))] A, #[derivative(Ord(order=\"0\"))] B }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn from_str_options() {
+    assert_eq!(
+        check("#[derivative(strict, FromStr(alias=\"a\"))] enum Foo { A }"),
+        Err("`FromStr(alias)` has no effect on a type, use it on its variants [D0010]".to_string())
+    );
+    assert_eq!(
+        check("#[derivative(strict, FromStr)] enum Foo { #[derivative(FromStr(error=\"E\"))] A }"),
+        Err("`FromStr(error)` has no effect on a variant, use it on the type [D0010]".to_string())
+    );
+}
+
+#[test]
+fn strict_on_variant() {
+    assert_eq!(
+        check("#[derivative(Debug)] enum Foo { #[derivative(strict)] A }"),
+        Ok(())
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug)] enum Foo { #[derivative(strict)] A }"),
+        Err("`strict` has no effect on a variant, use it on the type [D0010]".to_string())
+    );
+}
+
+#[test]
+fn ignore_types() {
+    assert_eq!(
+        check("#[derivative(strict, Clone, ignore_types(PhantomData))] struct Foo(u8);"),
+        Err(
+            "`ignore_types` has no effect because none of `Debug`, `Hash`, `PartialEq`, \
+             `PartialOrd` and `Ord` is derived [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug)] enum Foo { #[derivative(ignore_types(PhantomData))] A }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn bindgen() {
+    assert_eq!(
+        check("#[derivative(strict, Debug, bindgen=\"skip\")] struct Foo { a: u8 }"),
+        Err(
+            "`bindgen` has no effect because no field is a bitfield or an anonymous union or \
+             structure generated by bindgen [D0010]"
+                .to_string()
+        )
+    );
+    assert!(check(
+        "#[derivative(strict, Debug, bindgen=\"opaque\")]
+        struct Foo { _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]> }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn rename_all() {
+    assert_eq!(
+        check("#[derivative(strict, Debug, rename_all=\"snake_case\")] struct Foo;"),
+        Err("`rename_all` has no effect on a structure [D0010]".to_string())
+    );
+    assert_eq!(
+        check("#[derivative(strict, Clone, rename_all=\"snake_case\")] enum Foo { A }"),
+        Err(
+            "`rename_all` has no effect because none of `Debug`, `FromStr` and `VariantNames` is \
+             derived [D0010]"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        check("#[derivative(strict, Debug)] enum Foo { #[derivative(rename_all=\"snake_case\")] A }"),
+        Err("`rename_all` has no effect on a variant, use it on the type [D0010]".to_string())
+    );
+    assert!(check("#[derivative(strict, FromStr, rename_all=\"snake_case\")] enum Foo { A }").is_ok());
+}
+
+#[test]
+fn conversion_on_variant() {
+    assert_eq!(
+        check("#[derivative(strict, TryFrom)] enum Foo { #[derivative(TryFrom(error=\"E\"))] A }"),
+        Err("`TryFrom` has no effect on a variant, use it on the type [D0010]".to_string())
+    );
+}
@@ -0,0 +1,231 @@
+use proc_macro2;
+use quote::ToTokens;
+
+use ast;
+use bound;
+use case::RenameRule;
+use matcher;
+use syn;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator of the `is_<variant>`, `as_<variant>`, `as_<variant>_mut` and `into_<variant>`
+/// methods of an enumeration.
+///
+/// The variants without fields only get `is_<variant>`, and the variants marked
+/// `Accessors="skip"` get no method.
+pub struct AccessorsGenerator;
+
+impl TraitGenerator for AccessorsGenerator {
+    fn name(&self) -> &'static str {
+        "Accessors"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.accessors.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let variants = match input.body {
+        ast::Body::Enum(ref variants) => variants,
+        ast::Body::Struct(..) => {
+            return Err(Error::unsupported(
+                "`Accessors` can only be derived on enumerations",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let vis = input.vis;
+    let option_path = option_path();
+    let result_path = result_path();
+    // With a single variant, the other arms would be unreachable.
+    let has_other_variants = variants.len() > 1;
+
+    let mut names: Vec<(String, &syn::Ident)> = Vec::new();
+    let mut methods = Vec::new();
+    for variant in variants {
+        if variant.attrs.accessors_skip() {
+            continue;
+        }
+
+        let variant_ident = &variant.ident;
+        let method_name = match variant.attrs.accessors_rename() {
+            Some(rename) => rename.to_string(),
+            None => RenameRule::SnakeCase.apply_to_variant(&variant_ident.to_string()),
+        };
+        if let Some(&(_, other)) = names.iter().find(|&(n, _)| *n == method_name) {
+            return Err(Error::malformed(
+                format!(
+                    "the accessors of this variant have the same names as those of `{}`, use \
+                     `Accessors(rename=\"…\")` to rename them",
+                    other
+                ),
+                variant_ident.span(),
+            ));
+        }
+        names.push((method_name.clone(), variant_ident));
+
+        let variant_path = quote!(#name::#variant_ident);
+        let is_ident = method_ident("is_", &method_name, "");
+        let is_doc = format!("Returns `true` if the value is `{}::{}`.", name, variant_ident);
+        let other_false = if has_other_variants {
+            Some(quote!(_ => false,))
+        } else {
+            None
+        };
+        methods.push(quote! {
+            #[doc = #is_doc]
+            #[inline]
+            #[must_use]
+            #vis fn #is_ident(&self) -> bool {
+                match *self {
+                    #variant_path { .. } => true,
+                    #other_false
+                }
+            }
+        });
+
+        if variant.fields.is_empty() {
+            continue;
+        }
+
+        let field_tys: Vec<_> = variant.fields.iter().map(|f| f.ty).collect();
+        let other_none = if has_other_variants {
+            Some(quote!(_ => #option_path::None,))
+        } else {
+            None
+        };
+
+        let (pat, bis) = matcher::Matcher::new(matcher::BindingStyle::Ref).build_match_pattern(
+            &variant_path,
+            variant.style,
+            &variant.fields,
+        );
+        let as_ident = method_ident("as_", &method_name, "");
+        let as_doc = format!(
+            "Returns references to the fields of `{}::{}`, or `None` if the value is another \
+             variant.",
+            name, variant_ident
+        );
+        let ty = tuple(field_tys.iter().map(|ty| quote!(&#ty)));
+        let value = tuple(bis.iter().map(|bi| &bi.ident));
+        methods.push(quote! {
+            #[doc = #as_doc]
+            #[inline]
+            #[must_use]
+            #vis fn #as_ident(&self) -> #option_path<#ty> {
+                match *self {
+                    #pat => #option_path::Some(#value),
+                    #other_none
+                }
+            }
+        });
+
+        let (pat, bis) = matcher::Matcher::new(matcher::BindingStyle::RefMut)
+            .build_match_pattern(&variant_path, variant.style, &variant.fields);
+        let as_mut_ident = method_ident("as_", &method_name, "_mut");
+        let as_mut_doc = format!(
+            "Returns mutable references to the fields of `{}::{}`, or `None` if the value is \
+             another variant.",
+            name, variant_ident
+        );
+        let ty = tuple(field_tys.iter().map(|ty| quote!(&mut #ty)));
+        let value = tuple(bis.iter().map(|bi| &bi.ident));
+        methods.push(quote! {
+            #[doc = #as_mut_doc]
+            #[inline]
+            #vis fn #as_mut_ident(&mut self) -> #option_path<#ty> {
+                match *self {
+                    #pat => #option_path::Some(#value),
+                    #other_none
+                }
+            }
+        });
+
+        let (pat, bis) = matcher::Matcher::new(matcher::BindingStyle::Move).build_match_pattern(
+            &variant_path,
+            variant.style,
+            &variant.fields,
+        );
+        let into_ident = method_ident("into_", &method_name, "");
+        let into_doc = format!(
+            "Returns the fields of `{}::{}`, or the value itself if it is another variant.",
+            name, variant_ident
+        );
+        let ty = tuple(field_tys.iter().map(|ty| quote!(#ty)));
+        let value = tuple(bis.iter().map(|bi| &bi.ident));
+        let other_err = if has_other_variants {
+            Some(quote!(__other => #result_path::Err(__other),))
+        } else {
+            None
+        };
+        methods.push(quote! {
+            #[doc = #into_doc]
+            #[inline]
+            #[must_use]
+            #vis fn #into_ident(self) -> #result_path<#ty, Self> {
+                match self {
+                    #pat => #result_path::Ok(#value),
+                    #other_err
+                }
+            }
+        });
+    }
+
+    let generics = bound::without_defaults(input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #impl_attrs
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Return the identifier `<prefix><name><suffix>`, eg. `as_foo_mut`.
+fn method_ident(prefix: &str, name: &str, suffix: &str) -> syn::Ident {
+    syn::Ident::new(
+        &format!("{}{}{}", prefix, name, suffix),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+/// Return the tuple of `items`, or the item itself if there is only one.
+fn tuple<I, T>(items: I) -> proc_macro2::TokenStream
+where
+    I: IntoIterator<Item = T>,
+    T: ToTokens,
+{
+    let items: Vec<_> = items.into_iter().collect();
+    match *items.as_slice() {
+        [ref item] => quote!(#item),
+        ref items => quote!((#(#items),*)),
+    }
+}
+
+/// Return the path of the `Option` type, that is `::std::option::Option`.
+fn option_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::option::Option)
+    } else {
+        parse_quote!(::std::option::Option)
+    }
+}
+
+/// Return the path of the `Result` type, that is `::std::result::Result`.
+fn result_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::result::Result)
+    } else {
+        parse_quote!(::std::result::Result)
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use proc_macro2;
+
+use ast;
+use attr;
+use bound;
+use matcher;
+use syn;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `approx::AbsDiffEq`.
+pub struct AbsDiffEqGenerator;
+
+/// The generator for `approx::RelativeEq`, which also needs `AbsDiffEq`.
+pub struct RelativeEqGenerator;
+
+impl TraitGenerator for AbsDiffEqGenerator {
+    fn name(&self) -> &'static str {
+        "AbsDiffEq"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("AbsDiffEq").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_abs_diff_eq(input)
+    }
+}
+
+impl TraitGenerator for RelativeEqGenerator {
+    fn name(&self) -> &'static str {
+        "RelativeEq"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("RelativeEq").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_relative_eq(input)
+    }
+}
+
+fn derive_abs_diff_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let abs_diff_eq_trait_path: syn::Path = parse_quote!(::approx::AbsDiffEq);
+    let epsilon_field_ty = epsilon_field_ty(input, "AbsDiffEq")?;
+    let epsilon_ty = epsilon_ty(input, epsilon_field_ty);
+
+    let body = compare_fields(input, "AbsDiffEq", |a, b, epsilon, with| match with {
+        Some(with) => quote!(#with(#a, #b, #epsilon)),
+        None => quote!(#abs_diff_eq_trait_path::abs_diff_eq(#a, #b, #epsilon)),
+    });
+
+    let name = &input.ident;
+    let generics = approx_generics(input, "AbsDiffEq", &epsilon_ty, |_| None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #abs_diff_eq_trait_path for #name #ty_generics #where_clause {
+            type Epsilon = #epsilon_ty;
+
+            fn default_epsilon() -> Self::Epsilon {
+                <#epsilon_field_ty as #abs_diff_eq_trait_path>::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                match *self {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+fn derive_relative_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let relative_eq_trait_path: syn::Path = parse_quote!(::approx::RelativeEq);
+    let clone_trait_path = clone_trait_path();
+    let epsilon_field_ty = epsilon_field_ty(input, "RelativeEq")?;
+    let epsilon_ty = epsilon_ty(input, epsilon_field_ty);
+
+    let body = compare_fields(input, "RelativeEq", |a, b, epsilon, with| match with {
+        Some(with) => quote!(#with(#a, #b, #epsilon, #clone_trait_path::clone(&max_relative))),
+        None => quote! {
+            #relative_eq_trait_path::relative_eq(
+                #a,
+                #b,
+                #epsilon,
+                #clone_trait_path::clone(&max_relative),
+            )
+        },
+    });
+
+    let name = &input.ident;
+    let generics = approx_generics(input, "RelativeEq", &epsilon_ty, |ty| {
+        Some(parse_quote!(#ty: #relative_eq_trait_path))
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #relative_eq_trait_path for #name #ty_generics #where_clause {
+            fn default_max_relative() -> Self::Epsilon {
+                <#epsilon_field_ty as #relative_eq_trait_path>::default_max_relative()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                match *self {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+/// Build the arms comparing the fields of `self` and `other`, with `compare(a, b, epsilon, with)`
+/// for the fields compared by the trait `name` or by their `compare_with` function for `name`.
+fn compare_fields<F>(input: &ast::Input, name: &str, compare: F) -> proc_macro2::TokenStream
+where
+    F: Fn(
+        &syn::Ident,
+        &syn::Ident,
+        &proc_macro2::TokenStream,
+        Option<&syn::Path>,
+    ) -> proc_macro2::TokenStream,
+{
+    let clone_trait_path = clone_trait_path();
+    let variants: HashMap<&syn::Ident, &ast::Variant> = match input.body {
+        ast::Body::Enum(ref variants) => variants.iter().map(|v| (&v.ident, v)).collect(),
+        ast::Body::Struct(..) => HashMap::new(),
+    };
+    let has_other_variants = variants.len() > 1;
+
+    matcher::Matcher::new(matcher::BindingStyle::Ref)
+        .with_name("__self".into())
+        .build_arms(input, |arm_path, arm_name, style, _, outer_bis| {
+            let fields = match input.body {
+                ast::Body::Enum(_) => &variants[arm_name].fields[..],
+                ast::Body::Struct(_, ref fields) => &fields[..],
+            };
+            let (pat, inner_bis) = matcher::Matcher::new(matcher::BindingStyle::Ref)
+                .with_name("__other".into())
+                .build_match_pattern(&arm_path, style, fields);
+
+            let cmp = outer_bis.iter().zip(inner_bis).filter_map(|(o, i)| {
+                let attrs = &o.field.attrs;
+                let outer_name = &o.ident;
+                let inner_name = &i.ident;
+
+                if is_ignored(attrs, name) {
+                    return None;
+                }
+                let epsilon = match epsilon(attrs, name) {
+                    Some(epsilon) => quote!(#epsilon),
+                    None => quote!(#clone_trait_path::clone(&epsilon)),
+                };
+                let with = attrs.extension(name).and_then(attr::FieldExtension::with);
+                Some(match (with, attrs.partial_eq_compare_with()) {
+                    (None, Some(compare_fn)) => quote!(&& #compare_fn(#outer_name, #inner_name)),
+                    (with, _) => {
+                        let cmp = compare(outer_name, inner_name, &epsilon, with);
+                        quote!(&& #cmp)
+                    }
+                })
+            });
+
+            let other_variants = if has_other_variants {
+                Some(quote!(_ => false,))
+            } else {
+                None
+            };
+
+            quote! {
+                match *other {
+                    #pat => { true #(#cmp)* }
+                    #other_variants
+                }
+            }
+        })
+}
+
+/// Return the generics of the implementation of `name`: every field compared by its own
+/// implementation and using the type parameters gets a bound, with the same `Epsilon` unless the
+/// field has its own `epsilon`. The other fields are checked by the compiler in the body.
+fn approx_generics<F>(
+    input: &ast::Input,
+    name: &str,
+    epsilon_ty: &syn::Type,
+    extra_bound: F,
+) -> syn::Generics
+where
+    F: Fn(&syn::Type) -> Option<syn::WherePredicate>,
+{
+    let mut generics = bound::without_defaults(input.generics);
+    let mut predicates: Vec<syn::WherePredicate> = Vec::new();
+    if bound::uses_ty_params(input.generics, epsilon_ty) {
+        let clone_trait_path = clone_trait_path();
+        predicates.push(parse_quote!(#epsilon_ty: #clone_trait_path));
+    }
+
+    for field in input.body.all_fields() {
+        let field_bound = field
+            .attrs
+            .extension(name)
+            .and_then(attr::FieldExtension::bound);
+        if let Some(bound) = field_bound {
+            predicates.extend(bound.iter().cloned());
+        } else if is_compared_by_impl(&field.attrs, name)
+            && bound::uses_ty_params(input.generics, field.ty)
+        {
+            let ty = field.ty;
+            let own_epsilon_ty: syn::Type = parse_quote!(<#ty as ::approx::AbsDiffEq>::Epsilon);
+            predicates.push(
+                if epsilon(&field.attrs, name).is_some() || own_epsilon_ty == *epsilon_ty {
+                    parse_quote!(#ty: ::approx::AbsDiffEq)
+                } else {
+                    parse_quote!(#ty: ::approx::AbsDiffEq<Epsilon = #epsilon_ty>)
+                },
+            );
+            predicates.extend(extra_bound(ty));
+        }
+    }
+
+    if let Some(bound) = input
+        .attrs
+        .extension(name)
+        .and_then(attr::InputExtension::bound)
+    {
+        predicates.extend(bound.iter().cloned());
+    }
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Return the type of the tolerances, from `AbsDiffEq(epsilon_type="…")` or else the `Epsilon` of
+/// `epsilon_field_ty`.
+fn epsilon_ty(input: &ast::Input, epsilon_field_ty: &syn::Type) -> syn::Type {
+    match input
+        .attrs
+        .extension("AbsDiffEq")
+        .and_then(attr::InputExtension::epsilon_type)
+    {
+        Some(epsilon_type) => epsilon_type.clone(),
+        None => parse_quote!(<#epsilon_field_ty as ::approx::AbsDiffEq>::Epsilon),
+    }
+}
+
+/// Return the type of the first field compared by its own implementation without an `epsilon`,
+/// whose `Epsilon` is the one of the whole type.
+fn epsilon_field_ty<'a>(input: &'a ast::Input, name: &str) -> Result<&'a syn::Type, Error> {
+    input
+        .body
+        .all_fields()
+        .into_iter()
+        .find(|field| {
+            is_compared_by_impl(&field.attrs, name) && epsilon(&field.attrs, name).is_none()
+        })
+        .map(|field| field.ty)
+        .ok_or_else(|| {
+            Error::unsupported(
+                format!(
+                    "`{}` needs a field compared by its own implementation, whose `Epsilon` is \
+                     used for the whole type",
+                    name
+                ),
+                input.ident.span(),
+            )
+        })
+}
+
+/// Whether the field is compared with the implementation of `name` of its type, rather than
+/// ignored or compared by a function.
+fn is_compared_by_impl(attrs: &attr::Field, name: &str) -> bool {
+    !is_ignored(attrs, name)
+        && attrs
+            .extension(name)
+            .and_then(attr::FieldExtension::with)
+            .is_none()
+        && attrs.partial_eq_compare_with().is_none()
+}
+
+/// Whether the field is ignored by `name`. The fields ignored by `PartialEq` or `AbsDiffEq` are
+/// ignored by all the approximate comparisons.
+fn is_ignored(attrs: &attr::Field, name: &str) -> bool {
+    let ignores = |name| {
+        attrs
+            .extension(name)
+            .is_some_and(attr::FieldExtension::ignore)
+    };
+    attrs.ignore_partial_eq() || ignores("AbsDiffEq") || ignores(name)
+}
+
+/// The tolerance the field is compared with by `name`, from `epsilon="…"` on `name` or else on
+/// `AbsDiffEq`.
+fn epsilon<'a>(attrs: &'a attr::Field, name: &str) -> Option<&'a syn::Expr> {
+    attrs
+        .extension(name)
+        .and_then(attr::FieldExtension::epsilon)
+        .or_else(|| {
+            attrs
+                .extension("AbsDiffEq")
+                .and_then(attr::FieldExtension::epsilon)
+        })
+}
+
+/// Return the path of the `Clone` trait, that is `::std::clone::Clone`.
+fn clone_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::clone::Clone)
+    } else {
+        parse_quote!(::std::clone::Clone)
+    }
+}
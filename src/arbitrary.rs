@@ -0,0 +1,185 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `proptest::arbitrary::Arbitrary`.
+///
+/// The ignored fields are not generated but set to their default value, and the fields with a
+/// `strategy_with` function are generated by the strategy it returns.
+pub struct ArbitraryGenerator;
+
+impl TraitGenerator for ArbitraryGenerator {
+    fn name(&self) -> &'static str {
+        "Arbitrary"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("Arbitrary").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    if cfg!(feature = "use_core") {
+        return Err(Error::unsupported(
+            "`Arbitrary` is not available with the `use_core` feature",
+            input.ident.span(),
+        ));
+    }
+
+    let arbitrary_trait_path: syn::Path = parse_quote!(::proptest::arbitrary::Arbitrary);
+    let name = &input.ident;
+
+    let strategy = match input.body {
+        ast::Body::Enum(ref variants) => {
+            if variants.is_empty() {
+                return Err(Error::unsupported(
+                    "`Arbitrary` can't be derived on an enumeration without variants",
+                    input.ident.span(),
+                ));
+            }
+
+            let variants = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let strategy = fields_strategy(&quote!(#name::#variant_ident), &variant.fields);
+                quote!(::proptest::strategy::Strategy::boxed(#strategy))
+            });
+            quote!(::proptest::strategy::Union::new(
+                ::std::vec![#(#variants),*]
+            ))
+        }
+        ast::Body::Struct(_, ref fields) => fields_strategy(&quote!(#name), fields),
+    };
+
+    let generics = utils::build_impl_generics(
+        input,
+        &arbitrary_trait_path,
+        needs_arbitrary_bound,
+        |field| {
+            field
+                .extension("Arbitrary")
+                .and_then(attr::FieldExtension::bound)
+        },
+        |input| {
+            input
+                .extension("Arbitrary")
+                .and_then(attr::InputExtension::bound)
+        },
+    );
+    // The strategy is boxed, which requires the type to be `'static`.
+    let mut generics = generics;
+    let static_predicates: Vec<syn::WherePredicate> = input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match *param {
+            syn::GenericParam::Type(ref ty) => {
+                let ident = &ty.ident;
+                Some(parse_quote!(#ident: 'static))
+            }
+            syn::GenericParam::Lifetime(ref lifetime) => {
+                let lifetime = &lifetime.lifetime;
+                Some(parse_quote!(#lifetime: 'static))
+            }
+            syn::GenericParam::Const(_) => None,
+        })
+        .collect();
+    generics
+        .make_where_clause()
+        .predicates
+        .extend(static_predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #arbitrary_trait_path for #name #ty_generics #where_clause {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                ::proptest::strategy::Strategy::boxed(#strategy)
+            }
+        }
+    })
+}
+
+/// Return the strategy generating the structure or variant `path` from its `fields`.
+fn fields_strategy(
+    path: &proc_macro2::TokenStream,
+    fields: &[ast::Field],
+) -> proc_macro2::TokenStream {
+    let mut strategies = Vec::new();
+    let mut bindings = Vec::new();
+    let values: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = if is_ignored(&field.attrs) {
+                quote!(::std::default::Default::default())
+            } else {
+                let binding =
+                    syn::Ident::new(&format!("__arg_{}", i), proc_macro2::Span::call_site());
+                let ty = field.ty;
+                strategies.push(match strategy_with(&field.attrs) {
+                    Some(strategy_with) => quote!(#strategy_with()),
+                    None => quote!(::proptest::arbitrary::any::<#ty>()),
+                });
+                bindings.push(binding.clone());
+                quote!(#binding)
+            };
+
+            match field.ident {
+                Some(ref ident) => quote!(#ident: #value),
+                None => {
+                    let index = syn::Index::from(i);
+                    quote!(#index: #value)
+                }
+            }
+        })
+        .collect();
+    let construct = quote!(#path { #(#values),* });
+
+    // The strategies are nested in pairs, because tuples of strategies are only strategies up to
+    // some length.
+    let mut pairs = strategies.into_iter().zip(bindings).rev();
+    match pairs.next() {
+        Some((last_strategy, last_binding)) => {
+            let (strategy, pattern) = pairs.fold(
+                (last_strategy, quote!(#last_binding)),
+                |(strategy, pattern), (s, b)| (quote!((#s, #strategy)), quote!((#b, #pattern))),
+            );
+            quote!(::proptest::strategy::Strategy::prop_map(#strategy, |#pattern| #construct))
+        }
+        None => quote!(::proptest::strategy::LazyJust::new(|| #construct)),
+    }
+}
+
+fn needs_arbitrary_bound(attrs: &attr::Field) -> bool {
+    let extension = attrs.extension("Arbitrary");
+    !is_ignored(attrs)
+        && strategy_with(attrs).is_none()
+        && extension.and_then(attr::FieldExtension::bound).is_none()
+}
+
+fn is_ignored(attrs: &attr::Field) -> bool {
+    attrs
+        .extension("Arbitrary")
+        .is_some_and(attr::FieldExtension::ignore)
+}
+
+/// The function returning the strategy of the field, from `Arbitrary(strategy_with="…")`.
+fn strategy_with(attrs: &attr::Field) -> Option<&syn::Path> {
+    attrs
+        .extension("Arbitrary")
+        .and_then(attr::FieldExtension::with)
+}
@@ -0,0 +1,22 @@
+//! The handling of the fields that bindgen generates for bitfields and anonymous unions, see
+//! `attr::Bindgen`.
+
+use proc_macro2;
+
+use syn;
+
+/// Return an expression viewing the field bound to `arg`, a reference, as a `&[u8]`.
+///
+/// This reads all the bytes of the field, so the type must not have padding and must be fully
+/// initialized, which is the case of the bitfield units and of the unions used by C APIs.
+pub fn bytes(arg: &syn::Ident) -> proc_macro2::TokenStream {
+    let (slice_path, mem_path) = if cfg!(feature = "use_core") {
+        (quote!(::core::slice), quote!(::core::mem))
+    } else {
+        (quote!(::std::slice), quote!(::std::mem))
+    };
+
+    quote!(unsafe {
+        #slice_path::from_raw_parts(#arg as *const _ as *const u8, #mem_path::size_of_val(#arg))
+    })
+}
@@ -0,0 +1,203 @@
+use proc_macro2;
+
+use ast;
+use default;
+use syn;
+use syn::spanned::Spanned;
+use utils;
+use {allow_deprecated, impl_attrs, Error, TraitGenerator};
+
+/// The generator of `<Name>Builder`, which builds a structure field by field.
+///
+/// The required fields of `Default` are the parameters of `<Name>::builder`, the other fields can
+/// be set and otherwise get their default value, and the fields marked `Builder="ignore"` always
+/// get their default value.
+pub struct BuilderGenerator;
+
+impl TraitGenerator for BuilderGenerator {
+    fn name(&self) -> &'static str {
+        "Builder"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.builder.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match input.body {
+        ast::Body::Struct(ast::Style::Struct, ref fields) => fields,
+        _ => {
+            return Err(Error::unsupported(
+                "`Builder` can only be derived on structures with named fields",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let vis = input.vis;
+    let builder_name = syn::Ident::new(&format!("{}Builder", name), proc_macro2::Span::call_site());
+    let option_path = option_path();
+
+    let mut builder_fields = Vec::new();
+    let mut params = Vec::new();
+    let mut inits = Vec::new();
+    let mut setters = Vec::new();
+    let mut lets = Vec::new();
+    let mut names = Vec::new();
+    for field in fields {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("A structure field must have a name");
+        let ty = field.ty;
+        names.push(ident);
+
+        if field.attrs.ignore_builder() {
+            if field.attrs.default_required() {
+                return Err(Error::malformed(
+                    "a field ignored by `Builder` needs a default value, give it one with \
+                     `Default(value=\"…\")`",
+                    ty.span(),
+                ));
+            }
+            let default = default::field_default(field);
+            lets.push(quote!(let #ident = #default;));
+        } else if field.attrs.default_required() {
+            builder_fields.push(quote!(#ident: #ty));
+            params.push(quote!(#ident: #ty));
+            inits.push(quote!(#ident));
+            lets.push(quote!(let #ident = self.#ident;));
+        } else {
+            if ident == "build" {
+                return Err(Error::malformed(
+                    format!(
+                        "the setter of this field conflicts with `{}::build`, use \
+                         `Builder=\"ignore\"` to leave it out of the builder",
+                        builder_name
+                    ),
+                    ident.span(),
+                ));
+            }
+
+            let default = default::field_default(field);
+            let doc = format!("Sets the field `{}`.", ident);
+            builder_fields.push(quote!(#ident: #option_path<#ty>));
+            inits.push(quote!(#ident: #option_path::None));
+            setters.push(quote! {
+                #[doc = #doc]
+                #[inline]
+                #vis fn #ident(mut self, #ident: #ty) -> Self {
+                    self.#ident = #option_path::Some(#ident);
+                    self
+                }
+            });
+            lets.push(quote! {
+                let #ident = match self.#ident {
+                    #option_path::Some(#ident) => #ident,
+                    #option_path::None => #default,
+                };
+            });
+        }
+    }
+
+    // The fields using the type parameters may all be ignored, they are still used by the marker.
+    let marker = if input.generics.params.is_empty() {
+        None
+    } else {
+        let phantom_data_path = phantom_data_path();
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        builder_fields.push(quote!(__phantom: #phantom_data_path<fn() -> #name #ty_generics>));
+        inits.push(quote!(__phantom: #phantom_data_path));
+        Some(quote!(let _ = self.__phantom;))
+    };
+
+    let default_trait_path = default_trait_path();
+    let generics = utils::build_impl_generics(
+        input,
+        &default_trait_path,
+        |attrs| attrs.default_bound().is_none() && !attrs.default_required(),
+        |field| field.default_bound(),
+        |input| input.builder_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, _, struct_where_clause) = input.generics.split_for_impl();
+    let struct_generics = &input.generics.params;
+
+    let struct_doc = format!(
+        "A builder of [`{0}`], created by `{0}::builder` and finished by `build`.",
+        name
+    );
+
+    let impl_attrs = impl_attrs(input);
+    let allow_deprecated = allow_deprecated(input);
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[must_use]
+        #allow_deprecated
+        #vis struct #builder_name<#struct_generics> #struct_where_clause {
+            #(#builder_fields,)*
+        }
+
+        #impl_attrs
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a builder of this type from its required fields, the other fields get
+            /// their default value unless they are set.
+            #[inline]
+            #vis fn builder(#(#params),*) -> #builder_name #ty_generics {
+                #builder_name {
+                    #(#inits,)*
+                }
+            }
+        }
+
+        #impl_attrs
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #(#setters)*
+
+            /// Builds the value, the fields that were not set get their default value.
+            #[inline]
+            #[must_use]
+            #vis fn build(self) -> #name #ty_generics {
+                #marker
+                #(#lets)*
+                #name {
+                    #(#names),*
+                }
+            }
+        }
+    })
+}
+
+/// Return the path of the `Option` type, that is `::std::option::Option`.
+fn option_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::option::Option)
+    } else {
+        parse_quote!(::std::option::Option)
+    }
+}
+
+/// Return the path of the `PhantomData` type, that is `::std::marker::PhantomData`.
+fn phantom_data_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::marker::PhantomData)
+    } else {
+        parse_quote!(::std::marker::PhantomData)
+    }
+}
+
+/// Return the path of the `Default` trait, that is `::std::default::Default`.
+fn default_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::default::Default)
+    } else {
+        parse_quote!(::std::default::Default)
+    }
+}
@@ -2,12 +2,107 @@ use proc_macro2;
 
 use ast;
 use attr;
+use bound;
+use default;
 use matcher;
 use syn;
 use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Copy`.
+pub struct CopyGenerator;
+
+impl TraitGenerator for CopyGenerator {
+    fn name(&self) -> &'static str {
+        "Copy"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.copy.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        if input.attrs.copy_and_clone() && input.attrs.clone.is_some() {
+            return Err(Error::conflict(
+                "`Copy(and_clone)` can't be used when `Clone` is derived too, use \
+                 `Clone(is_copy)` instead",
+                input.ident.span(),
+            ));
+        }
+
+        Ok(derive_copy(input))
+    }
+}
+
+/// The generator for `Clone`.
+pub struct CloneGenerator;
+
+impl TraitGenerator for CloneGenerator {
+    fn name(&self) -> &'static str {
+        "Clone"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.clone.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        if input.attrs.clone_with().is_some() && input.attrs.clone_is_copy() == Some(true) {
+            return Err(Error::conflict(
+                "`Clone(is_copy)` can't be used with `Clone(clone_with)`, which clones the whole \
+                 value",
+                input.ident.span(),
+            ));
+        }
+        for field in input.body.all_fields() {
+            if field.attrs.clone_shared() && !is_shared_pointer(field.ty) {
+                return Err(Error::malformed(
+                    "`Clone(shared)` can only be used on an `Rc`, an `Arc` or a `Weak` pointer",
+                    field.span(),
+                ));
+            }
+            if field.attrs.clone_boxed() && boxed_type(field.ty).is_none() {
+                return Err(Error::malformed(
+                    "`Clone(boxed)` can only be used on a `Box`",
+                    field.span(),
+                ));
+            }
+            let dyn_cloned = field.attrs.clone_boxed() && field.attrs.clone_box().is_none();
+            if dyn_cloned && !cfg!(feature = "dyn-clone") {
+                return Err(Error::unsupported(
+                    "`Clone=\"boxed\"` needs the `dyn-clone` feature of derivative, or a clone-box \
+                     function given with `Clone(boxed=\"…\")`",
+                    field.span(),
+                ));
+            }
+        }
+        if input.attrs.clone_is_copy() == Some(true) {
+            for field in input.body.all_fields() {
+                if let Some(option) = custom_clone(&field.attrs) {
+                    return Err(Error::conflict(
+                        format!(
+                            "`Clone({})` can't be used with `Clone(is_copy)`, which copies all \
+                             the fields",
+                            option
+                        ),
+                        field.span(),
+                    ));
+                }
+            }
+        }
+        if input.attrs.clone_const() && !cfg!(feature = "const_impls") {
+            return Err(Error::unsupported(
+                "`Clone=\"const\"` needs the `const_impls` feature of derivative",
+                input.ident.span(),
+            ));
+        }
+
+        Ok(derive_clone(input))
+    }
+}
 
 /// Derive `Copy` for `input`.
-pub fn derive_copy(input: &ast::Input) -> proc_macro2::TokenStream {
+fn derive_copy(input: &ast::Input) -> proc_macro2::TokenStream {
     let name = &input.ident;
 
     let copy_trait_path = copy_trait_path();
@@ -20,146 +115,192 @@ pub fn derive_copy(input: &ast::Input) -> proc_macro2::TokenStream {
     );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let impl_attrs = impl_attrs(input);
+
+    // `Clone` gets the same bounds, so that the type is `Clone` whenever it is `Copy`.
+    let clone = if input.attrs.copy_and_clone() {
+        let clone_trait_path = clone_trait_path();
+        Some(quote! {
+            #[automatically_derived]
+            #impl_attrs
+            impl #impl_generics #clone_trait_path for #name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     quote! {
-        #[allow(unused_qualifications)]
+        #[automatically_derived]
+        #impl_attrs
         impl #impl_generics #copy_trait_path for #name #ty_generics #where_clause {}
+
+        #clone
     }
 }
 
 /// Derive `Clone` for `input`.
-pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
+fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
     let name = &input.ident;
 
+    // A `Copy` type can be cloned with `*self`. Without `Clone(is_copy)`, this is only done when
+    // `Copy` is known to be derived and there is no type parameter that could make it not `Copy`.
+    // With it, the type parameters get a `Copy` bound instead of a `Clone` one. A `clone_with`
+    // function on the type or on a field, and the other options of the fields, are always used.
+    let is_copy = input.attrs.clone_is_copy().unwrap_or_else(|| {
+        input.attrs.clone_with().is_none()
+            && !input.body.all_fields().iter().any(|f| custom_clone(&f.attrs).is_some())
+            && (input.attrs.copy.is_some() || input.attrs.derives_copy)
+            && input.generics.type_params().count() == 0
+    });
+
     let clone_trait_path = clone_trait_path();
-    let generics = utils::build_impl_generics(
+    let mut generics = utils::build_impl_generics(
         input,
-        &clone_trait_path,
+        &if is_copy { copy_trait_path() } else { clone_trait_path.clone() },
         needs_clone_bound,
         |field| field.clone_bound(),
         |input| input.clone_bound(),
     );
+    // The generic fields that are reset to `Default::default()` or cloned with
+    // `dyn_clone::clone_box` need the bounds to do so, unless the bounds are given.
+    if input.attrs.clone_bound().is_none() {
+        let default_trait_path = default_trait_path();
+        let fields = input
+            .body
+            .all_fields()
+            .into_iter()
+            .filter(|field| field.attrs.clone_bound().is_none());
+        let mut predicates = Vec::<syn::WherePredicate>::new();
+        for field in fields {
+            let ty = field.ty;
+            let resets = field.attrs.ignore_clone() && default::explicit_default(field).is_none();
+            if resets && bound::uses_ty_params(input.generics, ty) {
+                predicates.push(parse_quote!(#ty: #default_trait_path));
+            }
+            let dyn_cloned = boxed_type(ty).filter(|_| {
+                field.attrs.clone_boxed() && field.attrs.clone_box().is_none()
+            });
+            if let Some(boxed) = dyn_cloned.filter(|ty| bound::uses_ty_params(input.generics, ty)) {
+                predicates.push(parse_quote!(#boxed: ::dyn_clone::DynClone));
+            }
+        }
+        generics.make_where_clause().predicates.extend(predicates);
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (constness, where_clause) = if input.attrs.clone_const() {
+        let where_clause = utils::const_where_clause(where_clause, &clone_trait_path);
+        (Some(quote!(const)), where_clause)
+    } else {
+        (None, quote!(#where_clause))
+    };
+
+    let impl_attrs = impl_attrs(input);
 
-    let is_copy = input.attrs.copy.is_some();
-    if is_copy && input.generics.type_params().count() == 0 {
+    if is_copy {
         quote! {
-            #[allow(unused_qualifications)]
-            impl #impl_generics #clone_trait_path for #name #ty_generics #where_clause {
+            #[automatically_derived]
+            #impl_attrs
+            impl #impl_generics #constness #clone_trait_path for #name #ty_generics #where_clause {
                 fn clone(&self) -> Self {
                     *self
                 }
             }
         }
     } else {
-        let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
-            input,
-            |arm_path, _, style, _, bis| {
-                let field_clones = bis.iter().map(|bi| {
-                    let arg = &bi.ident;
-
-                    let clone = if let Some(clone_with) = bi.field.attrs.clone_with() {
-                        quote!(#clone_with(#arg))
-                    } else {
-                        quote!(#arg.clone())
-                    };
-
-                    if let Some(ref name) = bi.field.ident {
-                        quote! {
-                            #name: #clone
-                        }
-                    } else {
-                        clone
-                    }
-                });
+        let body = match input.attrs.clone_with() {
+            Some(clone_with) => quote!(#clone_with(self)),
+            None => {
+                let arms = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
+                    input,
+                    |arm_path, _, style, _, bis| {
+                        let field_clones = bis.iter().map(|bi| {
+                            let arg = &bi.ident;
+                            let span = bi.field.span();
 
-                match style {
-                    ast::Style::Struct => {
-                        quote! {
-                            #arm_path {
-                                #(#field_clones),*
+                            let clone = if bi.field.attrs.ignore_clone() {
+                                default::field_default(bi.field)
+                            } else if bi.field.attrs.clone_boxed() {
+                                clone_box(bi.field, arg)
+                            } else if let Some(clone_with) = bi.field.attrs.clone_with() {
+                                quote_spanned!(span=> #clone_with(#arg))
+                            } else {
+                                quote_spanned!(span=> #clone_trait_path::clone(#arg))
+                            };
+
+                            if let Some(ref name) = bi.field.ident {
+                                quote! {
+                                    #name: #clone
+                                }
+                            } else {
+                                clone
                             }
-                        }
-                    }
-                    ast::Style::Tuple => {
-                        quote! {
-                            #arm_path (#(#field_clones),*)
-                        }
-                    }
-                    ast::Style::Unit => {
-                        quote! {
-                            #arm_path
-                        }
-                    }
-                }
-            },
-        );
+                        });
 
-        let clone_from = if input.attrs.clone_from() {
-            Some(
-                matcher::Matcher::new(matcher::BindingStyle::RefMut).build_arms(
-                    input,
-                    |outer_arm_path, _, _, _, outer_bis| {
-                        let body = matcher::Matcher::new(matcher::BindingStyle::Ref)
-                            .with_name("__other".into())
-                            .build_arms(input, |inner_arm_path, _, _, _, inner_bis| {
-                                if outer_arm_path == inner_arm_path {
-                                    let field_clones = outer_bis.iter().zip(inner_bis).map(
-                                        |(outer_bi, inner_bi)| {
-                                            let outer = &outer_bi.ident;
-                                            let inner = &inner_bi.ident;
-
-                                            quote!(#outer.clone_from(#inner);)
-                                        },
-                                    );
-
-                                    quote! {
-                                        #(#field_clones)*
-                                        return;
+                        match style {
+                            ast::Style::Struct => {
+                                quote! {
+                                    #arm_path {
+                                        #(#field_clones),*
                                     }
-                                } else {
-                                    quote!()
                                 }
-                            });
-
-                        quote! {
-                            match *other {
-                                #body
+                            }
+                            ast::Style::Tuple => {
+                                quote! {
+                                    #arm_path (#(#field_clones),*)
+                                }
+                            }
+                            ast::Style::Unit => {
+                                quote! {
+                                    #arm_path
+                                }
                             }
                         }
                     },
-                ),
-            )
+                );
+
+                quote! {
+                    match *self {
+                        #arms
+                    }
+                }
+            }
+        };
+
+        let has_clone_from_with = input
+            .body
+            .all_fields()
+            .iter()
+            .any(|f| f.attrs.clone_from_with().is_some());
+
+        let clone_from = if input.attrs.clone_from() || has_clone_from_with {
+            Some(clone_from_body(input))
         } else {
             None
         };
 
         let clone_from = clone_from.map(|body| {
-            // Enumerations are only cloned-from if both variants are the same.
-            // If they are different, fallback to normal cloning.
-            let fallback = if let ast::Body::Enum(_) = input.body {
-                Some(quote!(*self = other.clone();))
-            } else {
-                None
-            };
-
             quote! {
+                #[allow(unreachable_code)]
                 fn clone_from(&mut self, other: &Self) {
-                    match *self {
-                        #body
-                    }
-
-                    #fallback
+                    #body
                 }
             }
         });
 
+        // Unlike the `Copy` case, not `#[automatically_derived]`, which would make the dead code
+        // lint report the `clone_with` functions as unused.
         quote! {
-            #[allow(unused_qualifications)]
-            impl #impl_generics #clone_trait_path for #name #ty_generics #where_clause {
+            #impl_attrs
+            impl #impl_generics #constness #clone_trait_path for #name #ty_generics #where_clause {
+                // The clone of a field with an uninhabited type has the span of the field, so the
+                // lint does not know that it's generated code.
+                #[allow(unreachable_code)]
                 fn clone(&self) -> Self {
-                    match *self {
-                        #body
-                    }
+                    #body
                 }
 
                 #clone_from
@@ -168,8 +309,144 @@ pub fn derive_clone(input: &ast::Input) -> proc_macro2::TokenStream {
     }
 }
 
+/// The body of `clone_from`, with a single arm per variant so that the expansion stays linear in
+/// the number of variants.
+fn clone_from_body(input: &ast::Input) -> proc_macro2::TokenStream {
+    let clone_trait_path = clone_trait_path();
+    let type_path = matcher::type_path(input);
+    let variants: Vec<(syn::Path, ast::Style, &[ast::Field])> = match input.body {
+        ast::Body::Enum(ref variants) => variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                (
+                    parse_quote!(#type_path::#variant_ident),
+                    variant.style,
+                    &variant.fields[..],
+                )
+            })
+            .collect(),
+        ast::Body::Struct(style, ref fields) => vec![(type_path, style, &fields[..])],
+    };
+
+    // An enumeration without variants has no value to clone from.
+    if variants.is_empty() {
+        return quote!(match *self {});
+    }
+
+    let arms = variants.iter().map(|&(ref path, style, fields)| {
+        let (outer_pat, outer_bis) = matcher::Matcher::new(matcher::BindingStyle::RefMut)
+            .build_match_pattern(path, style, fields);
+        let (inner_pat, inner_bis) = matcher::Matcher::new(matcher::BindingStyle::Ref)
+            .with_name("__other".into())
+            .build_match_pattern(path, style, fields);
+
+        let field_clones = outer_bis.iter().zip(inner_bis).map(|(outer_bi, inner_bi)| {
+            let outer = &outer_bi.ident;
+            let inner = &inner_bi.ident;
+            let attrs = &outer_bi.field.attrs;
+            let span = outer_bi.field.span();
+
+            if attrs.ignore_clone() {
+                let default = default::field_default(outer_bi.field);
+                quote!(*#outer = #default;)
+            } else if attrs.clone_boxed() {
+                let clone = clone_box(outer_bi.field, inner);
+                quote!(*#outer = #clone;)
+            } else if let Some(clone_from_with) = attrs.clone_from_with() {
+                quote_spanned!(span=> #clone_from_with(#outer, #inner);)
+            } else if let Some(clone_with) = attrs.clone_with() {
+                quote_spanned!(span=> *#outer = #clone_with(#inner);)
+            } else {
+                quote_spanned!(span=> #clone_trait_path::clone_from(#outer, #inner);)
+            }
+        });
+
+        quote! {
+            (&mut #outer_pat, &#inner_pat) => { #(#field_clones)* }
+        }
+    });
+
+    // Enumerations are only cloned-from if both variants are the same. If they are different,
+    // fallback to normal cloning.
+    let fallback = if variants.len() > 1 {
+        Some(quote!(_ => *self = other.clone(),))
+    } else {
+        None
+    };
+
+    quote! {
+        match (&mut *self, other) {
+            #(#arms)*
+            #fallback
+        }
+    }
+}
+
+/// The first option of the field changing how it is cloned, if any, in which case it can't be
+/// copied with the rest of the value.
+fn custom_clone(attrs: &attr::Field) -> Option<&'static str> {
+    if attrs.ignore_clone() {
+        Some("ignore")
+    } else if attrs.clone_with().is_some() {
+        Some("clone_with")
+    } else if attrs.clone_from_with().is_some() {
+        Some("clone_from_with")
+    } else if attrs.clone_shared() {
+        Some("shared")
+    } else if attrs.clone_boxed() {
+        Some("boxed")
+    } else {
+        None
+    }
+}
+
 fn needs_clone_bound(attrs: &attr::Field) -> bool {
-    attrs.clone_bound().is_none()
+    !attrs.ignore_clone()
+        && !attrs.clone_shared()
+        && !attrs.clone_boxed()
+        && attrs.clone_bound().is_none()
+}
+
+/// The type of the value in `ty` if it is a box, eg. `dyn Shape` for `Box<dyn Shape>`.
+fn boxed_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match *ty {
+        syn::Type::Path(ref ty) => ty.path.segments.iter().last()?,
+        _ => return None,
+    };
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if segment.ident == "Box" => {
+            match args.args.iter().next()? {
+                syn::GenericArgument::Type(ref ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Clone `arg`, a reference to the box `f`, by cloning the value in it with the clone-box
+/// function of `f`, `dyn_clone::clone_box` by default.
+fn clone_box(f: &ast::Field, arg: &syn::Ident) -> proc_macro2::TokenStream {
+    let clone_box = match f.attrs.clone_box() {
+        Some(clone_box) => quote!(#clone_box),
+        None => quote!(::dyn_clone::clone_box),
+    };
+    quote_spanned!(f.span()=> #clone_box(&**#arg))
+}
+
+/// Whether `ty` is one of the shared pointers that are `Clone` whatever they point to: `Rc`,
+/// `Arc` and their `Weak` pointers.
+fn is_shared_pointer(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Path(ref ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| ["Rc", "Arc", "Weak"].iter().any(|&p| segment.ident == p)),
+        _ => false,
+    }
 }
 
 /// Return the path of the `Clone` trait, that is `::std::clone::Clone`.
@@ -181,6 +458,15 @@ fn clone_trait_path() -> syn::Path {
     }
 }
 
+/// Return the path of the `Default` trait, that is `::std::default::Default`.
+fn default_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::default::Default)
+    } else {
+        parse_quote!(::std::default::Default)
+    }
+}
+
 /// Return the path of the `Copy` trait, that is `::std::marker::Copy`.
 fn copy_trait_path() -> syn::Path {
     if cfg!(feature = "use_core") {
@@ -1,15 +1,88 @@
 // https://github.com/rust-lang/rust/issues/13101
 
+use std::cmp;
+use std::collections::HashMap;
+
 use proc_macro2;
 
 use ast;
 use attr;
+use bindgen;
 use matcher;
 use syn;
 use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Eq`.
+pub struct EqGenerator;
+
+impl TraitGenerator for EqGenerator {
+    fn name(&self) -> &'static str {
+        "Eq"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.eq.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        Ok(derive_eq(input))
+    }
+}
+
+/// The generator for `PartialEq`.
+pub struct PartialEqGenerator;
+
+impl TraitGenerator for PartialEqGenerator {
+    fn name(&self) -> &'static str {
+        "PartialEq"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.partial_eq.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_partial_eq(input)
+    }
+}
+
+/// The generator for `Ord`.
+pub struct OrdGenerator;
+
+impl TraitGenerator for OrdGenerator {
+    fn name(&self) -> &'static str {
+        "Ord"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.ord.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_ord(input)
+    }
+}
+
+/// The generator for `PartialOrd`.
+pub struct PartialOrdGenerator;
+
+impl TraitGenerator for PartialOrdGenerator {
+    fn name(&self) -> &'static str {
+        "PartialOrd"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.partial_ord.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_partial_ord(input)
+    }
+}
 
 /// Derive `Eq` for `input`.
-pub fn derive_eq(input: &ast::Input) -> proc_macro2::TokenStream {
+fn derive_eq(input: &ast::Input) -> proc_macro2::TokenStream {
     let name = &input.ident;
 
     let eq_trait_path = eq_trait_path();
@@ -20,56 +93,99 @@ pub fn derive_eq(input: &ast::Input) -> proc_macro2::TokenStream {
         |field| field.eq_bound(),
         |input| input.eq_bound(),
     );
+    let generics = utils::with_deref_bounds(
+        input,
+        generics,
+        &eq_trait_path,
+        &deref_trait_path(),
+        needs_eq_deref_bound,
+        input.attrs.eq_bound(),
+    );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let impl_attrs = impl_attrs(input);
+
     quote! {
-        #[allow(unused_qualifications)]
+        #[automatically_derived]
+        #impl_attrs
         impl #impl_generics #eq_trait_path for #name #ty_generics #where_clause {}
     }
 }
 
 /// Derive `PartialEq` for `input`.
-pub fn derive_partial_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream, String> {
-    if let ast::Body::Enum(_) = input.body {
-        if !input.attrs.partial_eq_on_enum() {
-            return Err(
+fn derive_partial_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    if input.attrs.partial_eq_const() && !cfg!(feature = "const_impls") {
+        return Err(Error::unsupported(
+            "`PartialEq=\"const\"` needs the `const_impls` feature of derivative",
+            input.ident.span(),
+        ));
+    }
+
+    if let ast::Body::Enum(ref variants) = input.body {
+        // An enumeration without variants has no value to compare, hence nothing slow.
+        if !input.attrs.partial_eq_on_enum() && !variants.is_empty() {
+            return Err(Error::unsupported(
                 "can't use `#[derivative(PartialEq)]` on an enumeration without \
-                 `feature_allow_slow_enum`; see the documentation for more details"
-                    .into(),
-            );
+                 `feature_allow_slow_enum`; see the documentation for more details",
+                input.ident.span(),
+            ));
         }
     }
 
+    // Each arm only matches `other` against its own variant, so that the expansion stays linear
+    // in the number of variants.
+    let variants: HashMap<&syn::Ident, &ast::Variant> = match input.body {
+        ast::Body::Enum(ref variants) => variants.iter().map(|v| (&v.ident, v)).collect(),
+        ast::Body::Struct(..) => HashMap::new(),
+    };
+    let has_other_variants = variants.len() > 1;
+
     let body = matcher::Matcher::new(matcher::BindingStyle::Ref)
         .with_name("__self".into())
-        .build_arms(input, |_, outer_arm_name, _, _, outer_bis| {
-            let body = matcher::Matcher::new(matcher::BindingStyle::Ref)
+        .build_arms(input, |arm_path, arm_name, style, _, outer_bis| {
+            let fields = match input.body {
+                ast::Body::Enum(_) => &variants[arm_name].fields[..],
+                ast::Body::Struct(_, ref fields) => &fields[..],
+            };
+            let (pat, inner_bis) = matcher::Matcher::new(matcher::BindingStyle::Ref)
                 .with_name("__other".into())
-                .build_arms(input, |_, inner_arm_name, _, _, inner_bis| {
-                    if outer_arm_name == inner_arm_name {
-                        let cmp = outer_bis.iter().zip(inner_bis).map(|(o, i)| {
-                            let outer_name = &o.ident;
-                            let inner_name = &i.ident;
-
-                            if o.field.attrs.ignore_partial_eq() {
-                                None
-                            } else if let Some(compare_fn) = o.field.attrs.partial_eq_compare_with()
-                            {
-                                Some(quote!(&& #compare_fn(#outer_name, #inner_name)))
-                            } else {
-                                Some(quote!(&& #outer_name == #inner_name))
-                            }
-                        });
-
-                        quote!(true #(#cmp)*)
-                    } else {
-                        quote!(false)
+                .build_match_pattern(&arm_path, style, fields);
+
+            let cmp = outer_bis.iter().zip(inner_bis).map(|(o, i)| {
+                let outer_name = &o.ident;
+                let inner_name = &i.ident;
+                let span = o.field.span();
+
+                if o.field.attrs.ignore_partial_eq() {
+                    None
+                } else if o.field.attrs.partial_eq_deref() {
+                    match o.field.attrs.partial_eq_compare_with() {
+                        Some(compare_fn) => Some(quote_spanned! {span=>
+                            && #compare_fn(&**#outer_name, &**#inner_name)
+                        }),
+                        None => Some(quote_spanned!(span=> && **#outer_name == **#inner_name)),
                     }
-                });
+                } else if let Some(compare_fn) = o.field.attrs.partial_eq_compare_with() {
+                    Some(quote_spanned!(span=> && #compare_fn(#outer_name, #inner_name)))
+                } else if o.field.attrs.bindgen() == Some(attr::Bindgen::Opaque) {
+                    let outer = bindgen::bytes(outer_name);
+                    let inner = bindgen::bytes(inner_name);
+                    Some(quote!(&& #outer == #inner))
+                } else {
+                    Some(quote_spanned!(span=> && #outer_name == #inner_name))
+                }
+            });
+
+            let other_variants = if has_other_variants {
+                Some(quote!(_ => false,))
+            } else {
+                None
+            };
 
             quote! {
                 match *other {
-                    #body
+                    #pat => { true #(#cmp)* }
+                    #other_variants
                 }
             }
         });
@@ -84,11 +200,28 @@ pub fn derive_partial_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream,
         |field| field.partial_eq_bound(),
         |input| input.partial_eq_bound(),
     );
+    let generics = utils::with_deref_bounds(
+        input,
+        generics,
+        &partial_eq_trait_path,
+        &deref_trait_path(),
+        needs_partial_eq_deref_bound,
+        input.attrs.partial_eq_bound(),
+    );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (constness, where_clause) = if input.attrs.partial_eq_const() {
+        let where_clause = utils::const_where_clause(where_clause, &partial_eq_trait_path);
+        (Some(quote!(const)), where_clause)
+    } else {
+        (None, quote!(#where_clause))
+    };
+
+    let impl_attrs = impl_attrs(input);
 
     Ok(quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #partial_eq_trait_path for #name #ty_generics #where_clause {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #constness #partial_eq_trait_path for #name #ty_generics #where_clause {
             fn eq(&self, other: &Self) -> bool {
                 match *self {
                     #body
@@ -98,12 +231,289 @@ pub fn derive_partial_eq(input: &ast::Input) -> Result<proc_macro2::TokenStream,
     })
 }
 
+/// Derive `PartialOrd` for `input`.
+fn derive_partial_ord(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let option_path = option_path();
+    let ordering_path = ordering_path();
+
+    derive_ordering(
+        input,
+        &OrderingTrait {
+            name: "PartialOrd",
+            on_enum: input.attrs.partial_ord_on_enum(),
+            trait_path: partial_ord_trait_path(),
+            method: quote!(partial_cmp),
+            output: quote!(#option_path<#ordering_path>),
+            equal: quote!(#option_path::Some(#ordering_path::Equal)),
+            ignore: attr::Field::ignore_partial_ord,
+            cmp_with: attr::Field::partial_ord_partial_cmp_with,
+            reverse: attr::Field::partial_ord_reverse,
+            priority: attr::Field::partial_ord_priority,
+            order: attr::Input::partial_ord_order,
+            needs_bound: needs_partial_ord_bound,
+            field_bound: attr::Field::partial_ord_bound,
+            input_bound: attr::Input::partial_ord_bound,
+        },
+    )
+}
+
+/// Derive `Ord` for `input`.
+fn derive_ord(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let ordering_path = ordering_path();
+
+    derive_ordering(
+        input,
+        &OrderingTrait {
+            name: "Ord",
+            on_enum: input.attrs.ord_on_enum(),
+            trait_path: ord_trait_path(),
+            method: quote!(cmp),
+            output: quote!(#ordering_path),
+            equal: quote!(#ordering_path::Equal),
+            ignore: attr::Field::ignore_ord,
+            cmp_with: attr::Field::ord_cmp_with,
+            reverse: attr::Field::ord_reverse,
+            priority: attr::Field::ord_priority,
+            order: attr::Input::ord_order,
+            needs_bound: needs_ord_bound,
+            field_bound: attr::Field::ord_bound,
+            input_bound: attr::Input::ord_bound,
+        },
+    )
+}
+
+/// What differs between the implementations of `PartialOrd` and `Ord`.
+struct OrderingTrait {
+    name: &'static str,
+    /// Whether the trait is allowed on enums, with `feature_allow_slow_enum`.
+    on_enum: bool,
+    trait_path: syn::Path,
+    /// The comparison method, `partial_cmp` or `cmp`.
+    method: proc_macro2::TokenStream,
+    /// The type returned by `method`.
+    output: proc_macro2::TokenStream,
+    /// The value returned by `method` for equal values.
+    equal: proc_macro2::TokenStream,
+    ignore: fn(&attr::Field) -> bool,
+    cmp_with: fn(&attr::Field) -> Option<&syn::Path>,
+    reverse: fn(&attr::Field) -> bool,
+    priority: fn(&attr::Field) -> i32,
+    /// The rank of a variant, from `order`.
+    order: fn(&attr::Input) -> Option<usize>,
+    needs_bound: fn(&attr::Field) -> bool,
+    field_bound: fn(&attr::Field) -> Option<&[syn::WherePredicate]>,
+    input_bound: fn(&attr::Input) -> Option<&[syn::WherePredicate]>,
+}
+
+/// Derive `PartialOrd` or `Ord` for `input`.
+///
+/// The fields are compared by decreasing priority, then in order, until one is not equal. The
+/// variants of an enumeration are ordered by their `order`, or as they are declared.
+fn derive_ordering(
+    input: &ast::Input,
+    ordering: &OrderingTrait,
+) -> Result<proc_macro2::TokenStream, Error> {
+    if let ast::Body::Enum(ref variants) = input.body {
+        if !ordering.on_enum && !variants.is_empty() {
+            return Err(Error::unsupported(
+                format!(
+                    "can't use `#[derivative({})]` on an enumeration without \
+                     `feature_allow_slow_enum`; see the documentation for more details",
+                    ordering.name
+                ),
+                input.ident.span(),
+            ));
+        }
+    }
+
+    let trait_path = &ordering.trait_path;
+    let method = &ordering.method;
+    let equal = &ordering.equal;
+
+    // The rank of the variant of `other`, to compare different variants. As in `PartialEq`, each
+    // arm only matches `other` against its own variant.
+    let (other_index, variants) = match input.body {
+        ast::Body::Enum(ref variants) if variants.len() > 1 => {
+            let ranks = variant_ranks(variants, ordering)?;
+            let type_path = matcher::type_path(input);
+            let indexes = variants.iter().zip(&ranks).map(|(variant, rank)| {
+                let variant_ident = &variant.ident;
+                quote!(#type_path::#variant_ident { .. } => #rank,)
+            });
+            let other_index = quote! {
+                let __other_index: usize = match *other {
+                    #(#indexes)*
+                };
+            };
+            let variants: HashMap<&syn::Ident, (usize, &ast::Variant)> = variants
+                .iter()
+                .zip(ranks)
+                .map(|(v, rank)| (&v.ident, (rank, v)))
+                .collect();
+            (Some(other_index), variants)
+        }
+        ast::Body::Enum(ref variants) => {
+            let variants = variants.iter().map(|v| (&v.ident, (0, v))).collect();
+            (None, variants)
+        }
+        ast::Body::Struct(..) => (None, HashMap::new()),
+    };
+
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref)
+        .with_name("__self".into())
+        .build_arms(input, |arm_path, arm_name, style, _, outer_bis| {
+            let (index, fields) = match input.body {
+                ast::Body::Enum(_) => {
+                    let (index, variant) = variants[arm_name];
+                    (index, &variant.fields[..])
+                }
+                ast::Body::Struct(_, ref fields) => (0, &fields[..]),
+            };
+            let (pat, inner_bis) = matcher::Matcher::new(matcher::BindingStyle::Ref)
+                .with_name("__other".into())
+                .build_match_pattern(&arm_path, style, fields);
+
+            let mut comparisons: Vec<_> = outer_bis
+                .iter()
+                .zip(inner_bis)
+                .filter_map(|(o, i)| {
+                    if (ordering.ignore)(&o.field.attrs) {
+                        return None;
+                    }
+
+                    let (outer_name, inner_name) = if (ordering.reverse)(&o.field.attrs) {
+                        (&i.ident, &o.ident)
+                    } else {
+                        (&o.ident, &i.ident)
+                    };
+                    let span = o.field.span();
+
+                    let cmp = if let Some(cmp_fn) = (ordering.cmp_with)(&o.field.attrs) {
+                        quote_spanned!(span=> #cmp_fn(#outer_name, #inner_name))
+                    } else if o.field.attrs.bindgen() == Some(attr::Bindgen::Opaque) {
+                        let outer = bindgen::bytes(outer_name);
+                        let inner = bindgen::bytes(inner_name);
+                        quote!(#trait_path::#method(#outer, #inner))
+                    } else {
+                        quote_spanned!(span=> #trait_path::#method(#outer_name, #inner_name))
+                    };
+
+                    let cmp = quote! {
+                        match #cmp {
+                            #equal => (),
+                            __cmp => return __cmp,
+                        }
+                    };
+                    Some(((ordering.priority)(&o.field.attrs), cmp))
+                })
+                .collect();
+            // The sort is stable, so fields with the same priority stay in order.
+            comparisons.sort_by_key(|&(priority, _)| cmp::Reverse(priority));
+            let cmp = comparisons.into_iter().map(|(_, cmp)| cmp);
+
+            let other_variants = other_index
+                .as_ref()
+                .map(|_| quote!(_ => #trait_path::#method(&#index, &__other_index),));
+
+            quote! {
+                match *other {
+                    #pat => {
+                        #(#cmp)*
+                        #equal
+                    }
+                    #other_variants
+                }
+            }
+        });
+
+    let name = &input.ident;
+    let generics = utils::build_impl_generics(
+        input,
+        trait_path,
+        ordering.needs_bound,
+        ordering.field_bound,
+        ordering.input_bound,
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let output = &ordering.output;
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            fn #method(&self, other: &Self) -> #output {
+                #other_index
+                match *self {
+                    #body
+                }
+            }
+        }
+    })
+}
+
+/// Return the rank of each variant: its `order` if every variant has one, or its index if none
+/// has.
+fn variant_ranks(variants: &[ast::Variant], ordering: &OrderingTrait) -> Result<Vec<usize>, Error> {
+    let orders: Vec<_> = variants
+        .iter()
+        .map(|v| (ordering.order)(&v.attrs))
+        .collect();
+    if orders.iter().all(Option::is_none) {
+        return Ok((0..variants.len()).collect());
+    }
+
+    let mut ranks = Vec::with_capacity(variants.len());
+    for (variant, order) in variants.iter().zip(orders) {
+        let rank = order.ok_or_else(|| {
+            Error::malformed(
+                format!(
+                    "`{0}(order)` must be given to all the variants or none, this variant has \
+                     none",
+                    ordering.name
+                ),
+                variant.ident.span(),
+            )
+        })?;
+        if ranks.contains(&rank) {
+            return Err(Error::malformed(
+                format!(
+                    "another variant has `{}(order=\"{}\")`, different variants must not be \
+                     equal",
+                    ordering.name, rank
+                ),
+                variant.ident.span(),
+            ));
+        }
+        ranks.push(rank);
+    }
+
+    Ok(ranks)
+}
+
 fn needs_partial_eq_bound(attrs: &attr::Field) -> bool {
-    !attrs.ignore_partial_eq() && attrs.partial_eq_bound().is_none()
+    !attrs.ignore_partial_eq() && attrs.partial_eq_bound().is_none() && !attrs.partial_eq_deref()
+}
+
+fn needs_partial_eq_deref_bound(attrs: &attr::Field) -> bool {
+    !attrs.ignore_partial_eq() && attrs.partial_eq_bound().is_none() && attrs.partial_eq_deref()
+}
+
+fn needs_partial_ord_bound(attrs: &attr::Field) -> bool {
+    !attrs.ignore_partial_ord() && attrs.partial_ord_bound().is_none()
+}
+
+fn needs_ord_bound(attrs: &attr::Field) -> bool {
+    !attrs.ignore_ord() && attrs.ord_bound().is_none()
 }
 
 fn needs_eq_bound(attrs: &attr::Field) -> bool {
-    !attrs.ignore_partial_eq() && attrs.eq_bound().is_none()
+    !attrs.ignore_partial_eq() && attrs.eq_bound().is_none() && !attrs.partial_eq_deref()
+}
+
+fn needs_eq_deref_bound(attrs: &attr::Field) -> bool {
+    !attrs.ignore_partial_eq() && attrs.eq_bound().is_none() && attrs.partial_eq_deref()
 }
 
 /// Return the path of the `Eq` trait, that is `::std::cmp::Eq`.
@@ -115,6 +525,24 @@ fn eq_trait_path() -> syn::Path {
     }
 }
 
+/// Return the path of the `Deref` trait, that is `::std::ops::Deref`.
+fn deref_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::Deref)
+    } else {
+        parse_quote!(::std::ops::Deref)
+    }
+}
+
+/// Return the path of the `Ord` trait, that is `::std::cmp::Ord`.
+fn ord_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::cmp::Ord)
+    } else {
+        parse_quote!(::std::cmp::Ord)
+    }
+}
+
 /// Return the path of the `PartialEq` trait, that is `::std::cmp::PartialEq`.
 fn partial_eq_trait_path() -> syn::Path {
     if cfg!(feature = "use_core") {
@@ -123,3 +551,30 @@ fn partial_eq_trait_path() -> syn::Path {
         parse_quote!(::std::cmp::PartialEq)
     }
 }
+
+/// Return the path of the `PartialOrd` trait, that is `::std::cmp::PartialOrd`.
+fn partial_ord_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::cmp::PartialOrd)
+    } else {
+        parse_quote!(::std::cmp::PartialOrd)
+    }
+}
+
+/// Return the path of `Ordering`, that is `::std::cmp::Ordering`.
+fn ordering_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::cmp::Ordering)
+    } else {
+        parse_quote!(::std::cmp::Ordering)
+    }
+}
+
+/// Return the path of `Option`, that is `::std::option::Option`.
+fn option_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::option::Option)
+    } else {
+        parse_quote!(::std::option::Option)
+    }
+}
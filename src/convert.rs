@@ -0,0 +1,707 @@
+use proc_macro2;
+use quote::ToTokens;
+
+use ast;
+use attr;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `AsRef`, on structures with exactly one field or fields marked `AsRef`.
+pub struct AsRefGenerator;
+
+impl TraitGenerator for AsRefGenerator {
+    fn name(&self) -> &'static str {
+        "AsRef"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.as_ref.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_as_ref(input, false)
+    }
+}
+
+/// The generator for `AsMut`, on structures with exactly one field or fields marked `AsMut`.
+pub struct AsMutGenerator;
+
+impl TraitGenerator for AsMutGenerator {
+    fn name(&self) -> &'static str {
+        "AsMut"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.as_mut.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_as_ref(input, true)
+    }
+}
+
+/// The generator for `Borrow`, on structures with exactly one field.
+pub struct BorrowGenerator;
+
+impl TraitGenerator for BorrowGenerator {
+    fn name(&self) -> &'static str {
+        "Borrow"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.borrow.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_borrow(input, false)
+    }
+}
+
+/// The generator for `BorrowMut`, on structures with exactly one field.
+pub struct BorrowMutGenerator;
+
+impl TraitGenerator for BorrowMutGenerator {
+    fn name(&self) -> &'static str {
+        "BorrowMut"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.borrow_mut.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_borrow(input, true)
+    }
+}
+
+/// The generator for `From`, on structures with exactly one field.
+pub struct FromGenerator;
+
+impl TraitGenerator for FromGenerator {
+    fn name(&self) -> &'static str {
+        "From"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.from.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_from(input)
+    }
+}
+
+/// The generator for `TryFrom`, on structures with exactly one field and on enumerations whose
+/// variants have no fields.
+pub struct TryFromGenerator;
+
+impl TraitGenerator for TryFromGenerator {
+    fn name(&self) -> &'static str {
+        "TryFrom"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.try_from.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_try_from(input)
+    }
+}
+
+/// Derive `AsRef<Field>`, or `AsMut<Field>` if `mutable`, for each marked field of `input`, or
+/// for its only field.
+fn derive_as_ref(input: &ast::Input, mutable: bool) -> Result<proc_macro2::TokenStream, Error> {
+    let (trait_name, trait_path) = if mutable {
+        ("AsMut", as_mut_trait_path())
+    } else {
+        ("AsRef", as_ref_trait_path())
+    };
+    let is_marked = |field: &attr::Field| {
+        if mutable {
+            field.as_mut_target()
+        } else {
+            field.as_ref_target()
+        }
+    };
+
+    let fields = match input.body {
+        ast::Body::Struct(_, ref fields) => fields,
+        ast::Body::Enum(_) => {
+            return Err(Error::unsupported(
+                format!("`{}` can only be derived on structures", trait_name),
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let mut targets: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter(|&(_, field)| is_marked(&field.attrs))
+        .collect();
+    if targets.is_empty() {
+        if fields.len() != 1 {
+            return Err(Error::unsupported(
+                format!(
+                    "`{0}` needs fields marked `#[derivative({0})]` on a structure that does not \
+                     have exactly one field",
+                    trait_name
+                ),
+                input.ident.span(),
+            ));
+        }
+        targets.push((0, &fields[0]));
+    }
+
+    // Two implementations for the same type would conflict.
+    let mut types = Vec::new();
+    for &(_, field) in &targets {
+        let ty = field.ty.into_token_stream().to_string();
+        if types.contains(&ty) {
+            return Err(Error::malformed(
+                format!("another field marked `{}` has the same type", trait_name),
+                field.span(),
+            ));
+        }
+        types.push(ty);
+    }
+
+    let name = &input.ident;
+    let generics = if mutable {
+        utils::build_impl_generics(
+            input,
+            &trait_path,
+            |_| false,
+            |field| field.as_mut_bound(),
+            |input| input.as_mut_bound(),
+        )
+    } else {
+        utils::build_impl_generics(
+            input,
+            &trait_path,
+            |_| false,
+            |field| field.as_ref_bound(),
+            |input| input.as_ref_bound(),
+        )
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    let impls = targets.into_iter().map(|(index, field)| {
+        let ty = field.ty;
+        let member = match field.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        };
+
+        let method = if mutable {
+            quote! {
+                #[inline]
+                fn as_mut(&mut self) -> &mut #ty {
+                    &mut self.#member
+                }
+            }
+        } else {
+            quote! {
+                #[inline]
+                fn as_ref(&self) -> &#ty {
+                    &self.#member
+                }
+            }
+        };
+
+        quote! {
+            #[automatically_derived]
+            #impl_attrs
+            impl #impl_generics #trait_path<#ty> for #name #ty_generics #where_clause {
+                #method
+            }
+        }
+    });
+
+    Ok(quote!(#(#impls)*))
+}
+
+/// Derive `Borrow<Field>`, or `BorrowMut<Field>` if `mutable`, for `input`.
+fn derive_borrow(input: &ast::Input, mutable: bool) -> Result<proc_macro2::TokenStream, Error> {
+    let trait_name = if mutable { "BorrowMut" } else { "Borrow" };
+    let field = match input.body {
+        ast::Body::Struct(_, ref fields) if fields.len() == 1 => &fields[0],
+        _ => {
+            return Err(Error::unsupported(
+                format!(
+                    "`{}` can only be derived on structures with exactly one field",
+                    trait_name
+                ),
+                input.ident.span(),
+            ));
+        }
+    };
+    check_borrow_contract(input, field, trait_name)?;
+
+    let name = &input.ident;
+    let ty = field.ty;
+    let member = match field.ident {
+        Some(ref ident) => quote!(#ident),
+        None => {
+            let index = syn::Index::from(0);
+            quote!(#index)
+        }
+    };
+
+    let (trait_path, generics, method) = if mutable {
+        let trait_path = borrow_mut_trait_path();
+        let generics = utils::build_impl_generics(
+            input,
+            &trait_path,
+            |_| false,
+            |_| None,
+            |input| input.borrow_mut_bound(),
+        );
+        let method = quote! {
+            #[inline]
+            fn borrow_mut(&mut self) -> &mut #ty {
+                &mut self.#member
+            }
+        };
+        (trait_path, generics, method)
+    } else {
+        let trait_path = borrow_trait_path();
+        let generics = utils::build_impl_generics(
+            input,
+            &trait_path,
+            |_| false,
+            |_| None,
+            |input| input.borrow_bound(),
+        );
+        let method = quote! {
+            #[inline]
+            fn borrow(&self) -> &#ty {
+                &self.#member
+            }
+        };
+        (trait_path, generics, method)
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path<#ty> for #name #ty_generics #where_clause {
+            #method
+        }
+    })
+}
+
+/// `Borrow` requires the `Hash`, `Eq` and `Ord` of the structure to behave like those of its
+/// field. Report the attributes of the traits derived by *derivative* that change them.
+fn check_borrow_contract(
+    input: &ast::Input,
+    field: &ast::Field,
+    trait_name: &str,
+) -> Result<(), Error> {
+    let attrs = &input.attrs;
+    let field_attrs = &field.attrs;
+    let hash = attrs.hash.is_some();
+    let partial_eq = attrs.partial_eq.is_some();
+    let partial_ord = attrs.partial_ord.is_some();
+    let ord = attrs.ord.is_some();
+
+    let changed_by = [
+        ("Hash=\"ignore\"", hash && field_attrs.ignore_hash()),
+        ("Hash(hash_with)", hash && field_attrs.hash_with().is_some()),
+        (
+            "PartialEq=\"ignore\"",
+            partial_eq && field_attrs.ignore_partial_eq(),
+        ),
+        (
+            "PartialEq(compare_with)",
+            partial_eq && field_attrs.partial_eq_compare_with().is_some(),
+        ),
+        (
+            "PartialOrd=\"ignore\"",
+            partial_ord && field_attrs.ignore_partial_ord(),
+        ),
+        (
+            "PartialOrd(partial_cmp_with)",
+            partial_ord && field_attrs.partial_ord_partial_cmp_with().is_some(),
+        ),
+        (
+            "PartialOrd(reverse)",
+            partial_ord && field_attrs.partial_ord_reverse(),
+        ),
+        ("Ord=\"ignore\"", ord && field_attrs.ignore_ord()),
+        ("Ord(cmp_with)", ord && field_attrs.ord_cmp_with().is_some()),
+        ("Ord(reverse)", ord && field_attrs.ord_reverse()),
+    ];
+    let (option, span) = if hash && attrs.hash_type_tag() {
+        ("Hash(type_tag)", input.ident.span())
+    } else {
+        match changed_by.iter().find(|&&(_, changed)| changed) {
+            Some(&(option, _)) => (option, field.span()),
+            None => return Ok(()),
+        }
+    };
+
+    Err(Error::conflict(
+        format!(
+            "`{}` can't be derived with `{}`, which makes the structure hash or compare \
+             differently from its field",
+            trait_name, option
+        ),
+        span,
+    ))
+}
+
+/// Derive `From<Field>` for `input`, and `From<Input>` for its field with `From="into"`.
+fn derive_from(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let field = match input.body {
+        ast::Body::Struct(_, ref fields) if fields.len() == 1 => &fields[0],
+        _ => {
+            return Err(Error::unsupported(
+                "`From` can only be derived on structures with exactly one field",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let ty = field.ty;
+    let from_trait_path = from_trait_path();
+    let generics = utils::build_impl_generics(
+        input,
+        &from_trait_path,
+        |_| false,
+        |_| None,
+        |input| input.from_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (construct, field_value) = match field.ident {
+        Some(ref ident) => (quote!(#name { #ident: __value }), quote!(__value.#ident)),
+        None => (quote!(#name(__value)), quote!(__value.0)),
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    let into = if input.attrs.from_into() {
+        // `impl<T> From<Wrapper<T>> for T` is rejected by the orphan rules.
+        if is_ty_param(input.generics, ty) {
+            return Err(Error::unsupported(
+                "`From(into)` can't be derived when the field is a type parameter",
+                field.span(),
+            ));
+        }
+
+        Some(quote! {
+            #[automatically_derived]
+            #impl_attrs
+            impl #impl_generics #from_trait_path<#name #ty_generics> for #ty #where_clause {
+                #[inline]
+                fn from(__value: #name #ty_generics) -> Self {
+                    #field_value
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #from_trait_path<#ty> for #name #ty_generics #where_clause {
+            #[inline]
+            fn from(__value: #ty) -> Self {
+                #construct
+            }
+        }
+
+        #into
+    })
+}
+
+/// Derive `TryFrom<Field>` for a structure, which is checked by its `validate_with` function, or
+/// `TryFrom<Repr>` for an enumeration, from the discriminants of its variants.
+fn derive_try_from(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+    let try_from_trait_path = try_from_trait_path();
+    let from_trait_path = from_trait_path();
+    let result_path = result_path();
+
+    let validate = input.attrs.try_from_validate_with().map(|validate_fn| {
+        quote! {
+            if let #result_path::Err(__error) = #validate_fn(&__value) {
+                return #result_path::Err(__error);
+            }
+        }
+    });
+
+    let (source, error_ty, body, error_def) = match input.body {
+        ast::Body::Struct(_, ref fields) if fields.len() == 1 => {
+            let field = &fields[0];
+            // `From` implies `TryFrom` for the same type, with `Infallible` as error.
+            if input.attrs.from.is_some() {
+                return Err(Error::unsupported(
+                    "`TryFrom` can't be derived on a structure that also derives `From`",
+                    input.ident.span(),
+                ));
+            }
+            if validate.is_none() {
+                return Err(Error::unsupported(
+                    "`TryFrom` on a structure needs `TryFrom(validate_with=\"…\")`, use `From` \
+                     for an infallible conversion",
+                    input.ident.span(),
+                ));
+            }
+            let error_ty = match input.attrs.try_from_error() {
+                Some(error_ty) => quote!(#error_ty),
+                None => {
+                    return Err(Error::unsupported(
+                        "`TryFrom` on a structure needs `TryFrom(error=\"…\")`, the error type \
+                         of its `validate_with` function",
+                        input.ident.span(),
+                    ));
+                }
+            };
+
+            let ty = field.ty;
+            let construct = match field.ident {
+                Some(ref ident) => quote!(#name { #ident: __value }),
+                None => quote!(#name(__value)),
+            };
+
+            (
+                quote!(#ty),
+                error_ty,
+                quote!(#result_path::Ok(#construct)),
+                None,
+            )
+        }
+        ast::Body::Struct(..) => {
+            return Err(Error::unsupported(
+                "`TryFrom` can only be derived on structures with exactly one field",
+                input.ident.span(),
+            ));
+        }
+        ast::Body::Enum(ref variants) => {
+            let repr = input
+                .attrs
+                .repr
+                .clone()
+                .unwrap_or_else(|| syn::Ident::new("isize", proc_macro2::Span::call_site()));
+
+            let mut arms = Vec::new();
+            for variant in variants {
+                match variant.style {
+                    ast::Style::Unit => (),
+                    ast::Style::Struct | ast::Style::Tuple => {
+                        return Err(Error::unsupported(
+                            "`TryFrom` can only be derived on enumerations whose variants have no \
+                             fields",
+                            variant.ident.span(),
+                        ));
+                    }
+                }
+
+                let variant_ident = &variant.ident;
+                arms.push(quote! {
+                    __value if __value == #name::#variant_ident as #repr => {
+                        #result_path::Ok(#name::#variant_ident)
+                    }
+                });
+            }
+
+            let (error_ty, error, error_def) = match input.attrs.try_from_error() {
+                Some(error_ty) => (
+                    quote!(#error_ty),
+                    quote!(#from_trait_path::from(__value)),
+                    None,
+                ),
+                None => {
+                    let error_ty = syn::Ident::new(
+                        &format!("TryFrom{}Error", name),
+                        proc_macro2::Span::call_site(),
+                    );
+                    (
+                        quote!(#error_ty),
+                        quote!(#error_ty(__value)),
+                        Some(error_def(input, &error_ty, &repr)),
+                    )
+                }
+            };
+
+            let body = quote! {
+                match __value {
+                    #(#arms)*
+                    _ => #result_path::Err(#error),
+                }
+            };
+
+            (quote!(#repr), error_ty, body, error_def)
+        }
+    };
+
+    let generics = utils::build_impl_generics(
+        input,
+        &try_from_trait_path,
+        |_| false,
+        |_| None,
+        |input| input.try_from_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #error_def
+
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #try_from_trait_path<#source> for #name #ty_generics #where_clause {
+            type Error = #error_ty;
+
+            fn try_from(__value: #source) -> #result_path<Self, Self::Error> {
+                #validate
+                #body
+            }
+        }
+    })
+}
+
+/// Generate the `TryFrom<Name>Error` type, returned when no variant has the discriminant.
+fn error_def(
+    input: &ast::Input,
+    error_ty: &syn::Ident,
+    repr: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let fmt_path = fmt_path();
+    let doc = format!(
+        "The error returned when converting a [`{}`] from an integer fails, with the integer.",
+        name
+    );
+    let message = format!("`{{}}` is not the discriminant of a variant of `{}`", name);
+
+    let error_impl = if cfg!(feature = "use_core") {
+        None
+    } else {
+        Some(quote! {
+            #[automatically_derived]
+            impl ::std::error::Error for #error_ty {}
+        })
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct #error_ty(pub #repr);
+
+        #[automatically_derived]
+        #impl_attrs
+        impl #fmt_path::Display for #error_ty {
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                __f.write_fmt(format_args!(#message, self.0))
+            }
+        }
+
+        #error_impl
+    }
+}
+
+/// Whether `ty` is one of the type parameters of `generics`.
+fn is_ty_param(generics: &syn::Generics, ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Path(ref ty) => {
+            ty.qself.is_none()
+                && ty.path.leading_colon.is_none()
+                && ty.path.segments.len() == 1
+                && generics
+                    .type_params()
+                    .any(|param| param.ident == ty.path.segments[0].ident)
+        }
+        _ => false,
+    }
+}
+
+/// Return the path of the `AsRef` trait, that is `::std::convert::AsRef`.
+fn as_ref_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::AsRef)
+    } else {
+        parse_quote!(::std::convert::AsRef)
+    }
+}
+
+/// Return the path of the `AsMut` trait, that is `::std::convert::AsMut`.
+fn as_mut_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::AsMut)
+    } else {
+        parse_quote!(::std::convert::AsMut)
+    }
+}
+
+/// Return the path of the `Borrow` trait, that is `::std::borrow::Borrow`.
+fn borrow_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::borrow::Borrow)
+    } else {
+        parse_quote!(::std::borrow::Borrow)
+    }
+}
+
+/// Return the path of the `BorrowMut` trait, that is `::std::borrow::BorrowMut`.
+fn borrow_mut_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::borrow::BorrowMut)
+    } else {
+        parse_quote!(::std::borrow::BorrowMut)
+    }
+}
+
+/// Return the path of the `From` trait, that is `::std::convert::From`.
+fn from_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::From)
+    } else {
+        parse_quote!(::std::convert::From)
+    }
+}
+
+/// Return the path of the `TryFrom` trait, that is `::std::convert::TryFrom`.
+fn try_from_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::TryFrom)
+    } else {
+        parse_quote!(::std::convert::TryFrom)
+    }
+}
+
+/// Return the path of `Result`, that is `::std::result::Result`.
+fn result_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::result::Result)
+    } else {
+        parse_quote!(::std::result::Result)
+    }
+}
+
+/// Return the path of the `fmt` module, that is `::std::fmt`.
+fn fmt_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::fmt)
+    } else {
+        parse_quote!(::std::fmt)
+    }
+}
@@ -2,87 +2,588 @@ use proc_macro2;
 
 use ast;
 use attr;
+use bindgen;
+use bound;
 use matcher;
 use syn;
 use utils;
+use {allow_deprecated, impl_attrs, Error, TraitGenerator};
 
-pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
+/// The generator for `Debug`.
+pub struct DebugGenerator;
+
+impl TraitGenerator for DebugGenerator {
+    fn name(&self) -> &'static str {
+        "Debug"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.debug.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        for field in input.body.all_fields() {
+            if let (Some(ident), Some(_)) = (field.ident.as_ref(), field.attrs.debug_name()) {
+                return Err(Error::malformed(
+                    "`Debug(name)` can only be used on the fields of a tuple, this field is \
+                     already named, use `Debug(rename)` instead",
+                    ident.span(),
+                ));
+            }
+            if let (None, Some(_)) = (field.ident.as_ref(), field.attrs.debug_rename()) {
+                return Err(Error::malformed(
+                    "`Debug(rename)` can only be used on named fields, use `Debug(name)` on the \
+                     fields of a tuple",
+                    field.span(),
+                ));
+            }
+            let named_only = if field.attrs.debug_flatten() {
+                Some("flatten")
+            } else if field.attrs.debug_skip_if_default() {
+                Some("skip_if_default")
+            } else if field.attrs.debug_skip_if_none() {
+                Some("skip_if_none")
+            } else {
+                None
+            };
+            if let (None, Some(option)) = (field.ident.as_ref(), named_only) {
+                return Err(Error::malformed(
+                    format!("`Debug({})` can only be used on named fields", option),
+                    field.span(),
+                ));
+            }
+            if field.attrs.debug_sort_maps() {
+                if cfg!(feature = "use_core") {
+                    return Err(Error::unsupported(
+                        "`Debug(sort_maps)` can't be used with the `use_core` feature, as it \
+                         needs a `Vec`",
+                        field.span(),
+                    ));
+                }
+                if sorted_entry_types(field.ty).is_none() {
+                    return Err(Error::malformed(
+                        "`Debug(sort_maps)` can only be used on a map or a set whose type gives \
+                         its type arguments, eg. `HashMap<K, V>` or `HashSet<T>`",
+                        field.span(),
+                    ));
+                }
+            }
+        }
+
+        let delegated = input.body.all_fields().iter().any(|f| f.attrs.debug_delegate());
+        if input.attrs.debug_flattenable() && delegated {
+            return Err(Error::unsupported(
+                "`Debug(delegate)` can't be used on a structure marked `Debug(flattenable)`, \
+                 whose fields are shown by its parent",
+                input.ident.span(),
+            ));
+        }
+
+        if input.attrs.debug_flattenable()
+            && !matches!(input.body, ast::Body::Struct(ast::Style::Struct, _))
+        {
+            return Err(Error::unsupported(
+                "`Debug(flattenable)` can only be used on structures with named fields",
+                input.ident.span(),
+            ));
+        }
+
+        if input.attrs.debug_with_discriminant() {
+            let fieldless = match input.body {
+                ast::Body::Enum(ref variants) => variants.iter().all(|v| v.fields.is_empty()),
+                ast::Body::Struct(..) => false,
+            };
+            if !fieldless {
+                return Err(Error::unsupported(
+                    "`Debug(with_discriminant)` can only be used on an enumeration whose \
+                     variants have no fields",
+                    input.ident.span(),
+                ));
+            }
+        }
+
+        if cfg!(feature = "use_core") && input.attrs.debug_max_depth().is_some() {
+            return Err(Error::unsupported(
+                "`Debug(max_depth)` can't be used with the `use_core` feature, as it needs a \
+                 thread local",
+                input.ident.span(),
+            ));
+        }
+
+        Ok(derive(input))
+    }
+}
+
+fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
     let debug_trait_path = debug_trait_path();
     let fmt_path = fmt_path();
 
-    let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
+    let body = match input.attrs.debug_format_with() {
+        Some(format_fn) => quote!(#format_fn(self, __f)),
+        None => {
+            let arms = fmt_arms(input, false, false);
+            quote! {
+                match *self {
+                    #arms
+                }
+            }
+        }
+    };
+
+    let name = &input.ident;
+
+    let mut generics = utils::build_impl_generics(
         input,
-        |_, arm_name, style, attrs, bis| {
-            let field_prints = bis.iter().filter_map(|bi| {
-                if bi.field.attrs.ignore_debug() {
+        &debug_trait_path,
+        needs_debug_bound,
+        |field| field.debug_bound(),
+        |input| input.debug_bound(),
+    );
+    // The generic fields compared to their default value or copied out of a packed structure,
+    // and the generic keys that are sorted, need the bounds to do so, unless the bounds are given.
+    if input.attrs.debug_bound().is_none() {
+        let default_trait_path = default_trait_path();
+        let partial_eq_trait_path = partial_eq_trait_path();
+        let fields = input
+            .body
+            .all_fields()
+            .into_iter()
+            .filter(|field| field.attrs.debug_bound().is_none());
+        let mut predicates = Vec::<syn::WherePredicate>::new();
+        for field in fields {
+            let ty = field.ty;
+            if skips_default(input, field) && bound::uses_ty_params(input.generics, ty) {
+                predicates.push(parse_quote!(#ty: #partial_eq_trait_path + #default_trait_path));
+            }
+            if input.attrs.packed && bound::uses_ty_params(input.generics, ty) {
+                let copy_trait_path = copy_trait_path();
+                predicates.push(parse_quote!(#ty: #copy_trait_path));
+            }
+            let sorted_key = sorted_entry_types(ty)
+                .filter(|_| field.attrs.debug_sort_maps())
+                .map(|(key, _)| key);
+            if let Some(key) = sorted_key.filter(|key| bound::uses_ty_params(input.generics, key)) {
+                let ord_trait_path = ord_trait_path();
+                predicates.push(parse_quote!(#key: #ord_trait_path));
+            }
+        }
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let verbose_debug = if input.attrs.debug_verbose() {
+        Some(verbose_debug(input))
+    } else {
+        None
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    let flatten = if input.attrs.debug_flattenable() {
+        let body = fmt_arms(input, false, true);
+        let vis = input.vis;
+        Some(quote! {
+            #impl_attrs
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Adds the fields of this value to `builder`, for `Debug(flatten)`.
+                #[doc(hidden)]
+                #[allow(unused_mut)]
+                #vis fn __derivative_debug_fields<'_derivative, '_derivative_f: '_derivative>(
+                    &self,
+                    mut builder: #fmt_path::DebugStruct<'_derivative, '_derivative_f>,
+                ) -> #fmt_path::DebugStruct<'_derivative, '_derivative_f> {
+                    match *self {
+                        #body
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let depth_limit = input.attrs.debug_max_depth().map(depth_limit);
+
+    quote! {
+        #verbose_debug
+
+        #flatten
+
+        // Not `#[automatically_derived]`: the dead code lint ignores such `Debug` implementations,
+        // and would report the `format_with` functions and the fields they read as unused.
+        #impl_attrs
+        impl #impl_generics #debug_trait_path for #name #ty_generics #where_clause {
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                #depth_limit
+                #body
+            }
+        }
+    }
+}
+
+/// Generate the start of `fmt` for `Debug(max_depth)`: it counts the values of the type being
+/// formatted on the thread, and shows `…` instead of those nested deeper than `max_depth`.
+fn depth_limit(max_depth: usize) -> proc_macro2::TokenStream {
+    quote! {
+        thread_local!(static __DEPTH: ::std::cell::Cell<usize> = ::std::cell::Cell::new(0));
+
+        // Restores the depth even if a field panics.
+        struct __DepthGuard(usize);
+
+        impl ::std::ops::Drop for __DepthGuard {
+            fn drop(&mut self) {
+                __DEPTH.with(|depth| depth.set(self.0));
+            }
+        }
+
+        let __depth = __DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        let _guard = __DepthGuard(__depth);
+        if __depth >= #max_depth {
+            return __f.write_str("\u{2026}");
+        }
+    }
+}
+
+/// Build the match arms formatting the fields of `input`. With `verbose`, the ignored fields are
+/// formatted too. With `fields_only`, the arms add the fields to the `builder` of the type that
+/// flattens `input`, and return it.
+fn fmt_arms(input: &ast::Input, verbose: bool, fields_only: bool) -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = fmt_path();
+
+    // The fields of a packed structure may be unaligned, so they are copied out of it and the
+    // copies are borrowed instead.
+    let binding_style = if input.attrs.packed {
+        matcher::BindingStyle::Move
+    } else {
+        matcher::BindingStyle::Ref
+    };
+
+    matcher::Matcher::new(binding_style).build_arms(
+        input,
+        |arm_path, arm_name, style, attrs, bis| {
+            let arm_name = variant_name(input, arm_name);
+            // The verbose output shows all the fields, even with `Debug(delegate)`.
+            let delegated = !verbose && bis.iter().any(|bi| bi.field.attrs.debug_delegate());
+            let transparent =
+                !fields_only && (delegated || is_transparent(input, attrs, bis.len()));
+            // The fields that aren't `Copy` are reported where they are declared.
+            let copies = if input.attrs.packed {
+                let copy_trait_path = copy_trait_path();
+                let copies = bis.iter().map(|bi| {
+                    let arg = &bi.ident;
+                    let ty = bi.field.ty;
+                    let check = quote_spanned!(bi.field.span()=> __derivative_assert_copy::<#ty>());
+                    quote! {
+                        #check;
+                        let #arg = &#arg;
+                    }
+                });
+                Some(quote! {
+                    fn __derivative_assert_copy<T: #copy_trait_path>() {}
+                    #(#copies)*
+                })
+            } else {
+                None
+            };
+
+            if input.attrs.debug_with_discriminant() {
+                let format = qualified_name(input, format!("{}(={{}})", arm_name));
+                return quote! {
+                    #fmt_path::Formatter::write_fmt(__f, format_args!(#format, #arm_path as isize))
+                };
+            }
+
+            // A tuple with named fields is shown as a structure, the other fields being labelled
+            // with their index.
+            let labelled = bis.iter().any(|bi| bi.field.attrs.debug_name().is_some());
+
+            let field_prints = bis.iter().enumerate().filter_map(|(i, bi)| {
+                if bi.field.attrs.ignore_debug() && !verbose {
                     return None;
                 }
 
-                if attrs.debug_transparent() {
-                    return Some(quote!{
-                        #debug_trait_path::fmt(__arg_0, __f)
+                let arg = &bi.ident;
+                let span = bi.field.span();
+                let value = match bi.field.attrs.bindgen() {
+                    Some(attr::Bindgen::Skip) => quote!(&format_args!("..")),
+                    Some(attr::Bindgen::Opaque) => bindgen::bytes(arg),
+                    None => quote!(#arg),
+                };
+                let value = match (bi.field.attrs.debug_format(), bi.field.attrs.debug_mask()) {
+                    (Some(format), _) => quote!(&format_args!(#format, #value)),
+                    (None, Some(mask)) => quote!(&format_args!("{}", #mask)),
+                    (None, None) => value,
+                };
+
+                let dummy_debug = match bi.field.attrs.debug_format_with() {
+                    Some(format_fn) => Some(format_with(input, bi.field, i, arg, format_fn)),
+                    None if bi.field.attrs.debug_as_pointer() => {
+                        Some(as_pointer(bi.field, arg, input.generics.clone()))
+                    }
+                    None if bi.field.attrs.debug_sort_maps() => {
+                        Some(sorted(bi.field, arg, input.generics.clone()))
+                    }
+                    None => bi.field.attrs.debug_max_len().map(|max_len| {
+                        truncated(bi.field, arg, max_len, input.generics.clone())
+                    }),
+                };
+
+                // The formatter is passed as is, so that `{:#?}`, the width, etc. still apply.
+                if transparent {
+                    return Some(match bi.field.attrs.debug_format_with() {
+                        Some(format_fn) => {
+                            format_with_call(input, bi.field, i, format_fn, quote!(#arg))
+                        }
+                        None if dummy_debug.is_some() => quote! {
+                            #dummy_debug
+                            #debug_trait_path::fmt(&#arg, __f)
+                        },
+                        None => quote!(#debug_trait_path::fmt(#value, __f)),
                     });
                 }
 
-                let arg = &bi.ident;
+                // The builder is moved in and out, so that the same code works in `fmt` and in
+                // `__derivative_debug_fields`, which gets it by value.
+                if bi.field.attrs.debug_flatten() {
+                    let fields = quote_spanned!(span=> #arg.__derivative_debug_fields);
+                    return Some(quote! {
+                        let mut builder = #fields(builder);
+                    });
+                }
 
-                let dummy_debug = bi.field.attrs.debug_format_with().map(|format_fn| {
-                    format_with(bi.field, &arg, format_fn, input.generics.clone())
-                });
+                let label = if bi.field.ident.is_some() || labelled {
+                    Some(field_label(input, bi.field, i))
+                } else {
+                    None
+                };
 
-                let builder = if let Some(ref name) = bi.field.ident {
-                    let name = name.to_string();
+                // Only the reference is spanned, the locals must keep the hygiene of `call_site`.
+                let value = quote_spanned!(span=> &#value);
+                let builder = if let Some(name) = label {
                     quote! {
                         #dummy_debug
-                        let _ = builder.field(#name, &#arg);
+                        let _ = builder.field(#name, #value);
                     }
                 } else {
                     quote! {
                         #dummy_debug
-                        let _ = builder.field(&#arg);
+                        let _ = builder.field(#value);
                     }
                 };
 
-                Some(builder)
+                // The verbose output shows all the fields.
+                let ty = bi.field.ty;
+                let shown = if verbose {
+                    None
+                } else if skips_default(input, bi.field) {
+                    let default_trait_path = default_trait_path();
+                    Some(quote_spanned!(span=> *#arg != <#ty as #default_trait_path>::default()))
+                } else if skips_none(input, bi.field) {
+                    Some(quote_spanned!(span=> #arg.is_some()))
+                } else {
+                    None
+                };
+                match shown {
+                    Some(shown) => Some(quote! {
+                        if #shown {
+                            #builder
+                        }
+                    }),
+                    None => Some(builder),
+                }
             });
 
             let method = match style {
                 ast::Style::Struct => "debug_struct",
+                ast::Style::Tuple if labelled => "debug_struct",
                 ast::Style::Tuple | ast::Style::Unit => "debug_tuple",
             };
             let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+            let name = qualified_name(input, arm_name);
+
+            // `DebugTuple::finish_non_exhaustive` is too recent, the marker is shown as a field.
+            let elided = !verbose
+                && input.attrs.debug_non_exhaustive_hint()
+                && bis.iter().any(|bi| bi.field.attrs.ignore_debug());
+            let finish = if elided && method == "debug_tuple" {
+                quote! {
+                    let _ = builder.field(&format_args!(".."));
+                    builder.finish()
+                }
+            } else if elided {
+                quote!(builder.finish_non_exhaustive())
+            } else {
+                quote!(builder.finish())
+            };
 
-            if attrs.debug_transparent() {
+            if transparent {
+                quote! {
+                    #copies
+                    #(#field_prints)*
+                }
+            } else if fields_only {
                 quote! {
+                    #copies
                     #(#field_prints)*
+                    builder
                 }
             } else {
-                let name = arm_name.to_string();
                 quote! {
+                    #copies
                     let mut builder = __f.#method(#name);
                     #(#field_prints)*
-                    builder.finish()
+                    #finish
                 }
             }
         },
-    );
+    )
+}
+
+/// The name shown for `arm_name`, following the `rename_all` convention if it is a variant.
+pub fn variant_name(input: &ast::Input, arm_name: &syn::Ident) -> String {
+    match (&input.body, input.attrs.rename_all()) {
+        (&ast::Body::Enum(_), Some(rule)) => rule.apply_to_variant(&arm_name.to_string()),
+        _ => arm_name.to_string(),
+    }
+}
+
+/// The string literal `name`, the name of the type or variant, prefixed with the path of the
+/// type with `Debug(qualified)`, eg. `concat!(module_path!(), "::Enum::Variant")`.
+fn qualified_name(input: &ast::Input, name: String) -> proc_macro2::TokenStream {
+    if !input.attrs.debug_qualified() {
+        return quote!(#name);
+    }
+
+    let path = match input.body {
+        ast::Body::Enum(_) => format!("::{}::{}", input.ident, name),
+        ast::Body::Struct(..) => format!("::{}", name),
+    };
+    quote!(concat!(module_path!(), #path))
+}
+
+/// Whether an arm with the attributes `attrs` and `len` fields is shown as its fields, without
+/// its name. It is with `Debug(transparent)` on the structure or variant, and a variant with one
+/// field is with `Debug(transparent)` on the enumeration, unless it is marked
+/// `Debug(transparent="false")` or `Debug="ignore"`.
+pub fn is_transparent(input: &ast::Input, attrs: &attr::Input, len: usize) -> bool {
+    match (attrs.debug_transparent_setting(), &input.body) {
+        (Some(transparent), _) => transparent,
+        (None, &ast::Body::Enum(_)) => {
+            input.attrs.debug_transparent() && len == 1 && !attrs.debug_ignore()
+        }
+        (None, &ast::Body::Struct(..)) => false,
+    }
+}
+
+/// Whether the named field `field` is left out when it is equal to its default value, with
+/// `Debug(skip_if_default)` on the field or the type.
+fn skips_default(input: &ast::Input, field: &ast::Field) -> bool {
+    let skips = field.attrs.debug_skip_if_default() || input.attrs.debug_skip_if_default();
+    skips && field.ident.is_some() && !field.attrs.debug_flatten()
+}
+
+/// Whether the named field `field` is left out when it is `None`, with `Debug(skip_if_none)` on
+/// the field, or on the type if the field is an `Option`.
+fn skips_none(input: &ast::Input, field: &ast::Field) -> bool {
+    let is_option = match *field.ty {
+        syn::Type::Path(ref ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    };
+    let skips = field.attrs.debug_skip_if_none() || (input.attrs.debug_skip_if_none() && is_option);
+    skips && field.ident.is_some() && !field.attrs.debug_flatten()
+}
+
+/// The label of the named field `field`: its `Debug(rename)` if any, or else its name.
+/// The label of `field`, the `index`th of its structure or variant: its name, or the name given
+/// to a tuple field with `Debug(name="…")` or else its index.
+pub fn field_label(input: &ast::Input, field: &ast::Field, index: usize) -> String {
+    match (&field.ident, field.attrs.debug_name()) {
+        (&Some(_), _) => field_name(input, field),
+        (&None, Some(name)) => name.to_string(),
+        (&None, None) => index.to_string(),
+    }
+}
+
+/// The name shown for the named `field`: its `rename` if any, or else its name following the
+/// `Debug(rename_all)` convention of the type.
+pub fn field_name(input: &ast::Input, field: &ast::Field) -> String {
+    let ident = field
+        .ident
+        .as_ref()
+        .expect("A structure field must have a name")
+        .to_string();
+    match (field.attrs.debug_rename(), input.attrs.debug_rename_all()) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(rule)) => rule.apply_to_field(&ident),
+        (None, None) => ident,
+    }
+}
+
+/// Generate the `<Name>VerboseDebug` wrapper, whose `Debug` implementation also shows the ignored
+/// fields, and the inherent `verbose_debug` method returning it.
+fn verbose_debug(input: &ast::Input) -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = fmt_path();
 
     let name = &input.ident;
+    let wrapper = syn::Ident::new(
+        &format!("{}VerboseDebug", name),
+        proc_macro2::Span::call_site(),
+    );
+    let doc = format!(
+        "Wraps a reference to [`{0}`] so that its `Debug` implementation also shows the ignored \
+         fields, see [`{0}::verbose_debug`].",
+        name
+    );
+    let body = fmt_arms(input, true, false);
+
+    let lifetime = syn::GenericParam::Lifetime(syn::LifetimeDef::new(parse_quote!('_derivative)));
 
-    let generics = utils::build_impl_generics(
+    let (input_impl_generics, input_ty_generics, input_where_clause) =
+        input.generics.split_for_impl();
+
+    let mut struct_generics = input.generics.clone();
+    struct_generics.params.insert(0, lifetime.clone());
+    let struct_where_clause = &struct_generics.where_clause;
+
+    let mut generics = utils::build_impl_generics(
         input,
         &debug_trait_path,
-        needs_debug_bound,
+        |field| field.debug_bound().is_none(),
         |field| field.debug_bound(),
         |input| input.debug_bound(),
     );
+    generics.params.insert(0, lifetime);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let impl_attrs = impl_attrs(input);
+    let allow_deprecated = allow_deprecated(input);
+
     quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #debug_trait_path for #name #ty_generics #where_clause {
+        #[doc = #doc]
+        #allow_deprecated
+        pub struct #wrapper #struct_generics (&'_derivative #name #input_ty_generics)
+            #struct_where_clause;
+
+        #impl_attrs
+        impl #input_impl_generics #name #input_ty_generics #input_where_clause {
+            /// Returns a wrapper whose `Debug` implementation shows all the fields, including
+            /// the ignored ones.
+            pub fn verbose_debug<'_derivative>(&'_derivative self) -> #wrapper #ty_generics {
+                #wrapper(self)
+            }
+        }
+
+        #impl_attrs
+        impl #impl_generics #debug_trait_path for #wrapper #ty_generics #where_clause {
             fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
-                match *self {
+                match *self.0 {
                     #body
                 }
             }
@@ -91,7 +592,10 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
 }
 
 fn needs_debug_bound(attrs: &attr::Field) -> bool {
-    !attrs.ignore_debug() && attrs.debug_bound().is_none()
+    !attrs.ignore_debug()
+        && attrs.debug_mask().is_none()
+        && !attrs.debug_as_pointer()
+        && attrs.debug_bound().is_none()
 }
 
 /// Return the path of the `Debug` trait, that is `::std::fmt::Debug`.
@@ -112,6 +616,51 @@ fn fmt_path() -> syn::Path {
     }
 }
 
+/// Return the path of the `Default` trait, that is `::std::default::Default`.
+fn default_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::default::Default)
+    } else {
+        parse_quote!(::std::default::Default)
+    }
+}
+
+/// Return the path of the `PartialEq` trait, that is `::std::cmp::PartialEq`.
+fn partial_eq_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::cmp::PartialEq)
+    } else {
+        parse_quote!(::std::cmp::PartialEq)
+    }
+}
+
+/// Return the path of the `IntoIterator` trait, that is `::std::iter::IntoIterator`.
+fn into_iterator_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::iter::IntoIterator)
+    } else {
+        parse_quote!(::std::iter::IntoIterator)
+    }
+}
+
+/// Return the path of the `Copy` trait, that is `::std::marker::Copy`.
+fn copy_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::marker::Copy)
+    } else {
+        parse_quote!(::std::marker::Copy)
+    }
+}
+
+/// Return the path of the `Ord` trait, that is `::std::cmp::Ord`.
+fn ord_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::cmp::Ord)
+    } else {
+        parse_quote!(::std::cmp::Ord)
+    }
+}
+
 /// Return the path of the `PhantomData` type, that is `::std::marker::PhantomData`.
 fn phantom_path() -> syn::Path {
     if cfg!(feature = "use_core") {
@@ -121,23 +670,261 @@ fn phantom_path() -> syn::Path {
     }
 }
 
-fn format_with(
+/// Rebind `arg_n`, a reference to the field `f` of `input`, the `index`th of its structure or
+/// variant, to a wrapper whose `Debug` implementation calls `format_fn`.
+pub fn format_with(
+    input: &ast::Input,
     f: &ast::Field,
+    index: usize,
     arg_n: &syn::Ident,
     format_fn: &syn::Path,
-    mut generics: syn::Generics,
 ) -> proc_macro2::TokenStream {
     let debug_trait_path = debug_trait_path();
     let fmt_path = fmt_path();
+    let call = format_with_call(input, f, index, format_fn, quote!(&self.0));
+    let method = quote! {
+        fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+            #call
+        }
+    };
+
+    wrap_field(
+        f,
+        arg_n,
+        &debug_trait_path,
+        &method,
+        f.attrs.debug_bound().unwrap_or(&[]),
+        input.generics.clone(),
+    )
+}
+
+/// Call `format_fn` on `value`, the field `f` of `input`, the `index`th of its structure or
+/// variant, and `__f`, with the label of the field in between with `Debug(pass_name)`.
+fn format_with_call(
+    input: &ast::Input,
+    f: &ast::Field,
+    index: usize,
+    format_fn: &syn::Path,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if f.attrs.debug_pass_name() {
+        let label = field_label(input, f, index);
+        quote!(#format_fn(#value, #label, __f))
+    } else {
+        quote!(#format_fn(#value, __f))
+    }
+}
+
+/// Rebind `arg_n`, a reference to the field `f`, to a wrapper whose `Debug` implementation shows
+/// the address the field points to, followed by the strong count of an `Rc` or `Arc`.
+fn as_pointer(
+    f: &ast::Field,
+    arg_n: &syn::Ident,
+    generics: syn::Generics,
+) -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = fmt_path();
+    let ty = f.ty;
+    let is_counted = match *ty {
+        syn::Type::Path(ref ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| segment.ident == "Rc" || segment.ident == "Arc"),
+        _ => false,
+    };
+    let body = if is_counted {
+        quote! {
+            #fmt_path::Pointer::fmt(self.0, __f)?;
+            #fmt_path::Formatter::write_fmt(
+                __f,
+                format_args!(" (strong: {})", <#ty>::strong_count(self.0)),
+            )
+        }
+    } else {
+        quote!(#fmt_path::Pointer::fmt(self.0, __f))
+    };
+    let method = quote! {
+        fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+            #body
+        }
+    };
+
+    wrap_field(
+        f,
+        arg_n,
+        &debug_trait_path,
+        &method,
+        f.attrs.debug_bound().unwrap_or(&[]),
+        generics,
+    )
+}
+
+/// Rebind `arg_n`, a reference to the field `f`, a collection, to a wrapper whose `Debug`
+/// implementation shows its first `max_len` elements and how many more there are.
+fn truncated(
+    f: &ast::Field,
+    arg_n: &syn::Ident,
+    max_len: usize,
+    generics: syn::Generics,
+) -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = fmt_path();
+    let into_iterator_path = into_iterator_path();
+
+    // A reference to a collection, eg. `&[u8]`, is iterated through.
+    let (ty, collection) = match *f.ty {
+        syn::Type::Reference(ref ty) => (&*ty.elem, quote!(&**self.0)),
+        ref ty => (ty, quote!(self.0)),
+    };
+    let method = quote! {
+        fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+            let mut list = __f.debug_list();
+            let mut len = 0usize;
+            for item in #collection {
+                if len < #max_len {
+                    let _ = list.entry(&item);
+                }
+                len += 1;
+            }
+            if len > #max_len {
+                let _ = list.entry(&format_args!("\u{2026} ({} more)", len - #max_len));
+            }
+            list.finish()
+        }
+    };
+
+    let mut bounds = f.attrs.debug_bound().unwrap_or(&[]).to_vec();
+    bounds.push(parse_quote!(&'_derivative #ty: #into_iterator_path));
+    bounds.push(parse_quote!(
+        <&'_derivative #ty as #into_iterator_path>::Item: #debug_trait_path
+    ));
+
+    wrap_field(f, arg_n, &debug_trait_path, &method, &bounds, generics)
+}
+
+/// The types of the keys and of the values of a map, or of the elements of a set, given by the
+/// type arguments of `ty` or of the type it references. A type whose name ends with `Map` is a
+/// map, other types are sets.
+fn sorted_entry_types(ty: &syn::Type) -> Option<(&syn::Type, Option<&syn::Type>)> {
+    let ty = match *ty {
+        syn::Type::Reference(ref ty) => &*ty.elem,
+        ref ty => ty,
+    };
+    let segment = match *ty {
+        syn::Type::Path(ref ty) => ty.path.segments.iter().last()?,
+        _ => return None,
+    };
+    let mut args = match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) => {
+            args.args.iter().filter_map(|arg| match *arg {
+                syn::GenericArgument::Type(ref ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => return None,
+    };
+    let key = args.next()?;
+    if segment.ident.to_string().ends_with("Map") {
+        Some((key, Some(args.next()?)))
+    } else {
+        Some((key, None))
+    }
+}
+
+/// Rebind `arg_n`, a reference to the field `f`, a map or a set, to a wrapper whose `Debug`
+/// implementation collects its entries and shows them sorted by key.
+fn sorted(f: &ast::Field, arg_n: &syn::Ident, generics: syn::Generics) -> proc_macro2::TokenStream {
+    let debug_trait_path = debug_trait_path();
+    let fmt_path = fmt_path();
+    let into_iterator_path = into_iterator_path();
+    let ord_trait_path = ord_trait_path();
+
+    let (ty, collection) = match *f.ty {
+        syn::Type::Reference(ref ty) => (&*ty.elem, quote!(&**self.0)),
+        ref ty => (ty, quote!(self.0)),
+    };
+    let (key, value) =
+        sorted_entry_types(f.ty).expect("`Debug(sort_maps)` is checked to be on a map or a set");
+
+    // The entries of a concrete type are known, the keys are checked to be ordered where they are
+    // sorted.
+    let span = f.span();
+    let mut bounds = f.attrs.debug_bound().unwrap_or(&[]).to_vec();
+    let is_generic = bound::uses_ty_params(&generics, ty);
+    if is_generic {
+        bounds.push(parse_quote!(#key: #ord_trait_path + #debug_trait_path));
+    }
+    let method = match value {
+        Some(value) => {
+            if is_generic {
+                bounds.push(parse_quote!(#value: #debug_trait_path));
+                bounds.push(parse_quote!(
+                    &'_derivative #ty: #into_iterator_path<
+                        Item = (&'_derivative #key, &'_derivative #value),
+                    >
+                ));
+            }
+            let sort =
+                quote_spanned!(span=> entries.sort_by(|a, b| #ord_trait_path::cmp(a.0, b.0)));
+            quote! {
+                fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                    let mut entries: ::std::vec::Vec<_> =
+                        #into_iterator_path::into_iter(#collection).collect();
+                    #sort;
+                    __f.debug_map().entries(entries).finish()
+                }
+            }
+        }
+        None => {
+            if is_generic {
+                bounds.push(parse_quote!(
+                    &'_derivative #ty: #into_iterator_path<Item = &'_derivative #key>
+                ));
+            }
+            let sort = quote_spanned!(span=> entries.sort());
+            quote! {
+                fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                    let mut entries: ::std::vec::Vec<_> =
+                        #into_iterator_path::into_iter(#collection).collect();
+                    #sort;
+                    __f.debug_set().entries(entries).finish()
+                }
+            }
+        }
+    };
+
+    wrap_field(f, arg_n, &debug_trait_path, &method, &bounds, generics)
+}
+
+/// Rebind `arg_n`, a reference to the field `f`, to a wrapper implementing `trait_path` with
+/// `method`, in which `self.0` is the reference. `bounds` are added to the bounds of the
+/// implementation.
+pub fn wrap_field(
+    f: &ast::Field,
+    arg_n: &syn::Ident,
+    trait_path: &syn::Path,
+    method: &proc_macro2::TokenStream,
+    bounds: &[syn::WherePredicate],
+    mut generics: syn::Generics,
+) -> proc_macro2::TokenStream {
     let phantom_path = phantom_path();
 
-    let ctor_generics = generics.clone();
+    // The lifetimes are inferred, they can't be given since `'_derivative` is added below.
+    let mut ctor_generics = generics.clone();
+    ctor_generics.params = ctor_generics
+        .params
+        .into_iter()
+        .filter(|param| !matches!(*param, syn::GenericParam::Lifetime(_)))
+        .collect();
     let (_, ctor_ty_generics, _) = ctor_generics.split_for_impl();
+    let ctor_ty_generics = ctor_ty_generics.as_turbofish();
 
     generics
         .make_where_clause()
         .predicates
-        .extend(f.attrs.debug_bound().unwrap_or(&[]).iter().cloned());
+        .extend(bounds.iter().cloned());
 
     generics
         .params
@@ -157,9 +944,9 @@ fn format_with(
 
             syn::WherePredicate::Type(syn::PredicateType {
                 lifetimes: None,
-                bounded_ty: syn::Type::Path(syn::TypePath { qself: None, path: path }),
+                bounded_ty: syn::Type::Path(syn::TypePath { qself: None, path }),
                 colon_token: Default::default(),
-                bounds: bounds,
+                bounds,
             })
         })
         .collect::<Vec<_>>();
@@ -172,20 +959,27 @@ fn format_with(
 
     let ty = f.ty;
 
-    // Leave off the type parameter bounds, defaults, and attributes
-    let phantom = generics.type_params().map(|tp| &tp.ident);
+    // Leave off the type parameter bounds, defaults, and attributes. The lifetimes are used in
+    // the marker too, as the type of the field may not use them all.
+    let phantom_lifetimes = generics.lifetimes().map(|lt| {
+        let lifetime = &lt.lifetime;
+        quote!(&#lifetime ())
+    });
+    let phantom_types = generics.type_params().map(|tp| {
+        let ident = &tp.ident;
+        quote!(#ident)
+    });
+    let phantom: Vec<_> = phantom_lifetimes.chain(phantom_types).collect();
 
     quote!(
         let #arg_n = {
-            struct Dummy #ty_generics (&'_derivative #ty, #phantom_path <(#(#phantom),*)>) #where_clause;
+            struct Dummy #impl_generics (&'_derivative #ty, #phantom_path <(#(#phantom),*)>) #where_clause;
 
-            impl #impl_generics #debug_trait_path for Dummy #ty_generics #where_clause {
-                fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
-                    #format_fn(&self.0, __f)
-                }
+            impl #impl_generics #trait_path for Dummy #ty_generics #where_clause {
+                #method
             }
 
-            Dummy:: #ctor_ty_generics (#arg_n, #phantom_path)
+            Dummy #ctor_ty_generics (#arg_n, #phantom_path)
         };
     )
 }
@@ -3,46 +3,87 @@ use proc_macro2;
 use ast;
 use attr;
 use syn;
+use syn::spanned::Spanned;
 use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Default`.
+pub struct DefaultGenerator;
+
+impl TraitGenerator for DefaultGenerator {
+    fn name(&self) -> &'static str {
+        "Default"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.default.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        match input.attrs.default {
+            Some(ref default) => derive(input, default),
+            None => Ok(proc_macro2::TokenStream::new()),
+        }
+    }
+}
 
 /// Derive `Default` for `input`.
-pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::TokenStream {
+fn derive(
+    input: &ast::Input,
+    default: &attr::InputDefault,
+) -> Result<proc_macro2::TokenStream, Error> {
     fn make_variant_data(
         variant_name: &proc_macro2::TokenStream,
         style: ast::Style,
         fields: &[ast::Field],
     ) -> proc_macro2::TokenStream {
-        let default_trait_path = default_trait_path();
-
         match style {
             ast::Style::Struct => {
-                let mut defaults = Vec::new();
+                let defaults: Vec<_> = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| field_value(f, i))
+                    .collect();
+                let mut lets = Vec::new();
+                let mut inits = Vec::new();
+
+                let names: Vec<_> = fields
+                    .iter()
+                    .map(|f| f.ident.as_ref().expect("A structure field must have a name"))
+                    .collect();
 
-                for f in fields {
-                    let name = f
-                        .ident
-                        .as_ref()
-                        .expect("A structure field must have a name");
-                    let default = f.attrs.default_value().map_or_else(
-                        || quote!(#default_trait_path::default()),
-                        |v| quote!(#v),
-                    );
-
-                    defaults.push(quote!(#name: #default));
+                // The values using the previous fields, and the fields they use, are bound first,
+                // in order, so that a `value` can use the previous fields.
+                for (i, f) in fields.iter().enumerate() {
+                    let name = names[i];
+                    let default = &defaults[i];
+                    let is_used = defaults[i + 1..].iter().any(|d| mentions(d.clone(), name));
+                    let uses = names[..i].iter().any(|n| mentions(default.clone(), n));
+
+                    if f.attrs.default_required() {
+                        inits.push(quote!(#name));
+                    } else if is_used || uses {
+                        lets.push(quote!(let #name = #default;));
+                        inits.push(quote!(#name));
+                    } else {
+                        inits.push(quote!(#name: #default));
+                    }
                 }
 
-                quote!(#variant_name { #(#defaults),* })
+                if lets.is_empty() {
+                    quote!(#variant_name { #(#inits),* })
+                } else {
+                    quote!({
+                        #(#lets)*
+                        #variant_name { #(#inits),* }
+                    })
+                }
             }
             ast::Style::Tuple => {
                 let mut defaults = Vec::new();
 
-                for f in fields {
-                    let default = f.attrs.default_value().map_or_else(
-                        || quote!(#default_trait_path::default()),
-                        |v| quote!(#v),
-                    );
-
-                    defaults.push(default);
+                for (i, f) in fields.iter().enumerate() {
+                    defaults.push(field_value(f, i));
                 }
 
                 quote!(#variant_name ( #(#defaults),* ))
@@ -56,58 +97,466 @@ pub fn derive(input: &ast::Input, default: &attr::InputDefault) -> proc_macro2::
     let generics = utils::build_impl_generics(
         input,
         &default_trait_path,
-        |attrs| attrs.default_bound().is_none(),
+        |attrs| attrs.default_bound().is_none() && !attrs.default_required(),
         |field| field.default_bound(),
         |input| input.default_bound(),
     );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let body = match input.body {
-        ast::Body::Enum(ref data) => {
-            let arms = data.iter().filter_map(|variant| {
-                if variant.attrs.default.is_some() {
-                    let vname = &variant.ident;
-
-                    Some(make_variant_data(
-                        &quote!(#name::#vname),
-                        variant.style,
-                        &variant.fields,
-                    ))
-                } else {
-                    None
-                }
-            });
+    let variant = match input.body {
+        ast::Body::Enum(ref data) => Some(default_variant(input, data)?),
+        ast::Body::Struct(..) => None,
+    };
+
+    let constructed: Vec<&ast::Field> = match (variant, &input.body) {
+        (Some(variant), _) => variant.fields.iter().collect(),
+        (None, ast::Body::Struct(_, vd)) => vd.iter().collect(),
+        (None, ast::Body::Enum(_)) => unreachable!("enumerations have a default variant"),
+    };
+    let required: Vec<_> = constructed
+        .iter()
+        .enumerate()
+        .filter(|&(_, f)| f.attrs.default_required())
+        .collect();
+
+    if let Some(&(_, f)) = required.first() {
+        if !default.new && f.attrs.default_unavailable() {
+            return Err(Error::malformed(
+                "this field has no default value since its type can't implement `Default`, give \
+                 it one with `Default(value=\"…\")`, or use `Default(new)` to make it a \
+                 parameter of `new`",
+                f.ty.span(),
+            ));
+        }
+        if !default.new {
+            return Err(Error::malformed(
+                "`Default(required)` needs `Default(new)`, as `Default` can't be implemented",
+                f.ty.span(),
+            ));
+        }
+        if default.reset && matches!(input.body, ast::Body::Enum(_)) {
+            return Err(Error::unsupported(
+                "`Default(reset)` can't be used on an enumeration with `Default(required)` fields",
+                f.ty.span(),
+            ));
+        }
+    }
+
+    if default.const_value {
+        if let Some(f) = constructed.iter().find(|f| f.attrs.default_value().is_none()) {
+            let message = if f.attrs.default_required() && !f.attrs.default_unavailable() {
+                "`Default(const)` can't be used with `Default(required)` fields, as the type has no \
+                 default value"
+            } else if explicit_default(f).is_some() {
+                "`Default(const)` can only use `Default(value=\"…\")`, since this default value \
+                 can't be computed in a constant"
+            } else {
+                "`Default(const)` needs a `Default(value=\"…\")` on every field, as \
+                 `Default::default()` can't be called in a constant"
+            };
+            return Err(Error::malformed(message, f.ty.span()));
+        }
+    }
 
-            quote!(#(#arms),*)
+    let body = match (variant, &input.body) {
+        (Some(variant), _) => {
+            let vname = &variant.ident;
+            make_variant_data(&quote!(#name::#vname), variant.style, &variant.fields)
         }
-        ast::Body::Struct(style, ref vd) => make_variant_data(&quote!(#name), style, vd),
+        (None, &ast::Body::Struct(style, ref vd)) => make_variant_data(&quote!(#name), style, vd),
+        (None, &ast::Body::Enum(_)) => unreachable!("enumerations have a default variant"),
     };
 
-    let new_fn = if default.new {
+
+    let impl_attrs = impl_attrs(input);
+
+    let new_fn = if required.is_empty() && default.new {
         Some(quote!(
-            #[allow(unused_qualifications)]
+            #impl_attrs
             impl #impl_generics #name #ty_generics #where_clause {
                 /// Creates a default value for this type.
                 #[inline]
+                #[must_use]
                 pub fn new() -> Self {
                     #default_trait_path::default()
                 }
             }
         ))
+    } else if default.new {
+        let params = required.iter().map(|&(i, f)| {
+            let param = required_param(f, i);
+            let ty = f.ty;
+            quote!(#param: #ty)
+        });
+
+        Some(quote!(
+            #impl_attrs
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Creates a value of this type from its required fields, the other fields get
+                /// their default value.
+                #[inline]
+                #[must_use]
+                pub fn new(#(#params),*) -> Self {
+                    #body
+                }
+            }
+        ))
     } else {
         None
     };
 
-    quote!(
+    let const_value = if default.const_value {
+        Some(quote!(
+            #impl_attrs
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// The default value of this type, which can be used in constants and statics.
+                pub const DEFAULT: Self = #body;
+            }
+        ))
+    } else {
+        None
+    };
+
+    let reset_fn = if default.reset {
+        let body = match input.body {
+            ast::Body::Enum(_) => quote!(*self = #default_trait_path::default();),
+            ast::Body::Struct(_, ref vd) => {
+                let mut lets = Vec::new();
+                let mut resets = Vec::new();
+
+                for (i, f) in vd.iter().enumerate() {
+                    let member = match f.ident {
+                        Some(ref name) => quote!(#name),
+                        None => {
+                            let index = syn::Index::from(i);
+                            quote!(#index)
+                        }
+                    };
+
+                    // As in `default()`, the values using the previous fields, and the fields they
+                    // use, are bound first, in order, then assigned.
+                    if let Some(ref name) = f.ident {
+                        let is_used = vd[i + 1..]
+                            .iter()
+                            .filter_map(explicit_default)
+                            .any(|value| mentions(value, name));
+                        let uses = explicit_default(f).is_some_and(|value| {
+                            vd[..i]
+                                .iter()
+                                .filter_map(|other| other.ident.as_ref())
+                                .any(|other| mentions(value.clone(), other))
+                        });
+
+                        if is_used && f.attrs.default_required() {
+                            return Err(Error::unsupported(
+                                format!(
+                                    "`Default(reset)` can't be used with a default value using \
+                                     the `Default(required)` field `{}`",
+                                    name
+                                ),
+                                f.ty.span(),
+                            ));
+                        }
+                        if is_used || uses {
+                            let value = field_default(f);
+                            lets.push(quote!(let #name = #value;));
+                            resets.push(quote!(self.#member = #name;));
+                            continue;
+                        }
+                    }
+
+                    if f.attrs.default_required() {
+                        continue;
+                    }
+
+                    if let Some(capacity) = f.attrs.default_capacity() {
+                        resets.push(quote!(
+                            self.#member.clear();
+                            self.#member.reserve(#capacity);
+                        ));
+                        continue;
+                    }
+
+                    resets.push(match explicit_default(f) {
+                        Some(value) => quote!(self.#member = #value;),
+                        None if is_clearable(f.ty) => quote!(self.#member.clear();),
+                        None => quote!(self.#member = #default_trait_path::default();),
+                    });
+                }
+
+                quote!(#(#lets)* #(#resets)*)
+            }
+        };
+
+        Some(quote!(
+            #impl_attrs
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Resets every field of this value to its default value.
+                #[inline]
+                pub fn reset(&mut self) {
+                    #body
+                }
+            }
+        ))
+    } else {
+        None
+    };
+
+    let value_fns = if default.value_fns {
+        let fns = constructed
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| explicit_default(f).map(|value| (i, f, value)))
+            .map(|(i, f, value)| {
+                let used = constructed
+                    .iter()
+                    .filter_map(|other| other.ident.as_ref())
+                    .find(|other| mentions(value.clone(), other));
+                if let Some(used) = used {
+                    return Err(Error::malformed(
+                        format!(
+                            "`Default(value_fns)` can't make a function of this value, since it \
+                             uses the field `{}`",
+                            used
+                        ),
+                        f.ty.span(),
+                    ));
+                }
+
+                let field = match f.ident {
+                    Some(ref ident) => ident.to_string(),
+                    None => i.to_string(),
+                };
+                let doc = format!("Returns the default value of the field `{}`.", field);
+                let fn_name = syn::Ident::new(
+                    &format!("default_{}", field),
+                    proc_macro2::Span::call_site(),
+                );
+                let ty = f.ty;
+                Ok(quote!(
+                    #[doc = #doc]
+                    #[inline]
+                    #[must_use]
+                    pub fn #fn_name() -> #ty {
+                        #value
+                    }
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Some(quote!(
+            #impl_attrs
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#fns)*
+            }
+        ))
+    } else {
+        None
+    };
+
+    let default_value = if default.const_value {
+        quote!(Self::DEFAULT)
+    } else {
+        body
+    };
+    let default_impl = if required.is_empty() {
+        Some(quote!(
+            #[automatically_derived]
+            #impl_attrs
+            impl #impl_generics #default_trait_path for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    #default_value
+                }
+            }
+        ))
+    } else {
+        None
+    };
+
+    Ok(quote!(
         #new_fn
+        #const_value
+        #reset_fn
+        #value_fns
+        #default_impl
+    ))
+}
+
+/// Return the variant marked as the default of the enumeration `input`.
+///
+/// A variant marked with `Default(fallback)` is only the default if no other variant is, which
+/// is useful when the preferred default variant is behind a `#[cfg]` and thus not always present.
+fn default_variant<'a>(
+    input: &ast::Input,
+    variants: &'a [ast::Variant<'a>],
+) -> Result<&'a ast::Variant<'a>, Error> {
+    let defaults: Vec<_> = variants
+        .iter()
+        .filter(|variant| variant.attrs.default.is_some())
+        .collect();
+    let preferred: Vec<_> = defaults
+        .iter()
+        .filter(|variant| !variant.attrs.default.as_ref().is_some_and(|d| d.fallback))
+        .collect();
+
+    let candidates = if preferred.is_empty() {
+        defaults.iter().collect()
+    } else {
+        preferred
+    };
+
+    match *candidates.as_slice() {
+        [variant] => Ok(variant),
+        [] => Err(Error::malformed(
+            "no default variant, mark one with `#[derivative(Default)]`",
+            input.ident.span(),
+        )),
+        [_, second, ..] => Err(Error::malformed(
+            "only one variant can be the default, use `Default(fallback)` on the variant used when \
+             the others are disabled",
+            second.ident.span(),
+        )),
+    }
+}
 
-        #[allow(unused_qualifications)]
-        impl #impl_generics #default_trait_path for #name #ty_generics #where_clause {
-            fn default() -> Self {
-                #body
+/// Return the value of `f`, the `i`-th field of its variant, in the body of `default` or `new`:
+/// the parameter of `new` if it's required, its default value otherwise.
+fn field_value(f: &ast::Field, i: usize) -> proc_macro2::TokenStream {
+    if f.attrs.default_required() {
+        let param = required_param(f, i);
+        quote!(#param)
+    } else {
+        field_default(f)
+    }
+}
+
+/// Return the name of the parameter of `new` for the required field `f`, the `i`-th field of its
+/// variant: the name of the field, or `argN` for a tuple field.
+fn required_param(f: &ast::Field, i: usize) -> syn::Ident {
+    match f.ident {
+        Some(ref ident) => ident.clone(),
+        None => syn::Ident::new(&format!("arg{}", i), proc_macro2::Span::call_site()),
+    }
+}
+
+/// Return the default value of `f`: its explicit default if it has one, `Default::default()`
+/// otherwise.
+pub fn field_default(f: &ast::Field) -> proc_macro2::TokenStream {
+    explicit_default(f).unwrap_or_else(|| {
+        let default_trait_path = default_trait_path();
+        let ty = f.ty;
+        quote_spanned!(f.span()=> <#ty as #default_trait_path>::default())
+    })
+}
+
+/// Return the default value of `f` given by its `value`, `value_env` or `capacity` attributes, if
+/// any.
+pub fn explicit_default(f: &ast::Field) -> Option<proc_macro2::TokenStream> {
+    if let Some(value) = f.attrs.default_value() {
+        return Some(quote!(#value));
+    }
+
+    if let Some(capacity) = f.attrs.default_capacity() {
+        let ty = f.ty;
+        return Some(quote!(<#ty>::with_capacity(#capacity)));
+    }
+
+    let value_cfg = f.attrs.default_value_cfg();
+    if !value_cfg.is_empty() {
+        // The first matching configuration wins, and the type's default is used if none matches.
+        let values = value_cfg.iter().enumerate().map(|(i, (cfg, value))| {
+            let previous = value_cfg[..i].iter().map(|(cfg, _)| cfg);
+            quote! {
+                #[cfg(all(#cfg, not(any(#(#previous),*))))]
+                let __value = #value;
             }
+        });
+        let cfgs = value_cfg.iter().map(|(cfg, _)| cfg);
+        let default_trait_path = default_trait_path();
+
+        return Some(quote!({
+            #(#values)*
+            #[cfg(not(any(#(#cfgs),*)))]
+            let __value = #default_trait_path::default();
+            __value
+        }));
+    }
+
+    f.attrs.default_value_env().map(|var| {
+        let from_trait_path = from_trait_path();
+        match f.attrs.default_env_fallback() {
+            Some(fallback) => quote!(#from_trait_path::from(option_env!(#var).unwrap_or(#fallback))),
+            None => quote!(#from_trait_path::from(env!(#var))),
+        }
+    })
+}
+
+/// Whether `tokens` use the variable `ident`. The identifier of a method or field (`.len`), of a
+/// function (`len()`) or at the start of a path (`len::new()`) is not a use of the variable.
+fn mentions(tokens: proc_macro2::TokenStream, ident: &syn::Ident) -> bool {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(i, token)| match *token {
+        proc_macro2::TokenTree::Ident(ref name) => {
+            // `..len` is a range to the variable, unlike `.len`.
+            let is_member = i >= 1
+                && is_punct(&tokens[i - 1], '.')
+                && !(i >= 2 && is_punct(&tokens[i - 2], '.'));
+            let is_called = match tokens.get(i + 1) {
+                Some(proc_macro2::TokenTree::Group(group)) => {
+                    group.delimiter() == proc_macro2::Delimiter::Parenthesis
+                }
+                _ => false,
+            };
+            let is_path = tokens.get(i + 1).is_some_and(|token| is_punct(token, ':'))
+                && tokens.get(i + 2).is_some_and(|token| is_punct(token, ':'));
+
+            name == ident && !is_member && !is_called && !is_path
         }
-    )
+        proc_macro2::TokenTree::Group(ref group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Whether `token` is the punctuation `c`.
+fn is_punct(token: &proc_macro2::TokenTree, c: char) -> bool {
+    match *token {
+        proc_macro2::TokenTree::Punct(ref punct) => punct.as_char() == c,
+        _ => false,
+    }
+}
+
+/// Whether a field of type `ty` can be reset with `clear()`, keeping its allocation. The
+/// standard collections are recognized by name, hence this is only a heuristic.
+fn is_clearable(ty: &syn::Type) -> bool {
+    const CLEARABLE: &[&str] = &[
+        "BTreeMap",
+        "BTreeSet",
+        "BinaryHeap",
+        "HashMap",
+        "HashSet",
+        "LinkedList",
+        "String",
+        "Vec",
+        "VecDeque",
+    ];
+
+    match *ty {
+        syn::Type::Path(syn::TypePath { qself: None, ref path }) => path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| CLEARABLE.iter().any(|c| segment.ident == c)),
+        _ => false,
+    }
+}
+
+/// Return the path of the `From` trait, that is `::std::convert::From`.
+fn from_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::From)
+    } else {
+        parse_quote!(::std::convert::From)
+    }
 }
 
 /// Return the path of the `Default` trait, that is `::std::default::Default`.
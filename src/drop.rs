@@ -0,0 +1,86 @@
+use proc_macro2;
+
+use ast;
+use bound;
+use matcher;
+use syn;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Drop`, which calls the `Drop(with)` functions of the type and its fields.
+pub struct DropGenerator;
+
+impl TraitGenerator for DropGenerator {
+    fn name(&self) -> &'static str {
+        "Drop"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.drop.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let has_field_hooks = input
+        .body
+        .all_fields()
+        .iter()
+        .any(|f| f.attrs.drop_with().is_some());
+
+    let hook = input.attrs.drop_with().map(|with| quote!(#with(self);));
+    if hook.is_none() && !has_field_hooks {
+        return Err(Error::malformed(
+            "`Drop` needs `Drop(with=\"…\")` on the type or on a field",
+            input.ident.span(),
+        ));
+    }
+
+    // The fields are dropped after `drop` returns, so their hooks run before they are dropped.
+    let field_hooks = if has_field_hooks {
+        let arms = matcher::Matcher::new(matcher::BindingStyle::RefMut).build_arms(
+            input,
+            |_, _, _, _, bis| {
+                let hooks = bis.iter().filter_map(|bi| {
+                    let arg = &bi.ident;
+                    bi.field.attrs.drop_with().map(|with| quote!(#with(#arg);))
+                });
+                quote!(#(#hooks)*)
+            },
+        );
+        Some(quote!(match *self { #arms }))
+    } else {
+        None
+    };
+
+    let name = &input.ident;
+    let drop_trait_path = drop_trait_path();
+    // A `Drop` implementation must have the same bounds as the type, so there is no `bound`.
+    let generics = bound::without_defaults(input.generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #drop_trait_path for #name #ty_generics #where_clause {
+            fn drop(&mut self) {
+                #hook
+                #field_hooks
+            }
+        }
+    })
+}
+
+/// Return the path of the `Drop` trait, that is `::std::ops::Drop`.
+fn drop_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::Drop)
+    } else {
+        parse_quote!(::std::ops::Drop)
+    }
+}
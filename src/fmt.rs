@@ -0,0 +1,110 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for one of the radix formatting traits of `fmt`, eg. `LowerHex`, on structures
+/// with exactly one field.
+pub struct RadixGenerator {
+    /// The name of the trait in `fmt`.
+    name: &'static str,
+    /// The attributes of the trait on the input, if requested.
+    attrs: fn(&attr::Input) -> Option<&attr::InputRadix>,
+}
+
+pub const BINARY: RadixGenerator = RadixGenerator {
+    name: "Binary",
+    attrs: |input| input.binary.as_ref(),
+};
+
+pub const LOWER_HEX: RadixGenerator = RadixGenerator {
+    name: "LowerHex",
+    attrs: |input| input.lower_hex.as_ref(),
+};
+
+pub const OCTAL: RadixGenerator = RadixGenerator {
+    name: "Octal",
+    attrs: |input| input.octal.as_ref(),
+};
+
+pub const UPPER_HEX: RadixGenerator = RadixGenerator {
+    name: "UpperHex",
+    attrs: |input| input.upper_hex.as_ref(),
+};
+
+impl TraitGenerator for RadixGenerator {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        (self.attrs)(&input.attrs).is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_radix(input, self)
+    }
+}
+
+/// Derive the radix formatting trait of `generator` for `input` by formatting its field, with
+/// the same flags.
+fn derive_radix(
+    input: &ast::Input,
+    generator: &RadixGenerator,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let field = match input.body {
+        ast::Body::Struct(_, ref fields) if fields.len() == 1 => &fields[0],
+        _ => {
+            return Err(Error::unsupported(
+                format!(
+                    "`{}` can only be derived on structures with exactly one field",
+                    generator.name
+                ),
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let fmt_path = fmt_path();
+    let trait_ident = syn::Ident::new(generator.name, proc_macro2::Span::call_site());
+    let trait_path: syn::Path = parse_quote!(#fmt_path::#trait_ident);
+    let generics = utils::build_impl_generics(
+        input,
+        &trait_path,
+        |_| true,
+        |_| None,
+        |input| (generator.attrs)(input).and_then(attr::InputRadix::bound),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let member = match field.ident {
+        Some(ref ident) => quote!(#ident),
+        None => quote!(0),
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                #trait_path::fmt(&self.#member, __f)
+            }
+        }
+    })
+}
+
+/// Return the path of the `fmt` module, that is `::std::fmt`.
+fn fmt_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::fmt)
+    } else {
+        parse_quote!(::std::fmt)
+    }
+}
@@ -0,0 +1,187 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use debug;
+use matcher;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `defmt::Format`.
+///
+/// The fields use the `Debug` attributes too, so that the `defmt` logs get the same redaction as
+/// the `Debug` output: fields ignored by `Debug` are not shown, masked fields are shown as their
+/// mask, fields with a `format_with` function are shown with `defmt::Debug2Format`, and
+/// transparent types are shown as their field.
+pub struct FormatGenerator;
+
+impl TraitGenerator for FormatGenerator {
+    fn name(&self) -> &'static str {
+        "Format"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("Format").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        Ok(derive(input))
+    }
+}
+
+fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
+    let format_trait_path: syn::Path = parse_quote!(::defmt::Format);
+    let name = &input.ident;
+
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
+        input,
+        |_, arm_name, style, attrs, bis| {
+            let shown: Vec<_> = bis
+                .iter()
+                .filter(|bi| !is_ignored(&bi.field.attrs))
+                .collect();
+
+            let delegate = if debug::is_transparent(input, attrs, bis.len()) && bis.len() == 1 {
+                Some(&bis[0])
+            } else {
+                bis.iter().find(|bi| bi.field.attrs.debug_delegate())
+            };
+            if let Some(bi) = delegate {
+                let arg = &bi.ident;
+                return match bi.field.attrs.debug_mask() {
+                    Some(mask) => quote!(#format_trait_path::format(#mask, __f)),
+                    None => quote!(#format_trait_path::format(#arg, __f)),
+                };
+            }
+
+            let indexed = bis.iter().enumerate().filter(|&(_, bi)| !is_ignored(&bi.field.attrs));
+            let prepares = indexed.filter_map(|(i, bi)| {
+                let arg = &bi.ident;
+                match format_with(&bi.field.attrs) {
+                    Some(format_fn) => Some(format_with_wrapper(input, bi.field, arg, format_fn)),
+                    None => bi.field.attrs.debug_format_with().map(|format_fn| {
+                        debug::format_with(input, bi.field, i, arg, format_fn)
+                    }),
+                }
+            });
+            let args = shown.iter().map(|bi| {
+                let arg = &bi.ident;
+                if format_with(&bi.field.attrs).is_some() {
+                    quote!(#arg)
+                } else if let Some(mask) = bi.field.attrs.debug_mask() {
+                    quote!(#mask)
+                } else if bi.field.attrs.debug_format_with().is_some() {
+                    quote!(::defmt::Debug2Format(&#arg))
+                } else {
+                    quote!(#arg)
+                }
+            });
+
+            let mut format = debug::variant_name(input, arm_name);
+            if !shown.is_empty() {
+                let fields: Vec<_> = shown
+                    .iter()
+                    .map(|bi| match bi.field.ident {
+                        Some(_) => format!("{}: {{}}", debug::field_name(input, bi.field)),
+                        None => "{}".to_string(),
+                    })
+                    .collect();
+                match style {
+                    ast::Style::Struct => {
+                        format.push_str(&format!(" {{{{ {} }}}}", fields.join(", ")))
+                    }
+                    ast::Style::Tuple | ast::Style::Unit => {
+                        format.push_str(&format!("({})", fields.join(", ")))
+                    }
+                }
+            }
+
+            quote! {
+                #(#prepares)*
+                ::defmt::write!(__f, #format, #(#args),*)
+            }
+        },
+    );
+
+    let generics = utils::build_impl_generics(
+        input,
+        &format_trait_path,
+        needs_format_bound,
+        |field| {
+            field
+                .extension("Format")
+                .and_then(attr::FieldExtension::bound)
+        },
+        |input| {
+            input
+                .extension("Format")
+                .and_then(attr::InputExtension::bound)
+        },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #format_trait_path for #name #ty_generics #where_clause {
+            fn format(&self, __f: ::defmt::Formatter) {
+                match *self {
+                    #body
+                }
+            }
+        }
+    }
+}
+
+/// Rebind `arg`, a reference to `field`, to a wrapper implementing `defmt::Format` with the
+/// `Format(format_with)` function of the field.
+fn format_with_wrapper(
+    input: &ast::Input,
+    field: &ast::Field,
+    arg: &syn::Ident,
+    format_fn: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let method = quote! {
+        fn format(&self, __f: ::defmt::Formatter) {
+            #format_fn(&self.0, __f)
+        }
+    };
+    let bounds = field
+        .attrs
+        .extension("Format")
+        .and_then(attr::FieldExtension::bound)
+        .unwrap_or(&[]);
+
+    debug::wrap_field(
+        field,
+        arg,
+        &parse_quote!(::defmt::Format),
+        &method,
+        bounds,
+        input.generics.clone(),
+    )
+}
+
+fn is_ignored(attrs: &attr::Field) -> bool {
+    attrs.ignore_debug()
+        || attrs
+            .extension("Format")
+            .is_some_and(attr::FieldExtension::ignore)
+}
+
+/// The function formatting the field, from `Format(format_with="…")`.
+fn format_with(attrs: &attr::Field) -> Option<&syn::Path> {
+    attrs
+        .extension("Format")
+        .and_then(attr::FieldExtension::with)
+}
+
+fn needs_format_bound(attrs: &attr::Field) -> bool {
+    !is_ignored(attrs)
+        && format_with(attrs).is_none()
+        && attrs.debug_format_with().is_none()
+        && attrs.debug_mask().is_none()
+}
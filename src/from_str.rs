@@ -0,0 +1,200 @@
+use proc_macro2;
+
+use ast;
+use syn;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `FromStr`, on enumerations whose variants have no fields.
+pub struct FromStrGenerator;
+
+impl TraitGenerator for FromStrGenerator {
+    fn name(&self) -> &'static str {
+        "FromStr"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.from_str.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let variants = match input.body {
+        ast::Body::Enum(ref variants) => variants,
+        ast::Body::Struct(..) => {
+            return Err(Error::unsupported(
+                "`FromStr` can only be derived on enumerations",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let from_str_trait_path = from_str_trait_path();
+    let from_trait_path = from_trait_path();
+    let result_path = result_path();
+
+    let mut parsed: Vec<String> = Vec::new();
+    let mut arms = Vec::new();
+    for variant in variants {
+        match variant.style {
+            ast::Style::Unit => (),
+            ast::Style::Struct | ast::Style::Tuple => {
+                return Err(Error::unsupported(
+                    "`FromStr` can only be derived on enumerations whose variants have no fields",
+                    variant.ident.span(),
+                ));
+            }
+        }
+
+        let variant_ident = &variant.ident;
+        let strings: Vec<String> = Some(variant_name(input, variant))
+            .into_iter()
+            .chain(variant.attrs.from_str_aliases().iter().cloned())
+            .collect();
+        for string in &strings {
+            if parsed.contains(string) {
+                return Err(Error::malformed(
+                    format!("`{}` is already parsed as another variant", string),
+                    variant_ident.span(),
+                ));
+            }
+            parsed.push(string.clone());
+        }
+
+        arms.push(quote!(#(#strings)|* => #result_path::Ok(#name::#variant_ident),));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (error_ty, error, error_def) = match input.attrs.from_str_error() {
+        Some(error_ty) => (quote!(#error_ty), quote!(#from_trait_path::from(__s)), None),
+        None => {
+            let error_ty = syn::Ident::new(
+                &format!("Parse{}Error", name),
+                proc_macro2::Span::call_site(),
+            );
+            (
+                quote!(#error_ty),
+                quote!(#error_ty),
+                Some(error_def(input, &error_ty, &parsed)),
+            )
+        }
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #error_def
+
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #from_str_trait_path for #name #ty_generics #where_clause {
+            type Err = #error_ty;
+
+            fn from_str(__s: &str) -> #result_path<Self, Self::Err> {
+                match __s {
+                    #(#arms)*
+                    _ => #result_path::Err(#error),
+                }
+            }
+        }
+    })
+}
+
+/// Generate the `Parse<Name>Error` type, returned when no variant matches the string.
+fn error_def(
+    input: &ast::Input,
+    error_ty: &syn::Ident,
+    parsed: &[String],
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let fmt_path = fmt_path();
+    let doc = format!("The error returned when parsing a [`{}`] fails.", name);
+    let message = if parsed.is_empty() {
+        format!("`{}` has no variant to parse", name)
+    } else {
+        let expected: Vec<_> = parsed.iter().map(|s| format!("`{}`", s)).collect();
+        format!(
+            "invalid `{}`, expected one of {}",
+            name,
+            expected.join(", ")
+        )
+    };
+
+    let error_impl = if cfg!(feature = "use_core") {
+        None
+    } else {
+        Some(quote! {
+            #[automatically_derived]
+            impl ::std::error::Error for #error_ty {}
+        })
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct #error_ty;
+
+        #[automatically_derived]
+        #impl_attrs
+        impl #fmt_path::Display for #error_ty {
+            fn fmt(&self, __f: &mut #fmt_path::Formatter) -> #fmt_path::Result {
+                __f.write_str(#message)
+            }
+        }
+
+        #error_impl
+    }
+}
+
+/// The string a variant is parsed from, its name unless it is renamed, following the
+/// `rename_all` convention of the type if any.
+fn variant_name(input: &ast::Input, variant: &ast::Variant) -> String {
+    match (variant.attrs.from_str_rename(), input.attrs.rename_all()) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(rule)) => rule.apply_to_variant(&variant.ident.to_string()),
+        (None, None) => variant.ident.to_string(),
+    }
+}
+
+/// Return the path of the `FromStr` trait, that is `::std::str::FromStr`.
+fn from_str_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::str::FromStr)
+    } else {
+        parse_quote!(::std::str::FromStr)
+    }
+}
+
+/// Return the path of the `From` trait, that is `::std::convert::From`.
+fn from_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::convert::From)
+    } else {
+        parse_quote!(::std::convert::From)
+    }
+}
+
+/// Return the path of `Result`, that is `::std::result::Result`.
+fn result_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::result::Result)
+    } else {
+        parse_quote!(::std::result::Result)
+    }
+}
+
+/// Return the path of the `fmt` module, that is `::std::fmt`.
+fn fmt_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::fmt)
+    } else {
+        parse_quote!(::std::fmt)
+    }
+}
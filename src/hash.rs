@@ -1,45 +1,98 @@
+use std::collections::HashMap;
+
 use proc_macro2;
 
 use ast;
 use attr;
+use bindgen;
 use matcher;
 use syn;
 use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Hash`.
+pub struct HashGenerator;
+
+impl TraitGenerator for HashGenerator {
+    fn name(&self) -> &'static str {
+        "Hash"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.hash.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
 
-pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
     let hasher_trait_path = hasher_trait_path();
     let hash_trait_path = hash_trait_path();
 
+    // The variants are looked up by name so that the expansion stays linear in their number.
+    let indexes: HashMap<&syn::Ident, usize> = match input.body {
+        ast::Body::Enum(ref variants) => {
+            variants.iter().enumerate().map(|(i, v)| (&v.ident, i)).collect()
+        }
+        ast::Body::Struct(..) => HashMap::new(),
+    };
+
+    // The calls hashing a field have its span, but `__state` keeps the hygiene of `call_site`.
+    let state = syn::Ident::new("__state", proc_macro2::Span::call_site());
+
     let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
         input,
-        |arm_path, _, _, _, bis| {
+        |arm_path, arm_name, style, _, bis| {
             let field_prints = bis.iter().filter_map(|bi| {
                 if bi.field.attrs.ignore_hash() {
                     return None;
                 }
 
                 let arg = &bi.ident;
+                let span = bi.field.span();
+                let arg = if bi.field.attrs.hash_deref() {
+                    quote_spanned!(span=> &**#arg)
+                } else {
+                    quote!(#arg)
+                };
 
                 if let Some(hash_with) = bi.field.attrs.hash_with() {
+                    Some(quote_spanned! {span=>
+                        #hash_with(#arg, #state);
+                    })
+                } else if bi.field.attrs.bindgen() == Some(attr::Bindgen::Opaque) {
+                    let bytes = bindgen::bytes(&bi.ident);
                     Some(quote! {
-                        #hash_with(#arg, __state);
+                        #hash_trait_path::hash(#bytes, __state);
                     })
                 } else {
-                    Some(quote! {
-                        #hash_trait_path::hash(#arg, __state);
+                    Some(quote_spanned! {span=>
+                        #hash_trait_path::hash(#arg, #state);
                     })
                 }
             });
 
-            let variant = if let ast::Body::Enum(_) = input.body {
-                Some(quote!(
+            let type_tag = type_tag(input);
+
+            let variant = match (&input.body, style) {
+                (ast::Body::Enum(_), ast::Style::Unit) => Some(quote!(
                     #hash_trait_path::hash(&(#arm_path as u64), __state);
-                ))
-            } else {
-                None
+                )),
+                // Only unit variants can be cast to their discriminant, the others hash their
+                // index.
+                (ast::Body::Enum(_), _) => {
+                    let index = indexes[arm_name] as u64;
+                    Some(quote!(
+                        #hash_trait_path::hash(&#index, __state);
+                    ))
+                }
+                (ast::Body::Struct(..), _) => None,
             };
 
             quote! {
+                #type_tag
                 #variant
                 #(#field_prints)*
             }
@@ -54,11 +107,30 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
         |field| field.hash_bound(),
         |input| input.hash_bound(),
     );
+    let generics = utils::with_deref_bounds(
+        input,
+        generics,
+        &hash_trait_path,
+        &deref_trait_path(),
+        needs_hash_deref_bound,
+        input.attrs.hash_bound(),
+    );
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let content_hash = if input.attrs.hash_content_hash() {
+        Some(content_hash(input, &generics))
+    } else {
+        None
+    };
+
     let hasher_ty_parameter = utils::hygienic_type_parameter(input, "__H");
-    quote! {
-        #[allow(unused_qualifications)]
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #content_hash
+
+        #[automatically_derived]
+        #impl_attrs
         impl #impl_generics #hash_trait_path for #name #ty_generics #where_clause {
             fn hash<#hasher_ty_parameter>(&self, __state: &mut #hasher_ty_parameter)
                 where #hasher_ty_parameter: #hasher_trait_path
@@ -68,11 +140,114 @@ pub fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
                 }
             }
         }
+    })
+}
+
+/// Generate the inherent `content_hash` and `content_hash_with_seed` methods, which hash `self`
+/// with 64-bit FNV-1a, writing the integers in little-endian order and `usize` as `u64` so that
+/// the result doesn't depend on the platform.
+fn content_hash(input: &ast::Input, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let hash_trait_path = hash_trait_path();
+    let hasher_trait_path = hasher_trait_path();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let seed = match input.attrs.hash_content_hash_seed() {
+        Some(seed) => quote!(#seed),
+        None => quote!(0),
+    };
+
+    let write_le = [
+        ("write_u16", "u16"),
+        ("write_u32", "u32"),
+        ("write_u64", "u64"),
+        ("write_u128", "u128"),
+    ]
+    .iter()
+    .map(|&(method, ty)| {
+        let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+        let ty = syn::Ident::new(ty, proc_macro2::Span::call_site());
+        quote! {
+            #[inline]
+            fn #method(&mut self, __i: #ty) {
+                #hasher_trait_path::write(self, &__i.to_le_bytes())
+            }
+        }
+    })
+    .collect::<Vec<_>>();
+
+    let impl_attrs = impl_attrs(input);
+
+    quote! {
+        #impl_attrs
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the hash of this value computed with `content_hash_with_seed` and the seed
+            /// of the type, 0 by default.
+            pub fn content_hash(&self) -> u64 {
+                Self::content_hash_with_seed(self, #seed)
+            }
+
+            /// Returns the hash of this value computed with 64-bit FNV-1a, after `seed`. The
+            /// result is the same on every platform and with every compiler, as long as the
+            /// `Hash` implementations of the fields don't change.
+            pub fn content_hash_with_seed(&self, seed: u64) -> u64 {
+                struct __DerivativeFnv1a(u64);
+
+                impl #hasher_trait_path for __DerivativeFnv1a {
+                    #[inline]
+                    fn write(&mut self, __bytes: &[u8]) {
+                        for __byte in __bytes {
+                            self.0 = (self.0 ^ u64::from(*__byte)).wrapping_mul(0x100_0000_01b3);
+                        }
+                    }
+
+                    #(#write_le)*
+
+                    #[inline]
+                    fn write_usize(&mut self, __i: usize) {
+                        #hasher_trait_path::write_u64(self, __i as u64)
+                    }
+
+                    #[inline]
+                    fn finish(&self) -> u64 {
+                        self.0
+                    }
+                }
+
+                let mut __hasher = __DerivativeFnv1a(0xcbf2_9ce4_8422_2325);
+                #hasher_trait_path::write_u64(&mut __hasher, seed);
+                #hash_trait_path::hash(self, &mut __hasher);
+                #hasher_trait_path::finish(&__hasher)
+            }
+        }
     }
 }
 
+/// Hash the tag of the type if it is marked with `type_tag`: the given value, or the path of the
+/// type.
+fn type_tag(input: &ast::Input) -> Option<proc_macro2::TokenStream> {
+    if !input.attrs.hash_type_tag() {
+        return None;
+    }
+
+    let hasher_trait_path = hasher_trait_path();
+    let hash_trait_path = hash_trait_path();
+
+    Some(match input.attrs.hash_type_tag_value() {
+        Some(value) => quote!(#hasher_trait_path::write_u64(__state, #value);),
+        None => {
+            let name = input.ident.to_string();
+            quote!(#hash_trait_path::hash(concat!(module_path!(), "::", #name), __state);)
+        }
+    })
+}
+
 fn needs_hash_bound(attrs: &attr::Field) -> bool {
-    !attrs.ignore_hash() && attrs.hash_bound().is_none()
+    !attrs.ignore_hash() && attrs.hash_bound().is_none() && !attrs.hash_deref()
+}
+
+fn needs_hash_deref_bound(attrs: &attr::Field) -> bool {
+    !attrs.ignore_hash() && attrs.hash_bound().is_none() && attrs.hash_deref()
 }
 
 /// Return the path of the `Hash` trait, that is `::std::hash::Hash`.
@@ -84,6 +259,15 @@ fn hash_trait_path() -> syn::Path {
     }
 }
 
+/// Return the path of the `Deref` trait, that is `::std::ops::Deref`.
+fn deref_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::Deref)
+    } else {
+        parse_quote!(::std::ops::Deref)
+    }
+}
+
 /// Return the path of the `Hasher` trait, that is `::std::hash::Hasher`.
 fn hasher_trait_path() -> syn::Path {
     if cfg!(feature = "use_core") {
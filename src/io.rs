@@ -0,0 +1,173 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use bound;
+use ops;
+use syn;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `io::Read`, on structures with exactly one field or a field marked
+/// `Read="target"`.
+pub struct ReadGenerator;
+
+impl TraitGenerator for ReadGenerator {
+    fn name(&self) -> &'static str {
+        "Read"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.read.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_read(input)
+    }
+}
+
+/// The generator for `io::Write`, on structures with exactly one field or a field marked
+/// `Write="target"`.
+pub struct WriteGenerator;
+
+impl TraitGenerator for WriteGenerator {
+    fn name(&self) -> &'static str {
+        "Write"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.write.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_write(input)
+    }
+}
+
+/// Derive `io::Read` for `input`, forwarding all the methods with a default implementation to its
+/// target field too, so that it can override them.
+fn derive_read(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let (member, field) = target_field(input, "Read", attr::Field::read_target)?;
+    let read_trait_path: syn::Path = parse_quote!(::std::io::Read);
+    let generics = io_generics(input, field, &read_trait_path, input.attrs.read_bound());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #read_trait_path for #name #ty_generics #where_clause {
+            #[inline]
+            fn read(&mut self, __buf: &mut [u8]) -> ::std::io::Result<usize> {
+                #read_trait_path::read(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_vectored(
+                &mut self,
+                __bufs: &mut [::std::io::IoSliceMut<'_>],
+            ) -> ::std::io::Result<usize> {
+                #read_trait_path::read_vectored(&mut self.#member, __bufs)
+            }
+
+            #[inline]
+            fn read_to_end(&mut self, __buf: &mut ::std::vec::Vec<u8>) -> ::std::io::Result<usize> {
+                #read_trait_path::read_to_end(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_to_string(
+                &mut self,
+                __buf: &mut ::std::string::String,
+            ) -> ::std::io::Result<usize> {
+                #read_trait_path::read_to_string(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_exact(&mut self, __buf: &mut [u8]) -> ::std::io::Result<()> {
+                #read_trait_path::read_exact(&mut self.#member, __buf)
+            }
+        }
+    })
+}
+
+/// Derive `io::Write` for `input`, forwarding all the methods with a default implementation to
+/// its target field too, so that it can override them.
+fn derive_write(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let (member, field) = target_field(input, "Write", attr::Field::write_target)?;
+    let write_trait_path: syn::Path = parse_quote!(::std::io::Write);
+    let generics = io_generics(input, field, &write_trait_path, input.attrs.write_bound());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #write_trait_path for #name #ty_generics #where_clause {
+            #[inline]
+            fn write(&mut self, __buf: &[u8]) -> ::std::io::Result<usize> {
+                #write_trait_path::write(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn write_vectored(
+                &mut self,
+                __bufs: &[::std::io::IoSlice<'_>],
+            ) -> ::std::io::Result<usize> {
+                #write_trait_path::write_vectored(&mut self.#member, __bufs)
+            }
+
+            #[inline]
+            fn flush(&mut self) -> ::std::io::Result<()> {
+                #write_trait_path::flush(&mut self.#member)
+            }
+
+            #[inline]
+            fn write_all(&mut self, __buf: &[u8]) -> ::std::io::Result<()> {
+                #write_trait_path::write_all(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn write_fmt(&mut self, __args: ::std::fmt::Arguments<'_>) -> ::std::io::Result<()> {
+                #write_trait_path::write_fmt(&mut self.#member, __args)
+            }
+        }
+    })
+}
+
+/// Return the target field of `trait_name`, which is not available without `std`.
+fn target_field<'a, 'b>(
+    input: &'b ast::Input<'a>,
+    trait_name: &str,
+    is_marked: fn(&attr::Field) -> bool,
+) -> Result<(proc_macro2::TokenStream, &'b ast::Field<'a>), Error> {
+    if cfg!(feature = "use_core") {
+        return Err(Error::unsupported(
+            format!(
+                "`{}` is not available with the `use_core` feature",
+                trait_name
+            ),
+            input.ident.span(),
+        ));
+    }
+
+    let marker = format!("{}=\"target\"", trait_name);
+    ops::target_field(input, trait_name, &marker, is_marked)
+}
+
+/// Return the generics of the implementation of `trait_path` for `input`, bounded by
+/// `Field: Trait` for its target field whatever the bounds given to `bound` are.
+fn io_generics(
+    input: &ast::Input,
+    field: &ast::Field,
+    trait_path: &syn::Path,
+    input_bound: Option<&[syn::WherePredicate]>,
+) -> syn::Generics {
+    let ty = field.ty;
+    let generics = bound::without_defaults(input.generics);
+    let generics = bound::with_where_predicates(&generics, &[parse_quote!(#ty: #trait_path)]);
+    bound::with_where_predicates(&generics, input_bound.unwrap_or(&[]))
+}
@@ -0,0 +1,181 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use bound;
+use ops;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `FromIterator`, on structures with exactly one field.
+pub struct FromIteratorGenerator;
+
+impl TraitGenerator for FromIteratorGenerator {
+    fn name(&self) -> &'static str {
+        "FromIterator"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.from_iterator.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_from_iterator(input)
+    }
+}
+
+/// The generator for `Extend`, on structures with exactly one field or a field marked
+/// `Extend="target"`.
+pub struct ExtendGenerator;
+
+impl TraitGenerator for ExtendGenerator {
+    fn name(&self) -> &'static str {
+        "Extend"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extend.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_extend(input)
+    }
+}
+
+/// Derive `FromIterator<Item>` for `input` by collecting into its field. `Item` is the `item`
+/// type if given, or a type parameter of the implementation.
+fn derive_from_iterator(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let field = match input.body {
+        ast::Body::Struct(_, ref fields) if fields.len() == 1 => &fields[0],
+        _ => {
+            return Err(Error::unsupported(
+                "`FromIterator` can only be derived on structures with exactly one field",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let ty = field.ty;
+    let from_iterator_trait_path = from_iterator_trait_path();
+    let into_iterator_trait_path = into_iterator_trait_path();
+
+    let mut generics = bound::without_defaults(input.generics);
+    let item = match input.attrs.from_iterator_item() {
+        Some(item) => quote!(#item),
+        None => {
+            let item = utils::hygienic_type_parameter(input, "__Item");
+            generics.params.push(parse_quote!(#item));
+            quote!(#item)
+        }
+    };
+    // The field must be collected from `Item` whatever the bounds given to `bound` are.
+    let generics = bound::with_where_predicates(
+        &generics,
+        &[parse_quote!(#ty: #from_iterator_trait_path<#item>)],
+    );
+    let generics =
+        bound::with_where_predicates(&generics, input.attrs.from_iterator_bound().unwrap_or(&[]));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let iter_ty = utils::hygienic_type_parameter(input, "__Iter");
+    let collected = quote!(#from_iterator_trait_path::from_iter(__iter));
+    let construct = match field.ident {
+        Some(ref ident) => quote!(#name { #ident: #collected }),
+        None => quote!(#name(#collected)),
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #from_iterator_trait_path<#item> for #name #ty_generics #where_clause {
+            #[inline]
+            fn from_iter<#iter_ty>(__iter: #iter_ty) -> Self
+                where #iter_ty: #into_iterator_trait_path<Item = #item>
+            {
+                #construct
+            }
+        }
+    })
+}
+
+/// Derive `Extend<Item>` for `input` by extending its target field. `Item` is the `item` type if
+/// given, or a type parameter of the implementation.
+fn derive_extend(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let (member, field) = ops::target_field(
+        input,
+        "Extend",
+        "Extend=\"target\"",
+        attr::Field::extend_target,
+    )?;
+
+    let name = &input.ident;
+    let ty = field.ty;
+    let extend_trait_path = extend_trait_path();
+    let into_iterator_trait_path = into_iterator_trait_path();
+
+    let mut generics = bound::without_defaults(input.generics);
+    let item = match input.attrs.extend_item() {
+        Some(item) => quote!(#item),
+        None => {
+            let item = utils::hygienic_type_parameter(input, "__Item");
+            generics.params.push(parse_quote!(#item));
+            quote!(#item)
+        }
+    };
+    // The field must be extendable with `Item` whatever the bounds given to `bound` are.
+    let generics =
+        bound::with_where_predicates(&generics, &[parse_quote!(#ty: #extend_trait_path<#item>)]);
+    let generics =
+        bound::with_where_predicates(&generics, input.attrs.extend_bound().unwrap_or(&[]));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let iter_ty = utils::hygienic_type_parameter(input, "__Iter");
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #extend_trait_path<#item> for #name #ty_generics #where_clause {
+            #[inline]
+            fn extend<#iter_ty>(&mut self, __iter: #iter_ty)
+                where #iter_ty: #into_iterator_trait_path<Item = #item>
+            {
+                #extend_trait_path::extend(&mut self.#member, __iter)
+            }
+        }
+    })
+}
+
+/// Return the path of the `Extend` trait, that is `::std::iter::Extend`.
+fn extend_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::iter::Extend)
+    } else {
+        parse_quote!(::std::iter::Extend)
+    }
+}
+
+/// Return the path of the `FromIterator` trait, that is `::std::iter::FromIterator`.
+fn from_iterator_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::iter::FromIterator)
+    } else {
+        parse_quote!(::std::iter::FromIterator)
+    }
+}
+
+/// Return the path of the `IntoIterator` trait, that is `::std::iter::IntoIterator`.
+fn into_iterator_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::iter::IntoIterator)
+    } else {
+        parse_quote!(::std::iter::IntoIterator)
+    }
+}
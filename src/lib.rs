@@ -1,66 +1,227 @@
-// We support Rust 1.15 and struct initialization shorthand syntax is a Rust 1.17 feature
-//
-// NOTE: Rust 1.17 and older cannot parse `::` in attributes so we can't use
-// `allow(clippy::redundant_field_names)`.
-// Instead, we resort to using `allow(redundant_field_names)`, but this triggers
-// `renamed_and_removed_lints` which we also want to allow.
-#![cfg_attr(feature = "cargo-clippy", allow(
-    renamed_and_removed_lints,
-    redundant_field_names,
-))]
-
+extern crate derivative_core;
 extern crate proc_macro;
+#[cfg(not(feature = "syn-1"))]
 extern crate proc_macro2;
+#[cfg(feature = "syn-1")]
+extern crate proc_macro2_1 as proc_macro2;
+#[cfg(not(feature = "syn-1"))]
 #[macro_use]
 extern crate syn;
+#[cfg(feature = "syn-1")]
+#[macro_use]
+extern crate syn_1 as syn;
 
+#[cfg(not(feature = "syn-1"))]
 #[macro_use]
 extern crate quote;
+#[cfg(feature = "syn-1")]
+#[macro_use]
+extern crate quote_1 as quote;
 
-mod ast;
-mod attr;
-mod bound;
+mod accessors;
+mod approx;
+mod arbitrary;
+mod bindgen;
+mod builder;
 mod clone;
 mod cmp;
+mod convert;
 mod debug;
 mod default;
+mod drop;
+mod fmt;
+mod format;
+mod from_str;
 mod hash;
-mod matcher;
-mod utils;
+mod io;
+mod iter;
+mod ops;
+mod remote;
+mod valuable;
+mod variant_names;
+mod zeroize;
+
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use derivative_core::{generator, lint, report};
+use derivative_core::{ast, attr, bound, case, matcher, utils, Error, TraitGenerator};
 
 use proc_macro::TokenStream;
 
-fn derive_impls(input: &ast::Input) -> Result<proc_macro2::TokenStream, String> {
-    let mut tokens = proc_macro2::TokenStream::new();
+/// The generators of all the supported traits, in the order their implementations are emitted.
+const GENERATORS: &[&dyn TraitGenerator] = &[
+    &clone::CloneGenerator,
+    &clone::CopyGenerator,
+    &debug::DebugGenerator,
+    &default::DefaultGenerator,
+    &builder::BuilderGenerator,
+    &cmp::EqGenerator,
+    &hash::HashGenerator,
+    &cmp::PartialEqGenerator,
+    &cmp::PartialOrdGenerator,
+    &cmp::OrdGenerator,
+    &convert::AsRefGenerator,
+    &convert::AsMutGenerator,
+    &convert::BorrowGenerator,
+    &convert::BorrowMutGenerator,
+    &convert::FromGenerator,
+    &iter::FromIteratorGenerator,
+    &iter::ExtendGenerator,
+    &convert::TryFromGenerator,
+    &ops::DerefGenerator,
+    &ops::DerefMutGenerator,
+    &ops::IndexGenerator,
+    &ops::IndexMutGenerator,
+    &ops::ADD,
+    &ops::SUB,
+    &ops::MUL,
+    &ops::DIV,
+    &ops::ADD_ASSIGN,
+    &ops::SUB_ASSIGN,
+    &ops::MUL_ASSIGN,
+    &ops::DIV_ASSIGN,
+    &ops::NEG,
+    &ops::NOT,
+    &ops::BIT_AND,
+    &ops::BIT_OR,
+    &ops::BIT_XOR,
+    &ops::SHL,
+    &ops::SHR,
+    &drop::DropGenerator,
+    &io::ReadGenerator,
+    &io::WriteGenerator,
+    &fmt::BINARY,
+    &fmt::LOWER_HEX,
+    &fmt::OCTAL,
+    &fmt::UPPER_HEX,
+    &from_str::FromStrGenerator,
+    &accessors::AccessorsGenerator,
+    &variant_names::VariantNamesGenerator,
+    &valuable::ValuableGenerator,
+    &arbitrary::ArbitraryGenerator,
+    &format::FormatGenerator,
+    &zeroize::ZeroizeGenerator,
+    &approx::AbsDiffEqGenerator,
+    &approx::RelativeEqGenerator,
+];
 
-    if input.attrs.clone.is_some() {
-        tokens.extend(clone::derive_clone(input));
+/// The traits accepted in addition to those of `derivative_core`, depending on the features.
+fn extensions() -> Vec<&'static str> {
+    let mut extensions = ops::OPERATORS.to_vec();
+    if cfg!(feature = "valuable") {
+        extensions.push("Valuable");
     }
-    if input.attrs.copy.is_some() {
-        tokens.extend(clone::derive_copy(input));
+    if cfg!(feature = "proptest") {
+        extensions.push("Arbitrary");
     }
-    if input.attrs.debug.is_some() {
-        tokens.extend(debug::derive(input));
+    if cfg!(feature = "defmt") {
+        extensions.push("Format");
     }
-    if let Some(ref default) = input.attrs.default {
-        tokens.extend(default::derive(input, default));
+    if cfg!(feature = "zeroize") {
+        extensions.push("Zeroize");
     }
-    if input.attrs.eq.is_some() {
-        tokens.extend(cmp::derive_eq(input));
+    if cfg!(feature = "approx") {
+        extensions.push("AbsDiffEq");
+        extensions.push("RelativeEq");
     }
-    if input.attrs.hash.is_some() {
-        tokens.extend(hash::derive(input));
+    extensions
+}
+
+fn derive_impls(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    match input.attrs.remote() {
+        Some(remote) => remote::derive(input, remote, GENERATORS),
+        None => generator::generate_all(input, GENERATORS),
     }
-    if input.attrs.partial_eq.is_some() {
-        tokens.extend(cmp::derive_partial_eq(input)?);
+}
+
+/// Append the report of `input` to the file named by `DERIVATIVE_REPORT`, if set.
+fn write_report(input: &ast::Input) -> Result<(), Error> {
+    let path = match env::var_os("DERIVATIVE_REPORT") {
+        Some(ref path) if !path.is_empty() => path.clone(),
+        _ => return Ok(()),
+    };
+
+    let krate = env::var("CARGO_PKG_NAME").ok();
+    let mut line = report::to_json(krate.as_deref(), input, GENERATORS);
+    line.push('\n');
+
+    // Write the line at once, several crates might be compiled in parallel.
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| {
+            Error::io(
+                format!("could not write the report to `{}`: {}", path.to_string_lossy(), e),
+                input.ident.span(),
+            )
+        })
+}
+
+/// Render `warning` as tokens that make the compiler report it.
+///
+/// Procedural macros can't emit warnings on stable Rust, so this uses a deprecated item. The
+/// warning is only visible if its span comes from the input.
+fn warning_tokens(warning: &lint::Warning) -> proc_macro2::TokenStream {
+    let note = warning.message();
+    let usage = quote_spanned!(warning.span()=> let _ = derivative_warning;);
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct derivative_warning;
+            #usage
+        };
+    }
+}
+
+/// The attributes of the generated implementations.
+///
+/// With the `coverage` feature, the implementations are excluded from coverage reports. The
+/// attribute is only available on nightly compilers, so it is only applied with the
+/// `coverage_nightly` cfg, which `cargo llvm-cov` sets on those.
+fn impl_attrs(input: &ast::Input) -> proc_macro2::TokenStream {
+    let allow_deprecated = allow_deprecated(input);
+    let coverage = if cfg!(all(feature = "coverage", derivative_coverage_attribute)) {
+        Some(quote!(#[cfg_attr(coverage_nightly, coverage(off))]))
+    } else {
+        None
+    };
+
+    quote! {
+        #[allow(unused_qualifications)]
+        #allow_deprecated
+        #coverage
     }
+}
 
-    Ok(tokens)
+/// Allow the `deprecated` lint on the generated items if the type has deprecated parts, which
+/// they use like the built-in derives do.
+fn allow_deprecated(input: &ast::Input) -> Option<proc_macro2::TokenStream> {
+    if input.uses_deprecated() {
+        Some(quote!(#[allow(deprecated)]))
+    } else {
+        None
+    }
 }
 
-fn detail(input: TokenStream) -> Result<TokenStream, String> {
-    let parsed = syn::parse::<syn::DeriveInput>(input).map_err(|e| e.to_string())?;
-    let output = derive_impls(&ast::Input::from_ast(&parsed)?)?;
+fn detail(input: TokenStream) -> Result<TokenStream, Error> {
+    let parsed = syn::parse::<syn::DeriveInput>(input)?;
+    let input = ast::Input::from_ast_with_extensions(&parsed, "derivative", &extensions())?;
+    let mut output = derive_impls(&input)?;
+    if cfg!(any(feature = "lint_std_derive", feature = "lint_inferred_bounds")) {
+        let warnings = lint::check(&input);
+        let enabled = warnings.iter().filter(|warning| match warning.lint() {
+            lint::Lint::StdDerive => cfg!(feature = "lint_std_derive"),
+            lint::Lint::InferredBounds => cfg!(feature = "lint_inferred_bounds"),
+        });
+        output.extend(enabled.map(warning_tokens));
+    }
+    write_report(&input)?;
     Ok(output.into())
 }
 
@@ -71,6 +232,6 @@ fn detail(input: TokenStream) -> Result<TokenStream, String> {
 pub fn derivative(input: TokenStream) -> TokenStream {
     match detail(input) {
         Ok(output) => output,
-        Err(e) => panic!(e),
+        Err(e) => e.to_compile_error().into(),
     }
 }
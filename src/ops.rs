@@ -0,0 +1,685 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use bound;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `Deref`, on structures with exactly one field or a field marked `Deref`.
+pub struct DerefGenerator;
+
+impl TraitGenerator for DerefGenerator {
+    fn name(&self) -> &'static str {
+        "Deref"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.deref.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_deref(input)
+    }
+}
+
+/// The generator for `DerefMut`, which dereferences to the same field as `Deref`.
+pub struct DerefMutGenerator;
+
+impl TraitGenerator for DerefMutGenerator {
+    fn name(&self) -> &'static str {
+        "DerefMut"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.deref_mut.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_deref_mut(input)
+    }
+}
+
+/// The generator for `Index`, on structures with exactly one field or a field marked
+/// `Index="target"`.
+pub struct IndexGenerator;
+
+impl TraitGenerator for IndexGenerator {
+    fn name(&self) -> &'static str {
+        "Index"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.index.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_index(input, false)
+    }
+}
+
+/// The generator for `IndexMut`, which indexes the same field as `Index`.
+pub struct IndexMutGenerator;
+
+impl TraitGenerator for IndexMutGenerator {
+    fn name(&self) -> &'static str {
+        "IndexMut"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.index_mut.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_index(input, true)
+    }
+}
+
+/// The generator for a binary operator trait of `ops`, eg. `Add`, applied field by field on
+/// structures.
+pub struct BinaryOpGenerator {
+    /// The name of the trait in `ops`.
+    name: &'static str,
+    /// The name of the method of the trait.
+    method: &'static str,
+}
+
+pub const ADD: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Add",
+    method: "add",
+};
+
+pub const BIT_AND: BinaryOpGenerator = BinaryOpGenerator {
+    name: "BitAnd",
+    method: "bitand",
+};
+
+pub const BIT_OR: BinaryOpGenerator = BinaryOpGenerator {
+    name: "BitOr",
+    method: "bitor",
+};
+
+pub const BIT_XOR: BinaryOpGenerator = BinaryOpGenerator {
+    name: "BitXor",
+    method: "bitxor",
+};
+
+pub const DIV: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Div",
+    method: "div",
+};
+
+pub const MUL: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Mul",
+    method: "mul",
+};
+
+pub const SHL: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Shl",
+    method: "shl",
+};
+
+pub const SHR: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Shr",
+    method: "shr",
+};
+
+pub const SUB: BinaryOpGenerator = BinaryOpGenerator {
+    name: "Sub",
+    method: "sub",
+};
+
+/// The generator for a compound assignment operator trait of `ops`, eg. `AddAssign`, applied
+/// field by field on structures.
+pub struct AssignOpGenerator {
+    /// The name of the trait in `ops`.
+    name: &'static str,
+    /// The name of the method of the trait.
+    method: &'static str,
+}
+
+pub const ADD_ASSIGN: AssignOpGenerator = AssignOpGenerator {
+    name: "AddAssign",
+    method: "add_assign",
+};
+
+pub const DIV_ASSIGN: AssignOpGenerator = AssignOpGenerator {
+    name: "DivAssign",
+    method: "div_assign",
+};
+
+pub const MUL_ASSIGN: AssignOpGenerator = AssignOpGenerator {
+    name: "MulAssign",
+    method: "mul_assign",
+};
+
+pub const SUB_ASSIGN: AssignOpGenerator = AssignOpGenerator {
+    name: "SubAssign",
+    method: "sub_assign",
+};
+
+/// The generator for a unary operator trait of `ops`, that is `Neg` or `Not`, applied field by
+/// field on structures.
+pub struct UnaryOpGenerator {
+    /// The name of the trait in `ops`.
+    name: &'static str,
+    /// The name of the method of the trait.
+    method: &'static str,
+}
+
+pub const NEG: UnaryOpGenerator = UnaryOpGenerator {
+    name: "Neg",
+    method: "neg",
+};
+
+pub const NOT: UnaryOpGenerator = UnaryOpGenerator {
+    name: "Not",
+    method: "not",
+};
+
+/// The operator traits, which are parsed as extensions since they share the same attributes:
+/// `bound` on the input, and `bound`, `ignore` and `with` on the fields.
+pub const OPERATORS: &[&str] = &[
+    "Add",
+    "AddAssign",
+    "BitAnd",
+    "BitOr",
+    "BitXor",
+    "Div",
+    "DivAssign",
+    "Mul",
+    "MulAssign",
+    "Neg",
+    "Not",
+    "Shl",
+    "Shr",
+    "Sub",
+    "SubAssign",
+];
+
+impl TraitGenerator for BinaryOpGenerator {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension(self.name).is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_binary_op(input, self)
+    }
+}
+
+impl TraitGenerator for AssignOpGenerator {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension(self.name).is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_assign_op(input, self)
+    }
+}
+
+impl TraitGenerator for UnaryOpGenerator {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension(self.name).is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive_unary_op(input, self)
+    }
+}
+
+fn derive_deref(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let (member, field) = target_field(input, "Deref", "Deref", attr::Field::deref_target)?;
+
+    let name = &input.ident;
+    let ty = field.ty;
+    let deref_trait_path = deref_trait_path();
+    let generics = utils::build_impl_generics(
+        input,
+        &deref_trait_path,
+        |_| false,
+        |_| None,
+        |input| input.deref_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #deref_trait_path for #name #ty_generics #where_clause {
+            type Target = #ty;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.#member
+            }
+        }
+    })
+}
+
+fn derive_deref_mut(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let (member, _) = target_field(input, "DerefMut", "Deref", attr::Field::deref_target)?;
+
+    let name = &input.ident;
+    let deref_mut_trait_path = deref_mut_trait_path();
+    let generics = utils::build_impl_generics(
+        input,
+        &deref_mut_trait_path,
+        |_| false,
+        |_| None,
+        |input| input.deref_mut_bound(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #deref_mut_trait_path for #name #ty_generics #where_clause {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.#member
+            }
+        }
+    })
+}
+
+/// Derive `Index<Idx>`, or `IndexMut<Idx>` if `mutable`, for `input` by indexing its target field.
+/// `Idx` is the `index` type if given, or a type parameter of the implementation.
+fn derive_index(input: &ast::Input, mutable: bool) -> Result<proc_macro2::TokenStream, Error> {
+    let (trait_name, trait_path, index, input_bound) = if mutable {
+        let index = input.attrs.index_mut_type();
+        (
+            "IndexMut",
+            index_mut_trait_path(),
+            index,
+            input.attrs.index_mut_bound(),
+        )
+    } else {
+        (
+            "Index",
+            index_trait_path(),
+            input.attrs.index_type(),
+            input.attrs.index_bound(),
+        )
+    };
+    let (member, field) = target_field(
+        input,
+        trait_name,
+        "Index=\"target\"",
+        attr::Field::index_target,
+    )?;
+
+    let mut generics = bound::without_defaults(input.generics);
+    let index = match index {
+        Some(index) => quote!(#index),
+        None => {
+            let index = utils::hygienic_type_parameter(input, "__Idx");
+            generics.params.push(parse_quote!(#index));
+            quote!(#index)
+        }
+    };
+    let ty = field.ty;
+    // The field must be indexable by `Idx` whatever the bounds given to `bound` are.
+    let generics =
+        bound::with_where_predicates(&generics, &[parse_quote!(#ty: #trait_path<#index>)]);
+    let generics = bound::with_where_predicates(&generics, input_bound.unwrap_or(&[]));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let name = &input.ident;
+
+    let body = if mutable {
+        quote! {
+            #[inline]
+            fn index_mut(&mut self, __index: #index) -> &mut Self::Output {
+                #trait_path::index_mut(&mut self.#member, __index)
+            }
+        }
+    } else {
+        quote! {
+            type Output = <#ty as #trait_path<#index>>::Output;
+
+            #[inline]
+            fn index(&self, __index: #index) -> &Self::Output {
+                #trait_path::index(&self.#member, __index)
+            }
+        }
+    };
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path<#index> for #name #ty_generics #where_clause {
+            #body
+        }
+    })
+}
+
+/// Derive the binary operator trait of `generator` for `input`. Each field of the result is the
+/// operator applied to the fields of both operands, or its `with` function applied to them, or
+/// the field of the left operand if it is ignored or a `PhantomData`.
+fn derive_binary_op(
+    input: &ast::Input,
+    generator: &BinaryOpGenerator,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = op_fields(input, generator.name)?;
+    let name = &input.ident;
+    let trait_path = op_trait_path(generator.name);
+    let method = syn::Ident::new(generator.method, proc_macro2::Span::call_site());
+    let generics = op_generics(
+        input,
+        generator.name,
+        |ty_param| parse_quote!(#ty_param: #trait_path<Output = #ty_param>),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let values = fields.iter().enumerate().map(|(i, field)| {
+        let member = field_member(field, i);
+        let extension = field.attrs.extension(generator.name);
+        let value = match extension.and_then(attr::FieldExtension::with) {
+            Some(with) => quote!(#with(self.#member, __rhs.#member)),
+            None if is_op_ignored(field, generator.name) => quote!(self.#member),
+            None => quote!(#trait_path::#method(self.#member, __rhs.#member)),
+        };
+        quote!(#member: #value)
+    });
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            #[inline]
+            fn #method(self, __rhs: Self) -> Self {
+                #name { #(#values),* }
+            }
+        }
+    })
+}
+
+/// Derive the compound assignment operator trait of `generator` for `input`. The operator is
+/// applied to each field with the field of the right operand, or its `with` function is called
+/// with a mutable reference to the field and the field of the right operand. The ignored and
+/// `PhantomData` fields are left unchanged.
+fn derive_assign_op(
+    input: &ast::Input,
+    generator: &AssignOpGenerator,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = op_fields(input, generator.name)?;
+    let name = &input.ident;
+    let trait_path = op_trait_path(generator.name);
+    let method = syn::Ident::new(generator.method, proc_macro2::Span::call_site());
+    let generics = op_generics(
+        input,
+        generator.name,
+        |ty_param| parse_quote!(#ty_param: #trait_path),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let statements = fields.iter().enumerate().filter_map(|(i, field)| {
+        let member = field_member(field, i);
+        let extension = field.attrs.extension(generator.name);
+        match extension.and_then(attr::FieldExtension::with) {
+            Some(with) => Some(quote!(#with(&mut self.#member, __rhs.#member);)),
+            None if is_op_ignored(field, generator.name) => None,
+            None => Some(quote!(#trait_path::#method(&mut self.#member, __rhs.#member);)),
+        }
+    });
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            #[inline]
+            fn #method(&mut self, __rhs: Self) {
+                #(#statements)*
+            }
+        }
+    })
+}
+
+/// Derive the unary operator trait of `generator` for `input`. Each field of the result is the
+/// operator applied to the field, or its `with` function applied to it, or the field itself if it
+/// is ignored or a `PhantomData`.
+fn derive_unary_op(
+    input: &ast::Input,
+    generator: &UnaryOpGenerator,
+) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = op_fields(input, generator.name)?;
+    let name = &input.ident;
+    let trait_path = op_trait_path(generator.name);
+    let method = syn::Ident::new(generator.method, proc_macro2::Span::call_site());
+    let generics = op_generics(
+        input,
+        generator.name,
+        |ty_param| parse_quote!(#ty_param: #trait_path<Output = #ty_param>),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let values = fields.iter().enumerate().map(|(i, field)| {
+        let member = field_member(field, i);
+        let extension = field.attrs.extension(generator.name);
+        let value = match extension.and_then(attr::FieldExtension::with) {
+            Some(with) => quote!(#with(self.#member)),
+            None if is_op_ignored(field, generator.name) => quote!(self.#member),
+            None => quote!(#trait_path::#method(self.#member)),
+        };
+        quote!(#member: #value)
+    });
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            #[inline]
+            fn #method(self) -> Self {
+                #name { #(#values),* }
+            }
+        }
+    })
+}
+
+/// Return the fields of `input`, to which the operator `trait_name` is applied.
+fn op_fields<'a, 'b>(
+    input: &'b ast::Input<'a>,
+    trait_name: &str,
+) -> Result<&'b [ast::Field<'a>], Error> {
+    match input.body {
+        ast::Body::Struct(_, ref fields) => Ok(fields),
+        ast::Body::Enum(_) => Err(Error::unsupported(
+            format!("`{}` can only be derived on structures", trait_name),
+            input.ident.span(),
+        )),
+    }
+}
+
+/// Return the generics of the implementation of the operator trait `trait_name`: the bounds of
+/// the fields, and the `bound` of the input or else the bound returned by `ty_param_bound` for
+/// each type parameter used in a field the operator is applied to.
+fn op_generics<F>(input: &ast::Input, trait_name: &str, ty_param_bound: F) -> syn::Generics
+where
+    F: Fn(&syn::Ident) -> syn::WherePredicate,
+{
+    let generics = bound::without_defaults(input.generics);
+    let generics = bound::with_where_predicates_from_fields(input, &generics, |field| {
+        field
+            .extension(trait_name)
+            .and_then(attr::FieldExtension::bound)
+    });
+
+    let input_bound = input
+        .attrs
+        .extension(trait_name)
+        .and_then(attr::InputExtension::bound);
+    if let Some(predicates) = input_bound {
+        return bound::with_where_predicates(&generics, predicates);
+    }
+
+    let needs_bound = |field: &attr::Field| match field.extension(trait_name) {
+        Some(extension) => {
+            !extension.ignore() && extension.with().is_none() && extension.bound().is_none()
+        }
+        None => true,
+    };
+    let relevant = bound::relevant_ty_params(input, &generics, needs_bound);
+    let predicates: Vec<syn::WherePredicate> = generics
+        .type_params()
+        .map(|ty_param| &ty_param.ident)
+        .filter(|ident| relevant.contains(*ident))
+        .map(ty_param_bound)
+        .collect();
+    bound::with_where_predicates(&generics, &predicates)
+}
+
+/// Whether the operator `trait_name` is not applied to `field`, because it is ignored or a
+/// `PhantomData`, which does not implement the operators.
+fn is_op_ignored(field: &ast::Field, trait_name: &str) -> bool {
+    let ignored = field
+        .attrs
+        .extension(trait_name)
+        .is_some_and(attr::FieldExtension::ignore);
+    ignored || is_phantom_data(field.ty)
+}
+
+/// Whether `ty` is `PhantomData`, whatever its path.
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Path(ref ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Return how to access `field`, the `index`-th field of its structure.
+fn field_member(field: &ast::Field, index: usize) -> proc_macro2::TokenStream {
+    match field.ident {
+        Some(ref ident) => quote!(#ident),
+        None => {
+            let index = syn::Index::from(index);
+            quote!(#index)
+        }
+    }
+}
+
+/// Return the field that `trait_name` delegates to, that is the field for which `is_marked`
+/// returns true, from `#[derivative(<marker>)]`, or the only field of the structure, and how to
+/// access it.
+pub fn target_field<'a, 'b>(
+    input: &'b ast::Input<'a>,
+    trait_name: &str,
+    marker: &str,
+    is_marked: fn(&attr::Field) -> bool,
+) -> Result<(proc_macro2::TokenStream, &'b ast::Field<'a>), Error> {
+    let fields = match input.body {
+        ast::Body::Struct(_, ref fields) => fields,
+        ast::Body::Enum(_) => {
+            return Err(Error::unsupported(
+                format!("`{}` can only be derived on structures", trait_name),
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let mut marked = fields
+        .iter()
+        .enumerate()
+        .filter(|&(_, f)| is_marked(&f.attrs));
+    let (index, field) = match (marked.next(), marked.next()) {
+        (Some(target), None) => target,
+        (Some(_), Some((_, other))) => {
+            return Err(Error::malformed(
+                format!("only one field can be marked `{}`", marker),
+                other.span(),
+            ));
+        }
+        (None, _) if fields.len() == 1 => (0, &fields[0]),
+        (None, _) => {
+            return Err(Error::unsupported(
+                format!(
+                    "`{}` needs a field marked `#[derivative({})]` on a structure that does not \
+                     have exactly one field",
+                    trait_name, marker
+                ),
+                input.ident.span(),
+            ));
+        }
+    };
+
+    Ok((field_member(field, index), field))
+}
+
+/// Return the path of the operator trait `name`, eg. `::std::ops::Add`.
+fn op_trait_path(name: &str) -> syn::Path {
+    let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::#name)
+    } else {
+        parse_quote!(::std::ops::#name)
+    }
+}
+
+/// Return the path of the `Deref` trait, that is `::std::ops::Deref`.
+fn deref_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::Deref)
+    } else {
+        parse_quote!(::std::ops::Deref)
+    }
+}
+
+/// Return the path of the `DerefMut` trait, that is `::std::ops::DerefMut`.
+fn deref_mut_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::DerefMut)
+    } else {
+        parse_quote!(::std::ops::DerefMut)
+    }
+}
+
+/// Return the path of the `Index` trait, that is `::std::ops::Index`.
+fn index_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::Index)
+    } else {
+        parse_quote!(::std::ops::Index)
+    }
+}
+
+/// Return the path of the `IndexMut` trait, that is `::std::ops::IndexMut`.
+fn index_mut_trait_path() -> syn::Path {
+    if cfg!(feature = "use_core") {
+        parse_quote!(::core::ops::IndexMut)
+    } else {
+        parse_quote!(::std::ops::IndexMut)
+    }
+}
@@ -0,0 +1,259 @@
+use proc_macro2;
+
+use ast;
+use bound;
+use matcher;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The traits that can be derived with `remote`, the functions generated for them, and the
+/// attribute using these functions.
+const REMOTE_FUNCTIONS: &[(&str, &str, &str)] = &[
+    ("Debug", "debug_remote", "format_with"),
+    ("Hash", "hash_remote", "hash_with"),
+    ("PartialEq", "eq_remote", "compare_with"),
+    ("PartialOrd", "partial_cmp_remote", "partial_cmp_with"),
+    ("Ord", "cmp_remote", "cmp_with"),
+];
+
+/// Generate the functions implementing the requested traits for the foreign type given to
+/// `remote`, of which `input` is a copy.
+///
+/// Each trait is implemented as usual, except that the implementation is for the foreign type
+/// and of a private trait with the same methods. The functions of the mirror type call it, and
+/// have the signatures expected by `format_with`, `hash_with`, `compare_with`, `partial_cmp_with`
+/// and `cmp_with`.
+pub fn derive(
+    input: &ast::Input,
+    remote: &syn::Path,
+    generators: &[&dyn TraitGenerator],
+) -> Result<proc_macro2::TokenStream, Error> {
+    if input.attrs.hash_content_hash() {
+        return Err(Error::conflict(
+            "`Hash(content_hash)` can't be used with `remote`",
+            input.ident.span(),
+        ));
+    }
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let remote_ty: syn::Type = parse_quote!(#remote #ty_generics);
+
+    let impl_attrs = impl_attrs(input);
+
+    let mut items = Vec::new();
+    for generator in generators {
+        if !generator.is_requested(input) {
+            continue;
+        }
+
+        let trait_name = generator.name();
+        let &(_, function_name, with) = REMOTE_FUNCTIONS
+            .iter()
+            .find(|&&(n, _, _)| n == trait_name)
+            .ok_or_else(|| {
+                Error::unsupported(
+                    format!(
+                        "`{}` can't be derived with `remote`, only `Debug`, `Hash`, `PartialEq`, \
+                         `PartialOrd` and `Ord` can",
+                        trait_name
+                    ),
+                    input.ident.span(),
+                )
+            })?;
+        let function_name = syn::Ident::new(function_name, proc_macro2::Span::call_site());
+        let doc = format!(
+            "The `{}` implementation of `{}`, to use with `{}`.",
+            trait_name,
+            quote!(#remote).to_string().replace(' ', ""),
+            with
+        );
+        let trait_ident = syn::Ident::new(
+            &format!("__DerivativeRemote{}", trait_name),
+            proc_macro2::Span::call_site(),
+        );
+
+        let file = syn::parse2::<syn::File>(generator.generate(input)?)?;
+        for item in file.items {
+            let mut item = match item {
+                syn::Item::Impl(item) => item,
+                item => {
+                    items.push(quote!(#item));
+                    continue;
+                }
+            };
+
+            let sigs: Vec<_> = item
+                .items
+                .iter()
+                .filter_map(|impl_item| match *impl_item {
+                    syn::ImplItem::Method(ref method) => Some(&method.sig),
+                    _ => None,
+                })
+                .collect();
+            let trait_def = quote! {
+                trait #trait_ident {
+                    #(#sigs;)*
+                }
+            };
+
+            if let Some((_, ref mut path, _)) = item.trait_ {
+                *path = parse_quote!(#trait_ident);
+            }
+            *item.self_ty = remote_ty.clone();
+
+            let function =
+                remote_function(input, trait_name, &function_name, &trait_ident, &remote_ty);
+            let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+            items.push(quote! {
+                #trait_def
+
+                #item
+
+                #[automatically_derived]
+                #impl_attrs
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #[doc = #doc]
+                    #function
+                }
+            });
+        }
+    }
+
+    let check = mirror_check(input, &remote_ty);
+    let output = quote! {
+        #(#items)*
+
+        #check
+    };
+
+    Ok(quote!(const _: () = { #output };))
+}
+
+/// Generate the function named `function_name` of the mirror type, which calls the method of the
+/// private trait `trait_ident` implemented for the foreign type.
+fn remote_function(
+    input: &ast::Input,
+    trait_name: &str,
+    function_name: &syn::Ident,
+    trait_ident: &syn::Ident,
+    remote_ty: &syn::Type,
+) -> proc_macro2::TokenStream {
+    let (std, fmt_path) = if cfg!(feature = "use_core") {
+        (quote!(::core), quote!(::core::fmt))
+    } else {
+        (quote!(::std), quote!(::std::fmt))
+    };
+
+    match trait_name {
+        "Debug" => quote! {
+            pub fn #function_name(
+                __value: &#remote_ty,
+                __f: &mut #fmt_path::Formatter,
+            ) -> #fmt_path::Result {
+                #trait_ident::fmt(__value, __f)
+            }
+        },
+        "Hash" => {
+            let hasher_ty_parameter = utils::hygienic_type_parameter(input, "__H");
+            quote! {
+                pub fn #function_name<#hasher_ty_parameter>(
+                    __value: &#remote_ty,
+                    __state: &mut #hasher_ty_parameter,
+                ) where
+                    #hasher_ty_parameter: #std::hash::Hasher,
+                {
+                    #trait_ident::hash(__value, __state)
+                }
+            }
+        }
+        "PartialEq" => quote! {
+            pub fn #function_name(__a: &#remote_ty, __b: &#remote_ty) -> bool {
+                #trait_ident::eq(__a, __b)
+            }
+        },
+        "PartialOrd" => quote! {
+            pub fn #function_name(
+                __a: &#remote_ty,
+                __b: &#remote_ty,
+            ) -> #std::option::Option<#std::cmp::Ordering> {
+                #trait_ident::partial_cmp(__a, __b)
+            }
+        },
+        "Ord" => quote! {
+            pub fn #function_name(__a: &#remote_ty, __b: &#remote_ty) -> #std::cmp::Ordering {
+                #trait_ident::cmp(__a, __b)
+            }
+        },
+        _ => unreachable!("`{}` is not in `REMOTE_FUNCTIONS`", trait_name),
+    }
+}
+
+/// Generate a function converting the foreign type into the mirror type and back, so that the
+/// compiler checks that they have the same fields.
+fn mirror_check(input: &ast::Input, remote_ty: &syn::Type) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let remote_path = matcher::type_path(input);
+    let shapes: Vec<(syn::Path, syn::Path, ast::Style, &[ast::Field])> = match input.body {
+        ast::Body::Enum(ref variants) => variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                (
+                    parse_quote!(#name::#variant_ident),
+                    parse_quote!(#remote_path::#variant_ident),
+                    variant.style,
+                    &variant.fields[..],
+                )
+            })
+            .collect(),
+        ast::Body::Struct(style, ref fields) => {
+            vec![(parse_quote!(#name), remote_path.clone(), style, &fields[..])]
+        }
+    };
+
+    let matcher = matcher::Matcher::new(matcher::BindingStyle::Move);
+    let mut to_mirror = Vec::new();
+    let mut to_remote = Vec::new();
+    for &(ref mirror_path, ref remote_path, style, fields) in &shapes {
+        let (pat, bis) = matcher.build_match_pattern(remote_path, style, fields);
+        let value = construct(mirror_path, &bis);
+        to_mirror.push(quote!(#pat => #value,));
+
+        let (pat, bis) = matcher.build_match_pattern(mirror_path, style, fields);
+        let value = construct(remote_path, &bis);
+        to_remote.push(quote!(#pat => #value,));
+    }
+
+    let generics = bound::without_defaults(input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[allow(dead_code)]
+        fn __derivative_remote_check #impl_generics (
+            __remote: #remote_ty,
+        ) -> #remote_ty #where_clause {
+            let __mirror: #name #ty_generics = match __remote {
+                #(#to_mirror)*
+            };
+            match __mirror {
+                #(#to_remote)*
+            }
+        }
+    }
+}
+
+/// Construct the structure or variant `path` from the bindings of its fields.
+fn construct(path: &syn::Path, bis: &[matcher::BindingInfo]) -> proc_macro2::TokenStream {
+    let fields = bis.iter().enumerate().map(|(i, bi)| {
+        let arg = &bi.ident;
+        match bi.field.ident {
+            Some(ref ident) => quote!(#ident: #arg),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(#index: #arg)
+            }
+        }
+    });
+    quote!(#path { #(#fields),* })
+}
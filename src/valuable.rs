@@ -0,0 +1,248 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use debug;
+use matcher;
+use syn;
+use syn::spanned::Spanned;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `valuable::Valuable`, and `Structable` or `Enumerable`.
+///
+/// The fields use the `Debug` attributes too, so that structured logs get the same redaction as
+/// the `Debug` output: fields ignored by `Debug` are not visited, masked fields are visited as
+/// their mask, and fields with a `format_with` function are visited as the string it formats.
+pub struct ValuableGenerator;
+
+impl TraitGenerator for ValuableGenerator {
+    fn name(&self) -> &'static str {
+        "Valuable"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("Valuable").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let valuable_trait_path: syn::Path = parse_quote!(::valuable::Valuable);
+    let name = &input.ident;
+
+    if cfg!(feature = "use_core") {
+        let formatted = input
+            .body
+            .all_fields()
+            .into_iter()
+            .find(|f| !is_ignored(&f.attrs) && is_formatted(&f.attrs));
+        if let Some(f) = formatted {
+            return Err(Error::unsupported(
+                "`Debug(format_with)` can't be used with `Valuable` and the `use_core` feature, \
+                 use `Valuable(with)` instead",
+                f.ty.span(),
+            ));
+        }
+    }
+
+    let body = matcher::Matcher::new(matcher::BindingStyle::Ref).build_arms(
+        input,
+        |_, _, style, _, bis| {
+            let shown: Vec<_> = bis.iter().filter(|bi| !is_ignored(&bi.field.attrs)).collect();
+
+            // The string formatted by `Debug(format_with)` must outlive the values.
+            let formatted = bis
+                .iter()
+                .enumerate()
+                .filter(|&(_, bi)| !is_ignored(&bi.field.attrs) && is_formatted(&bi.field.attrs));
+            let prepares = formatted.map(|(i, bi)| {
+                let arg = &bi.ident;
+                let format_fn = bi.field.attrs.debug_format_with().expect("the field is formatted");
+                let dummy_debug = debug::format_with(input, bi.field, i, arg, format_fn);
+                quote! {
+                    #dummy_debug
+                    let #arg = format!("{:?}", #arg);
+                }
+            });
+            let values = shown.iter().map(|bi| {
+                let arg = &bi.ident;
+                match bi.field.attrs.extension("Valuable").and_then(attr::FieldExtension::with) {
+                    Some(with) => quote!(#with(#arg)),
+                    None if is_formatted(&bi.field.attrs) => {
+                        quote!(::valuable::Value::String(&#arg))
+                    }
+                    None => match bi.field.attrs.debug_mask() {
+                        Some(mask) => quote!(::valuable::Value::String(#mask)),
+                        None => quote!(#valuable_trait_path::as_value(#arg)),
+                    },
+                }
+            });
+
+            match style {
+                ast::Style::Struct => {
+                    let fields = named_fields(input, shown.iter().map(|bi| bi.field));
+                    quote! {
+                        #(#prepares)*
+                        static __FIELDS: &[::valuable::NamedField<'static>] = #fields;
+                        ::valuable::Visit::visit_named_fields(
+                            __visit,
+                            &::valuable::NamedValues::new(__FIELDS, &[#(#values),*]),
+                        )
+                    }
+                }
+                ast::Style::Tuple | ast::Style::Unit => quote! {
+                    #(#prepares)*
+                    ::valuable::Visit::visit_unnamed_fields(__visit, &[#(#values),*])
+                },
+            }
+        },
+    );
+
+    let generics = utils::build_impl_generics(
+        input,
+        &valuable_trait_path,
+        needs_valuable_bound,
+        |field| field.extension("Valuable").and_then(attr::FieldExtension::bound),
+        |input| input.extension("Valuable").and_then(attr::InputExtension::bound),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let name_str = name.to_string();
+    let impl_attrs = impl_attrs(input);
+    let (value, definition) = match input.body {
+        ast::Body::Enum(ref variants) => {
+            let defs = variants.iter().map(|variant| {
+                let variant_name = variant.ident.to_string();
+                let fields = fields_def(input, variant.style, &variant.fields);
+                quote!(::valuable::VariantDef::new(#variant_name, #fields))
+            });
+            let variants_def = quote! {
+                static __VARIANTS: &[::valuable::VariantDef<'static>] = &[#(#defs),*];
+            };
+            let arms = variants.iter().enumerate().map(|(i, variant)| {
+                let variant_ident = &variant.ident;
+                quote!(#name::#variant_ident { .. } => ::valuable::Variant::Static(&__VARIANTS[#i]),)
+            });
+
+            (
+                quote!(::valuable::Value::Enumerable(self)),
+                quote! {
+                    #[automatically_derived]
+                    #impl_attrs
+                    impl #impl_generics ::valuable::Enumerable for #name #ty_generics #where_clause {
+                        fn definition(&self) -> ::valuable::EnumDef {
+                            #variants_def
+                            ::valuable::EnumDef::new_static(#name_str, __VARIANTS)
+                        }
+
+                        fn variant(&self) -> ::valuable::Variant {
+                            #variants_def
+                            match *self {
+                                #(#arms)*
+                            }
+                        }
+                    }
+                },
+            )
+        }
+        ast::Body::Struct(style, ref fields) => {
+            // Unlike in the `static` of the variants, the fields must be in a `static` to be
+            // borrowed for `'static`.
+            let fields = match style {
+                ast::Style::Struct => {
+                    let shown = fields.iter().filter(|f| !is_ignored(&f.attrs));
+                    let fields = named_fields(input, shown);
+                    quote!({
+                        static __FIELDS: &[::valuable::NamedField<'static>] = #fields;
+                        ::valuable::Fields::Named(__FIELDS)
+                    })
+                }
+                ast::Style::Tuple | ast::Style::Unit => fields_def(input, style, fields),
+            };
+            (
+                quote!(::valuable::Value::Structable(self)),
+                quote! {
+                    #[automatically_derived]
+                    #impl_attrs
+                    impl #impl_generics ::valuable::Structable for #name #ty_generics #where_clause {
+                        fn definition(&self) -> ::valuable::StructDef {
+                            ::valuable::StructDef::new_static(#name_str, #fields)
+                        }
+                    }
+                },
+            )
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #valuable_trait_path for #name #ty_generics #where_clause {
+            fn as_value(&self) -> ::valuable::Value {
+                #value
+            }
+
+            // Without the parentheses, `dyn ::valuable::Visit` is a path on Rust 2015.
+            #[allow(unused_parens)]
+            fn visit(&self, __visit: &mut dyn (::valuable::Visit)) {
+                match *self {
+                    #body
+                }
+            }
+        }
+
+        #definition
+    })
+}
+
+/// Return the `valuable::Fields` of a structure or variant, without its ignored fields.
+fn fields_def(
+    input: &ast::Input,
+    style: ast::Style,
+    fields: &[ast::Field],
+) -> proc_macro2::TokenStream {
+    let shown = fields.iter().filter(|f| !is_ignored(&f.attrs));
+
+    match style {
+        ast::Style::Struct => {
+            let fields = named_fields(input, shown);
+            quote!(::valuable::Fields::Named(#fields))
+        }
+        ast::Style::Tuple | ast::Style::Unit => {
+            let len = shown.count();
+            quote!(::valuable::Fields::Unnamed(#len))
+        }
+    }
+}
+
+/// Return the slice of the `valuable::NamedField`s of `fields`.
+fn named_fields<'a, 'b: 'a, I>(input: &ast::Input, fields: I) -> proc_macro2::TokenStream
+where
+    I: Iterator<Item = &'a ast::Field<'b>>,
+{
+    let names = fields.map(|f| debug::field_name(input, f));
+    quote!(&[#(::valuable::NamedField::new(#names)),*])
+}
+
+/// Whether the field is ignored, with `Valuable="ignore"` or `Debug="ignore"`.
+fn is_ignored(attrs: &attr::Field) -> bool {
+    attrs.ignore_debug() || attrs.extension("Valuable").is_some_and(attr::FieldExtension::ignore)
+}
+
+/// Whether the field is visited as the string its `Debug(format_with)` function formats.
+fn is_formatted(attrs: &attr::Field) -> bool {
+    attrs.debug_format_with().is_some()
+        && attrs.extension("Valuable").and_then(attr::FieldExtension::with).is_none()
+}
+
+fn needs_valuable_bound(attrs: &attr::Field) -> bool {
+    let with = attrs.extension("Valuable").and_then(attr::FieldExtension::with);
+    !is_ignored(attrs)
+        && with.is_none()
+        && attrs.debug_format_with().is_none()
+        && attrs.debug_mask().is_none()
+}
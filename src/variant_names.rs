@@ -0,0 +1,87 @@
+use proc_macro2;
+
+use ast;
+use bound;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator of the `VARIANT_NAMES` constant and the `variant_name` method of an
+/// enumeration.
+pub struct VariantNamesGenerator;
+
+impl TraitGenerator for VariantNamesGenerator {
+    fn name(&self) -> &'static str {
+        "VariantNames"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.variant_names.is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        derive(input)
+    }
+}
+
+fn derive(input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+    let variants = match input.body {
+        ast::Body::Enum(ref variants) => variants,
+        ast::Body::Struct(..) => {
+            return Err(Error::unsupported(
+                "`VariantNames` can only be derived on enumerations",
+                input.ident.span(),
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let vis = input.vis;
+    let names: Vec<_> = variants
+        .iter()
+        .map(|variant| variant_name(input, variant))
+        .collect();
+    let arms: Vec<_> = variants
+        .iter()
+        .zip(&names)
+        .map(|(variant, variant_name)| {
+            let variant_ident = &variant.ident;
+            quote!(#name::#variant_ident { .. } => #variant_name,)
+        })
+        .collect();
+
+
+    let generics = bound::without_defaults(input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    Ok(quote! {
+        #impl_attrs
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The names of the variants of this type, in order.
+            #vis const VARIANT_NAMES: &'static [&'static str] = &[#(#names),*];
+
+            /// Returns the name of the variant of this value.
+            #[inline]
+            #[must_use]
+            #vis fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// The name of a variant: its `VariantNames` or `FromStr` rename if any, or else its name
+/// following the `rename_all` convention of the type.
+fn variant_name(input: &ast::Input, variant: &ast::Variant) -> String {
+    let rename = variant
+        .attrs
+        .variant_names_rename()
+        .or_else(|| variant.attrs.from_str_rename());
+    match (rename, input.attrs.rename_all()) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(rule)) => rule.apply_to_variant(&variant.ident.to_string()),
+        (None, None) => variant.ident.to_string(),
+    }
+}
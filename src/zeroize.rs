@@ -0,0 +1,101 @@
+use proc_macro2;
+
+use ast;
+use attr;
+use matcher;
+use syn;
+use utils;
+use {impl_attrs, Error, TraitGenerator};
+
+/// The generator for `zeroize::Zeroize`.
+///
+/// The ignored fields are left as they are, and the fields with a `zeroize_with` function are
+/// wiped by calling it with a mutable reference to the field.
+pub struct ZeroizeGenerator;
+
+impl TraitGenerator for ZeroizeGenerator {
+    fn name(&self) -> &'static str {
+        "Zeroize"
+    }
+
+    fn is_requested(&self, input: &ast::Input) -> bool {
+        input.attrs.extension("Zeroize").is_some()
+    }
+
+    fn generate(&self, input: &ast::Input) -> Result<proc_macro2::TokenStream, Error> {
+        Ok(derive(input))
+    }
+}
+
+fn derive(input: &ast::Input) -> proc_macro2::TokenStream {
+    let zeroize_trait_path: syn::Path = parse_quote!(::zeroize::Zeroize);
+
+    let arms = matcher::Matcher::new(matcher::BindingStyle::RefMut).build_arms(
+        input,
+        |_, _, _, _, bis| {
+            let stmts = bis.iter().filter_map(|bi| {
+                let arg = &bi.ident;
+                if is_ignored(&bi.field.attrs) {
+                    None
+                } else if let Some(with) = zeroize_with(&bi.field.attrs) {
+                    Some(quote!(#with(#arg);))
+                } else {
+                    Some(quote!(#zeroize_trait_path::zeroize(#arg);))
+                }
+            });
+            quote!(#(#stmts)*)
+        },
+    );
+
+    let name = &input.ident;
+    let generics = utils::build_impl_generics(
+        input,
+        &zeroize_trait_path,
+        needs_zeroize_bound,
+        |field| {
+            field
+                .extension("Zeroize")
+                .and_then(attr::FieldExtension::bound)
+        },
+        |input| {
+            input
+                .extension("Zeroize")
+                .and_then(attr::InputExtension::bound)
+        },
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_attrs = impl_attrs(input);
+
+    quote! {
+        #[automatically_derived]
+        #impl_attrs
+        impl #impl_generics #zeroize_trait_path for #name #ty_generics #where_clause {
+            fn zeroize(&mut self) {
+                match *self {
+                    #arms
+                }
+            }
+        }
+    }
+}
+
+fn needs_zeroize_bound(attrs: &attr::Field) -> bool {
+    let extension = attrs.extension("Zeroize");
+    !is_ignored(attrs)
+        && zeroize_with(attrs).is_none()
+        && extension.and_then(attr::FieldExtension::bound).is_none()
+}
+
+fn is_ignored(attrs: &attr::Field) -> bool {
+    attrs
+        .extension("Zeroize")
+        .is_some_and(attr::FieldExtension::ignore)
+}
+
+/// The function wiping the field, from `Zeroize(zeroize_with="…")`.
+fn zeroize_with(attrs: &attr::Field) -> Option<&syn::Path> {
+    attrs
+        .extension("Zeroize")
+        .and_then(attr::FieldExtension::with)
+}
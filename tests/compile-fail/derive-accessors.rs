@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Accessors)]
+struct Point(u8, u8); //~ ERROR `Accessors` can only be derived on enumerations
+
+#[derive(Derivative)]
+#[derivative(Accessors)]
+enum Event {
+    KeyPress(char),
+    #[derivative(Accessors(rename = "key_press"))]
+    KeyRelease(char), //~ ERROR the accessors of this variant have the same names as those of `KeyPress`
+}
+
+fn main() {}
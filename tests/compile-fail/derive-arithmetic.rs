@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Add)]
+enum Shape {
+//~^ ERROR `Add` can only be derived on structures
+    Point,
+}
+
+fn main() {}
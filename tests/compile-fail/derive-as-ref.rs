@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(AsRef)]
+struct Pair(u8, u8);
+//~^ ERROR `AsRef` needs fields marked `#[derivative(AsRef)]` on a structure that does not have exactly one field
+
+#[derive(Derivative)]
+#[derivative(AsMut)]
+struct SameType {
+    #[derivative(AsMut)]
+    a: u8,
+    #[derivative(AsMut)]
+    b: u8,
+    //~^ ERROR another field marked `AsMut` has the same type
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Borrow)]
+struct Pair(u8, u8);
+//~^ ERROR `Borrow` can only be derived on structures with exactly one field
+
+#[derive(Derivative)]
+#[derivative(Borrow, PartialEq, Eq, Hash)]
+struct CaseInsensitive(#[derivative(PartialEq(compare_with = "eq_ignore_case"))] String);
+//~^ ERROR `Borrow` can't be derived with `PartialEq(compare_with)`
+
+#[derive(Derivative)]
+#[derivative(BorrowMut, Hash(type_tag))]
+struct Tagged(u8);
+//~^ ERROR `BorrowMut` can't be derived with `Hash(type_tag)`
+
+fn eq_ignore_case(a: &String, b: &String) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Point(u8, u8); //~ ERROR `Builder` can only be derived on structures with named fields
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Task {
+    #[derivative(Builder="ignore")]
+    owner: &'static Task, //~ ERROR a field ignored by `Builder` needs a default value
+}
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Pipeline {
+    build: bool, //~ ERROR the setter of this field conflicts with `PipelineBuilder::build`
+}
+
+fn main() {}
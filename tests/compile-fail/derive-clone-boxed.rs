@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate derivative;
+
+use std::rc::Rc;
+
+trait Shape {}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Foo {
+    #[derivative(Clone(boxed="Foo::clone_shape"))]
+    foo: Rc<dyn Shape>,
+    //~^ ERROR `Clone(boxed)` can only be used on a `Box`
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Bar {
+    #[derivative(Clone="boxed")]
+    bar: Box<dyn Shape>,
+    //~^ ERROR `Clone="boxed"` needs the `dyn-clone` feature of derivative, or a clone-box function given with `Clone(boxed="…")`
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Baz {
+    #[derivative(Clone(boxed, shared))]
+    baz: Box<dyn Shape>,
+    //~^^ ERROR `Clone(boxed)` conflicts with `Clone(shared)`
+}
+
+fn main() {}
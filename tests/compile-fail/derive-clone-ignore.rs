@@ -0,0 +1,21 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Foo {
+    #[derivative(Clone(ignore, clone_with="Clone::clone"))]
+    foo: u8,
+    //~^^ ERROR `Clone(clone_with)` conflicts with `Clone(ignore)`
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(is_copy))]
+struct Bar {
+    bar: u8,
+    #[derivative(Clone="ignore")]
+    count: u8,
+    //~^ ERROR `Clone(ignore)` can't be used with `Clone(is_copy)`, which copies all the fields
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate derivative;
+
+use std::rc::Rc;
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Foo<T> {
+    #[derivative(Clone="shared")]
+    foo: Box<T>,
+    //~^ ERROR `Clone(shared)` can only be used on an `Rc`, an `Arc` or a `Weak` pointer
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Bar<T> {
+    #[derivative(Clone(shared, clone_with="Rc::clone"))]
+    bar: Rc<T>,
+    //~^^ ERROR `Clone(clone_with)` conflicts with `Clone(shared)`
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_with="Foo::copy", is_copy))]
+struct Foo {
+//~^ ERROR `Clone(is_copy)` can't be used with `Clone(clone_with)`, which clones the whole value
+    foo: u8,
+}
+
+impl Foo {
+    fn copy(&self) -> Self {
+        Foo { foo: self.foo }
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(is_copy))]
+struct Bar {
+    #[derivative(Clone(clone_with="Clone::clone"))]
+    bar: u8,
+    //~^ ERROR `Clone(clone_with)` can't be used with `Clone(is_copy)`, which copies all the fields
+}
+
+fn main() {}
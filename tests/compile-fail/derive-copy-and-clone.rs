@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Copy(and_clone), Clone)]
+struct Foo {
+//~^ ERROR `Copy(and_clone)` can't be used when `Clone` is derived too, use `Clone(is_copy)` instead
+    foo: u8,
+}
+
+fn main() {}
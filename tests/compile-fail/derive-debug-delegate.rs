@@ -0,0 +1,29 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Twice {
+    #[derivative(Debug(delegate))]
+    first: u8,
+    #[derivative(Debug(delegate))]
+    second: u8, //~ ERROR only one field can be marked `Debug(delegate)`
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Ignored {
+    #[derivative(Debug(delegate, ignore))] //~ ERROR `Debug(delegate)` conflicts with `Debug(ignore)`
+    value: u8,
+    other: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Flattenable { //~ ERROR `Debug(delegate)` can't be used on a structure marked `Debug(flattenable)`, whose fields are shown by its parent
+    #[derivative(Debug(delegate))]
+    value: u8,
+    other: u8,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Inner {
+    value: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Tuple(u32); //~ ERROR `Debug(flattenable)` can only be used on structures with named fields
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Positional(#[derivative(Debug(flatten))] Inner); //~ ERROR `Debug(flatten)` can only be used on named fields
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Renamed {
+    #[derivative(Debug(flatten, rename="other"))] //~ ERROR `Debug(flatten)` conflicts with `Debug(rename)`
+    inner: Inner,
+}
+
+struct Plain {
+    value: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct NotFlattenable {
+    #[derivative(Debug(flatten))]
+    plain: Plain, //~ ERROR no method named `__derivative_debug_fields` found for reference `&Plain`
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Ignored {
+    #[derivative(Debug="ignore", Debug(format="{:x}"))] //~ ERROR `Debug(format)` conflicts with `Debug(ignore)`
+    id: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Formatted {
+    #[derivative(Debug(format="{:x}", format_with="std::fmt::Debug::fmt"))] //~ ERROR `Debug(format)` conflicts with `Debug(format_with)`
+    id: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct NotHex {
+    #[derivative(Debug(format="{:x}"))]
+    name: &'static str, //~ ERROR the trait bound `str: LowerHex` is not satisfied
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Masked {
+    #[derivative(Debug(mask, format="{:x}"))] //~ ERROR `Debug(format)` conflicts with `Debug(mask)`
+    id: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Truncated {
+    #[derivative(Debug(max_len="many"))] //~ ERROR Invalid value for `max_len`: `many`
+    bytes: Vec<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Named {
+    #[derivative(Debug(pass_name))] //~ ERROR `Debug(pass_name)` can only be used with `Debug(format_with)`
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[repr(C, packed)]
+struct Message {
+    id: u32,
+    text: String, //~ ERROR the trait bound `String: Copy` is not satisfied
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Point(
+    #[derivative(Debug(rename="x"))] i32, //~ ERROR `Debug(rename)` can only be used on named fields
+    i32,
+);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Size {
+    #[derivative(Debug(name="w"))]
+    width: u32, //~ ERROR `Debug(name)` can only be used on the fields of a tuple
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(rename_all="camel"))] //~ ERROR Invalid value for `rename_all`: `camel`
+struct Camel {
+    field_name: u32,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Tuple(#[derivative(Debug(skip_if_none))] Option<u8>); //~ ERROR `Debug(skip_if_none)` can only be used on named fields
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Ignored {
+    #[derivative(Debug(skip_if_default, ignore))] //~ ERROR `Debug(skip_if_default)` conflicts with `Debug(ignore)`
+    retries: u32,
+}
+
+struct NoDefault;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Config {
+    #[derivative(Debug(skip_if_default), Debug(format_with="fmt_no_default"))]
+    value: NoDefault,
+    //~^ ERROR binary operation `!=` cannot be applied to type `NoDefault`
+    //~| ERROR the trait bound `NoDefault: Default` is not satisfied
+}
+
+fn fmt_no_default(_: &NoDefault, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.write_str("NoDefault")
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate derivative;
+
+use std::collections::HashMap;
+
+type Headers = HashMap<String, String>;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Alias {
+    #[derivative(Debug(sort_maps))]
+    headers: Headers, //~ ERROR `Debug(sort_maps)` can only be used on a map or a set whose type gives its type arguments, eg. `HashMap<K, V>` or `HashSet<T>`
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Truncated {
+    #[derivative(Debug(sort_maps, max_len="2"))] //~ ERROR `Debug(max_len)` conflicts with `Debug(sort_maps)`
+    counts: HashMap<u8, u8>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Unordered;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Keys {
+    #[derivative(Debug(sort_maps))]
+    keys: std::collections::HashSet<Unordered>, //~ ERROR the trait bound `Unordered: Ord` is not satisfied
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default(const))]
+struct Counter {
+    #[derivative(Default(value="1"))]
+    step: u32,
+    count: u32, //~ ERROR `Default(const)` needs a `Default(value="…")` on every field
+}
+
+#[derive(Derivative)]
+#[derivative(Default(const))]
+struct Buffer {
+    #[derivative(Default(capacity="16"))]
+    data: Vec<u8>, //~ ERROR `Default(const)` can only use `Default(value="…")`
+}
+
+#[derive(Derivative)]
+#[derivative(Default(const, new))]
+struct Named {
+    #[derivative(Default(required))]
+    name: String, //~ ERROR `Default(const)` can't be used with `Default(required)` fields
+}
+
+fn main() {}
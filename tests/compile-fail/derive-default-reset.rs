@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default(new, reset))]
+struct Settings {
+    #[derivative(Default(required))]
+    name: String, //~ ERROR `Default(reset)` can't be used with a default value using the `Default(required)` field `name`
+    #[derivative(Default(value="name.len()"))]
+    len: usize,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default)]
+struct Callback {
+    call: fn(u8) -> u8,
+    //~^ ERROR this field has no default value since its type can't implement `Default`
+    calls: u32,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Default)]
+enum NoDefault {
+    //~^ ERROR no default variant
+    A,
+    B,
+}
+
+#[derive(Derivative)]
+#[derivative(Default)]
+enum TwoDefaults {
+    #[derivative(Default)]
+    A,
+    #[derivative(Default)]
+    B,
+    //~^ ERROR only one variant can be the default
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Deref)]
+struct Pair(u8, u8);
+//~^ ERROR `Deref` needs a field marked `#[derivative(Deref)]` on a structure that does not have exactly one field
+
+#[derive(Derivative)]
+#[derivative(Deref)]
+struct Twice {
+    #[derivative(Deref)]
+    a: u8,
+    #[derivative(Deref)]
+    b: u8,
+    //~^ ERROR only one field can be marked `Deref`
+}
+
+#[derive(Derivative)]
+#[derivative(DerefMut)]
+enum Either {
+//~^ ERROR `DerefMut` can only be derived on structures
+    A(u8),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Drop)]
+struct Connection {
+//~^ ERROR `Drop` needs `Drop(with="…")` on the type or on a field
+    id: u32,
+}
+
+fn main() {}
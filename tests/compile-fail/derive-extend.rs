@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Extend)]
+struct Pair {
+    #[derivative(Extend = "target")]
+    a: Vec<u8>,
+    #[derivative(Extend = "target")]
+    b: Vec<u8>,
+//~^ ERROR only one field can be marked `Extend="target"`
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(FromIterator)]
+struct Pair(Vec<u8>, Vec<u8>);
+//~^ ERROR `FromIterator` can only be derived on structures with exactly one field
+
+fn main() {}
@@ -0,0 +1,27 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(FromStr)]
+struct Foo;
+//~^ ERROR `FromStr` can only be derived on enumerations
+
+#[derive(Derivative)]
+#[derivative(FromStr)]
+enum WithFields {
+    A,
+    B(u8),
+    //~^ ERROR `FromStr` can only be derived on enumerations whose variants have no fields
+}
+
+#[derive(Derivative)]
+#[derivative(FromStr)]
+enum Duplicate {
+    #[derivative(FromStr(alias = "b"))]
+    A,
+    #[derivative(FromStr(rename = "b"))]
+    B,
+    //~^ ERROR `b` is already parsed as another variant
+}
+
+fn main() {}
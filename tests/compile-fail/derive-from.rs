@@ -0,0 +1,21 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(From)]
+struct Pair(u8, u8);
+//~^ ERROR `From` can only be derived on structures with exactly one field
+
+#[derive(Derivative)]
+#[derivative(From)]
+enum Either {
+//~^ ERROR `From` can only be derived on structures with exactly one field
+    A(u8),
+}
+
+#[derive(Derivative)]
+#[derivative(From = "into")]
+struct Wrapper<T>(T);
+//~^ ERROR `From(into)` can't be derived when the field is a type parameter
+
+fn main() {}
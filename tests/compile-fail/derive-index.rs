@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Index)]
+struct Pair(Vec<u8>, Vec<u8>);
+//~^ ERROR `Index` needs a field marked `#[derivative(Index="target")]` on a structure that does not have exactly one field
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Write)]
+struct Tee<W> {
+//~^ ERROR `Write` needs a field marked `#[derivative(Write="target")]` on a structure that does not have exactly one field
+    first: W,
+    second: W,
+}
+
+fn main() {}
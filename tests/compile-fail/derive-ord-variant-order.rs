@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative, PartialEq, Eq, PartialOrd)]
+#[derivative(Ord = "feature_allow_slow_enum")]
+enum Missing {
+    #[derivative(Ord(order = "1"))]
+    A,
+    B,
+    //~^ ERROR `Ord(order)` must be given to all the variants or none, this variant has none
+}
+
+#[derive(Derivative, PartialEq)]
+#[derivative(PartialOrd = "feature_allow_slow_enum")]
+enum Duplicate {
+    #[derivative(PartialOrd(order = "1"))]
+    A,
+    #[derivative(PartialOrd(order = "1"))]
+    B,
+    //~^ ERROR another variant has `PartialOrd(order="1")`, different variants must not be equal
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(UpperHex)]
+enum Color {
+//~^ ERROR `UpperHex` can only be derived on structures with exactly one field
+    Red,
+}
+
+fn main() {}
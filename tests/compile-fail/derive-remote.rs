@@ -0,0 +1,17 @@
+#[macro_use]
+extern crate derivative;
+
+mod other {
+    pub struct Duration {
+        pub secs: u64,
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, remote = "other::Duration")]
+struct Duration {
+//~^ ERROR `Clone` can't be derived with `remote`, only `Debug`, `Hash`, `PartialEq`, `PartialOrd` and `Ord` can
+    secs: u64,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(TryFrom(error = "()"))]
+struct Unchecked(u8);
+//~^ ERROR `TryFrom` on a structure needs `TryFrom(validate_with="…")`
+
+#[derive(Derivative)]
+#[derivative(From, TryFrom(error = "()", validate_with = "check"))]
+struct Both(u8);
+//~^ ERROR `TryFrom` can't be derived on a structure that also derives `From`
+
+#[derive(Derivative)]
+#[derivative(TryFrom)]
+enum WithFields {
+    A,
+    B(u8),
+    //~^ ERROR `TryFrom` can only be derived on enumerations whose variants have no fields
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(VariantNames)]
+struct Point(u8, u8); //~ ERROR `VariantNames` can only be derived on enumerations
+
+fn main() {}
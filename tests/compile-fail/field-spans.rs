@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate derivative;
+
+struct NoTraits;
+
+#[derive(Derivative)]
+#[derivative(Debug, Clone, Default)]
+struct Foo {
+    foo: u8,
+    bar: NoTraits,
+    //~^ ERROR doesn't implement `Debug`
+    //~| ERROR the trait bound `NoTraits: Clone` is not satisfied
+    //~| ERROR the trait bound `NoTraits: Default` is not satisfied
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Foo {
+    #[derivative(Debug=ignore)]
+    //~^ ERROR invalid `derivative` attribute
+    foo: u8,
+}
+
+fn main() {}
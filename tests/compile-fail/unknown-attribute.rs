@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug(foo="bar"))]
+//~^ ERROR unknown attribute `foo` [D0002]
+struct Foo;
+
+fn main() {}
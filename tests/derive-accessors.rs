@@ -0,0 +1,67 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Accessors)]
+enum Shape<T> {
+    Point,
+    Circle(T),
+    Rectangle {
+        width: T,
+        height: T,
+    },
+    #[derivative(Accessors(rename = "poly"))]
+    RegularPolygon(u8, T),
+    #[derivative(Accessors = "skip")]
+    #[allow(dead_code)]
+    Unknown(String),
+}
+
+#[derive(Derivative)]
+#[derivative(Accessors)]
+enum Wrapper<'a> {
+    Text(&'a str),
+}
+
+#[test]
+fn main() {
+    let mut circle = Shape::Circle(2);
+    assert!(circle.is_circle());
+    assert!(!circle.is_point());
+    assert_eq!(circle.as_circle(), Some(&2));
+    assert_eq!(circle.as_rectangle(), None);
+    if let Some(radius) = circle.as_circle_mut() {
+        *radius = 3;
+    }
+    assert_eq!(circle.into_circle(), Ok(3));
+
+    let rectangle = Shape::Rectangle {
+        width: 1,
+        height: 2,
+    };
+    assert_eq!(rectangle.as_rectangle(), Some((&1, &2)));
+    assert_eq!(
+        rectangle.into_circle(),
+        Err(Shape::Rectangle {
+            width: 1,
+            height: 2
+        })
+    );
+
+    let mut polygon = Shape::RegularPolygon(6, 1.5);
+    assert!(polygon.is_poly());
+    if let Some((sides, _)) = polygon.as_poly_mut() {
+        *sides = 8;
+    }
+    assert_eq!(polygon.into_poly(), Ok((8, 1.5)));
+
+    assert!(Shape::Point::<u8>.is_point());
+
+    let wrapper = Wrapper::Text("hello");
+    assert!(wrapper.is_text());
+    assert_eq!(wrapper.as_text(), Some(&"hello"));
+    assert_eq!(wrapper.into_text().ok(), Some("hello"));
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "approx")]
+
+#[macro_use]
+extern crate approx;
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative)]
+#[derivative(AbsDiffEq, RelativeEq, PartialEq)]
+struct Measurement {
+    value: f64,
+    #[derivative(AbsDiffEq(epsilon = "0.5"))]
+    temperature: f64,
+    #[derivative(PartialEq = "ignore")]
+    label: &'static str,
+    #[derivative(PartialEq(compare_with = "same_len"))]
+    samples: Vec<u8>,
+    #[derivative(
+        AbsDiffEq(compare_with = "abs_diff_eq_pair"),
+        RelativeEq(compare_with = "relative_eq_pair")
+    )]
+    pair: (f64, f64),
+}
+
+fn same_len(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len()
+}
+
+fn abs_diff_eq_pair(a: &(f64, f64), b: &(f64, f64), epsilon: f64) -> bool {
+    abs_diff_eq!(a.0, b.0, epsilon = epsilon) && abs_diff_eq!(a.1, b.1, epsilon = epsilon)
+}
+
+fn relative_eq_pair(a: &(f64, f64), b: &(f64, f64), epsilon: f64, max_relative: f64) -> bool {
+    relative_eq!(a.0, b.0, epsilon = epsilon, max_relative = max_relative)
+        && relative_eq!(a.1, b.1, epsilon = epsilon, max_relative = max_relative)
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(AbsDiffEq)]
+enum Shape<T> {
+    Circle(T),
+    Rect { width: T, height: T },
+    Empty,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(AbsDiffEq(epsilon_type = "f32"), RelativeEq)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+fn measurement(value: f64, temperature: f64, label: &'static str) -> Measurement {
+    Measurement {
+        value,
+        temperature,
+        label,
+        samples: vec![1, 2, 3],
+        pair: (value, value),
+    }
+}
+
+#[test]
+fn main() {
+    let a = measurement(1.0, 20.0, "a");
+    assert_eq!(a.label, "a");
+
+    assert_abs_diff_eq!(a, measurement(1.0, 20.4, "b"));
+    assert_abs_diff_ne!(a, measurement(1.0, 20.6, "a"));
+    assert_abs_diff_eq!(a, measurement(1.05, 20.0, "a"), epsilon = 0.1);
+    assert_abs_diff_ne!(a, measurement(1.05, 20.0, "a"));
+    assert_abs_diff_ne!(
+        a,
+        Measurement {
+            samples: vec![],
+            ..measurement(1.0, 20.0, "a")
+        }
+    );
+
+    assert_relative_eq!(a, measurement(1.0 + f64::EPSILON, 20.0, "a"));
+    assert_relative_ne!(a, measurement(1.1, 20.0, "a"));
+    assert_relative_eq!(a, measurement(1.1, 20.0, "a"), max_relative = 0.2);
+
+    assert_abs_diff_eq!(Shape::Circle(1.0f32), Shape::Circle(1.0f32 + f32::EPSILON / 2.0));
+    assert_abs_diff_ne!(Shape::Circle(1.0f32), Shape::Circle(1.1f32));
+    assert_abs_diff_ne!(
+        Shape::Circle(1.0f32),
+        Shape::Rect {
+            width: 1.0,
+            height: 1.0
+        }
+    );
+    assert_abs_diff_eq!(
+        Shape::Rect {
+            width: 1.0,
+            height: 2.0
+        },
+        Shape::Rect {
+            width: 1.05,
+            height: 1.95
+        },
+        epsilon = 0.1
+    );
+    assert_abs_diff_eq!(Shape::<f64>::Empty, Shape::Empty);
+
+    let point = Point { x: 1.0f32, y: 2.0 };
+    assert_relative_eq!(point, Point { x: 1.0, y: 2.0 });
+    assert_relative_ne!(point, Point { x: 1.0, y: 2.1 });
+    assert_relative_eq!(point, Point { x: 1.0, y: 2.1 }, max_relative = 0.1);
+}
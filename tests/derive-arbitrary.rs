@@ -0,0 +1,93 @@
+#![cfg(all(feature = "proptest", not(feature = "use_core")))]
+
+#[macro_use]
+extern crate derivative;
+extern crate proptest;
+
+use proptest::arbitrary::any;
+use proptest::strategy::{Just, Strategy};
+use proptest::test_runner::TestRunner;
+
+#[derive(Debug, Derivative)]
+#[derivative(Arbitrary)]
+struct Point {
+    x: i8,
+    y: i8,
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Arbitrary)]
+struct User {
+    #[derivative(Arbitrary(strategy_with = "name_strategy"))]
+    name: String,
+    age: u8,
+    #[derivative(Arbitrary = "ignore")]
+    sessions: Vec<u32>,
+}
+
+fn name_strategy() -> impl Strategy<Value = String> {
+    "[a-z]{1,8}"
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Arbitrary)]
+enum Shape<T> {
+    Point(T),
+    Circle {
+        #[derivative(Arbitrary(strategy_with = "radius_strategy"))]
+        radius: u32,
+        center: T,
+    },
+    Empty,
+}
+
+fn radius_strategy() -> Just<u32> {
+    Just(7)
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Arbitrary)]
+struct Wide(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+
+#[test]
+fn main() {
+    let mut runner = TestRunner::default();
+
+    runner
+        .run(&any::<Point>(), |point| {
+            let _ = (point.x, point.y);
+            Ok(())
+        })
+        .unwrap();
+
+    runner
+        .run(&any::<User>(), |user| {
+            assert!(!user.name.is_empty() && user.name.len() <= 8);
+            assert!(user.name.chars().all(|c| c.is_ascii_lowercase()));
+            assert!(user.sessions.is_empty());
+            let _ = user.age;
+            Ok(())
+        })
+        .unwrap();
+
+    runner
+        .run(&any::<Shape<bool>>(), |shape| {
+            match shape {
+                Shape::Point(_) | Shape::Empty => (),
+                Shape::Circle { radius, center } => {
+                    assert_eq!(radius, 7);
+                    let _ = center;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+
+    runner
+        .run(&any::<Wide>(), |wide| {
+            let Wide(a, b, c, d, e, f, g, h, i, j, k, l, m, n) = wide;
+            let _ = [a, b, c, d, e, f, g, h, i, j, k, l, m, n];
+            Ok(())
+        })
+        .unwrap();
+}
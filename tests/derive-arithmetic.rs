@@ -0,0 +1,85 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Add, Sub, Mul, Div)]
+struct Vector<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Add, Sub)]
+struct Stats {
+    strength: u32,
+    #[derivative(Add(with = "max"), Sub = "ignore")]
+    level: u32,
+    #[derivative(Add = "ignore", Sub = "ignore")]
+    name: &'static str,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Add(bound = "T: Copy + std::ops::Add<Output = T>"))]
+struct Meters<T>(T, PhantomData<T>);
+
+fn max(a: u32, b: u32) -> u32 {
+    std::cmp::max(a, b)
+}
+
+#[test]
+fn main() {
+    let a = Vector { x: 6, y: 8 };
+    let b = Vector { x: 2, y: 4 };
+    assert_eq!(a + Vector { x: 1, y: 1 }, Vector { x: 7, y: 9 });
+    assert_eq!(Vector { x: 6, y: 8 } - Vector { x: 2, y: 4 }, Vector { x: 4, y: 4 });
+    assert_eq!(Vector { x: 6, y: 8 } * b, Vector { x: 12, y: 32 });
+    assert_eq!(Vector { x: 6.0, y: 8.0 } / Vector { x: 2.0, y: 4.0 }, Vector { x: 3.0, y: 2.0 });
+
+    let base = Stats {
+        strength: 10,
+        level: 2,
+        name: "base",
+    };
+    let bonus = Stats {
+        strength: 5,
+        level: 3,
+        name: "bonus",
+    };
+    assert_eq!(
+        base + bonus,
+        Stats {
+            strength: 15,
+            level: 3,
+            name: "base",
+        }
+    );
+
+    let penalty = Stats {
+        strength: 4,
+        level: 1,
+        name: "penalty",
+    };
+    let hero = Stats {
+        strength: 10,
+        level: 2,
+        name: "hero",
+    };
+    assert_eq!(
+        hero - penalty,
+        Stats {
+            strength: 6,
+            level: 2,
+            name: "hero",
+        }
+    );
+
+    assert_eq!(
+        Meters(1.5, PhantomData) + Meters(2.0, PhantomData),
+        Meters(3.5, PhantomData)
+    );
+}
@@ -0,0 +1,42 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt::Display;
+
+#[derive(Derivative)]
+#[derivative(AsRef, AsMut)]
+struct Name(String);
+
+#[derive(Derivative)]
+#[derivative(AsRef, AsMut)]
+struct Request<B> {
+    #[derivative(AsRef, AsMut)]
+    headers: Vec<(String, String)>,
+    #[derivative(AsRef(bound = "B: Display"))]
+    body: Box<B>,
+    id: u64,
+}
+
+fn header_count<T: AsRef<Vec<(String, String)>>>(value: &T) -> usize {
+    value.as_ref().len()
+}
+
+#[test]
+fn main() {
+    let mut name = Name("foo".to_string());
+    AsMut::<String>::as_mut(&mut name).push_str("bar");
+    assert_eq!(AsRef::<String>::as_ref(&name), "foobar");
+
+    let mut request = Request {
+        headers: vec![],
+        body: Box::new(42u8),
+        id: 1,
+    };
+    AsMut::<Vec<(String, String)>>::as_mut(&mut request).push(("a".into(), "b".into()));
+    assert_eq!(header_count(&request), 1);
+    assert_eq!(**AsRef::<Box<u8>>::as_ref(&request), 42);
+    assert_eq!(request.id, 1);
+}
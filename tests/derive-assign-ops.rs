@@ -0,0 +1,75 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Add, AddAssign, SubAssign, MulAssign, DivAssign)]
+struct Vector<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(AddAssign, SubAssign)]
+struct Stats {
+    strength: u32,
+    #[derivative(AddAssign(with = "keep_max"), SubAssign = "ignore")]
+    level: u32,
+    #[derivative(AddAssign = "ignore", SubAssign = "ignore")]
+    name: &'static str,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(MulAssign(bound = "T: std::ops::MulAssign"))]
+struct Scale<T>(T, PhantomData<T>);
+
+fn keep_max(a: &mut u32, b: u32) {
+    *a = std::cmp::max(*a, b);
+}
+
+#[test]
+fn main() {
+    let mut v = Vector { x: 6, y: 8 };
+    v += Vector { x: 2, y: 4 };
+    assert_eq!(v, Vector { x: 8, y: 12 });
+    v -= Vector { x: 1, y: 1 };
+    assert_eq!(v, Vector { x: 7, y: 11 });
+    v *= Vector { x: 2, y: 3 };
+    assert_eq!(v, Vector { x: 14, y: 33 });
+    v /= Vector { x: 7, y: 11 };
+    assert_eq!(v, Vector { x: 2, y: 3 });
+    // The arithmetic and compound assignment operators agree.
+    assert_eq!(v + Vector { x: 1, y: 1 }, Vector { x: 3, y: 4 });
+
+    let mut hero = Stats {
+        strength: 10,
+        level: 2,
+        name: "hero",
+    };
+    hero += Stats {
+        strength: 5,
+        level: 3,
+        name: "bonus",
+    };
+    hero -= Stats {
+        strength: 1,
+        level: 1,
+        name: "penalty",
+    };
+    assert_eq!(
+        hero,
+        Stats {
+            strength: 14,
+            level: 3,
+            name: "hero",
+        }
+    );
+
+    let mut scale = Scale(1.5, PhantomData);
+    scale *= Scale(2.0, PhantomData);
+    assert_eq!(scale, Scale(3.0, PhantomData));
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code, non_camel_case_types)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// What bindgen generates for a C structure with a bitfield and an anonymous union.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct __BindgenBitfieldUnit<Storage> {
+    storage: Storage,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union packet__bindgen_ty_1 {
+    pub word: u32,
+    pub bytes: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Derivative)]
+#[derivative(Debug, Hash, PartialEq, bindgen="skip")]
+pub struct packet {
+    pub id: u16,
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+    pub __bindgen_anon_1: packet__bindgen_ty_1,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Derivative)]
+#[derivative(Debug, Hash, PartialEq, bindgen="opaque")]
+pub struct opaque_packet {
+    pub id: u16,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+    pub __bindgen_anon_1: packet__bindgen_ty_1,
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    let unit = |storage| __BindgenBitfieldUnit { storage };
+    let anon = |word| packet__bindgen_ty_1 { word };
+
+    let a = packet { id: 1, _bitfield_align_1: [], _bitfield_1: unit([1, 2]), __bindgen_anon_1: anon(3) };
+    let b = packet { id: 1, _bitfield_align_1: [], _bitfield_1: unit([4, 5]), __bindgen_anon_1: anon(6) };
+    assert_eq!(
+        format!("{:?}", a),
+        "packet { id: 1, _bitfield_align_1: [], _bitfield_1: .., __bindgen_anon_1: .. }"
+    );
+    assert!(a == b);
+    assert_eq!(hash(&a), hash(&b));
+
+    let a = opaque_packet { id: 1, _bitfield_1: unit([1, 2]), __bindgen_anon_1: anon(0x0403_0201) };
+    let b = opaque_packet { id: 1, _bitfield_1: unit([1, 2]), __bindgen_anon_1: anon(0x0403_0201) };
+    let c = opaque_packet { id: 1, _bitfield_1: unit([1, 3]), __bindgen_anon_1: anon(0x0403_0201) };
+    if cfg!(target_endian = "little") {
+        assert_eq!(
+            format!("{:?}", a),
+            "opaque_packet { id: 1, _bitfield_1: [1, 2], __bindgen_anon_1: [1, 2, 3, 4] }"
+        );
+    }
+    assert!(a == b);
+    assert!(a != c);
+    assert_eq!(hash(&a), hash(&b));
+}
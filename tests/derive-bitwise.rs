@@ -0,0 +1,78 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Clone, Copy, Debug, PartialEq, Derivative)]
+#[derivative(BitAnd, BitOr, BitXor, Not)]
+struct Permissions(u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Derivative)]
+#[derivative(BitAnd, BitOr(bound = "T: std::ops::BitOr<Output = T> + Copy"), Shl, Shr)]
+struct Masks<T> {
+    read: T,
+    write: T,
+    #[derivative(BitAnd = "ignore", BitOr = "ignore", Shl = "ignore", Shr = "ignore")]
+    owner: u32,
+}
+
+#[test]
+fn main() {
+    let read = Permissions(0b100);
+    let write = Permissions(0b010);
+    let all = Permissions(0b111);
+    assert_eq!(read | write, Permissions(0b110));
+    assert_eq!(all & read, read);
+    assert_eq!(all ^ read, Permissions(0b011));
+    assert_eq!(!all & all, Permissions(0));
+
+    let masks = Masks {
+        read: 0b1100u8,
+        write: 0b0110,
+        owner: 1,
+    };
+    let other = Masks {
+        read: 0b1010,
+        write: 0b0011,
+        owner: 2,
+    };
+    assert_eq!(
+        masks & other,
+        Masks {
+            read: 0b1000,
+            write: 0b0010,
+            owner: 1,
+        }
+    );
+    assert_eq!(
+        masks | other,
+        Masks {
+            read: 0b1110,
+            write: 0b0111,
+            owner: 1,
+        }
+    );
+
+    let shifts = Masks {
+        read: 1,
+        write: 2,
+        owner: 3,
+    };
+    assert_eq!(
+        masks << shifts,
+        Masks {
+            read: 0b11000,
+            write: 0b11000,
+            owner: 1,
+        }
+    );
+    assert_eq!(
+        masks >> shifts,
+        Masks {
+            read: 0b110,
+            write: 0b1,
+            owner: 1,
+        }
+    );
+}
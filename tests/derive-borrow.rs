@@ -0,0 +1,47 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::borrow::BorrowMut;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Derivative)]
+#[derivative(Borrow, Hash, PartialEq, Eq)]
+struct UserId(u64);
+
+#[derive(Derivative)]
+#[derivative(Borrow, BorrowMut, PartialEq, Eq, PartialOrd, Ord)]
+struct Name {
+    value: String,
+}
+
+#[test]
+fn main() {
+    let mut ages = HashMap::new();
+    ages.insert(UserId(1), 30);
+    ages.insert(UserId(2), 40);
+    assert_eq!(ages.get(&1), Some(&30));
+    assert_eq!(ages.get(&2), Some(&40));
+    assert_eq!(ages.get(&3), None);
+
+    let mut names = BTreeSet::new();
+    names.insert(Name {
+        value: "b".to_string(),
+    });
+    names.insert(Name {
+        value: "a".to_string(),
+    });
+    let (a, c) = ("a".to_string(), "c".to_string());
+    assert!(names.contains(&a));
+    assert!(!names.contains(&c));
+
+    let mut name = Name {
+        value: "a".to_string(),
+    };
+    BorrowMut::<String>::borrow_mut(&mut name).push('b');
+    assert_eq!(name.value, "ab");
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+trait Handle {
+    type Target: Clone + Debug + Hash + PartialEq;
+
+    fn get(&self) -> Self::Target;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Shared;
+
+impl Handle for Shared {
+    type Target = u8;
+
+    fn get(&self) -> u8 {
+        42
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Hash, PartialEq)]
+struct Entry<H: Handle> {
+    #[derivative(bound_all="H::Target: Clone + Debug + Hash + PartialEq")]
+    value: H::Target,
+    #[derivative(
+        bound_all="",
+        Clone(clone_with="Rc::clone"),
+        Debug="ignore",
+        Hash="ignore",
+        PartialEq="ignore"
+    )]
+    handle: Rc<H>,
+}
+
+struct NoTraits;
+
+impl Handle for NoTraits {
+    type Target = &'static str;
+
+    fn get(&self) -> &'static str {
+        "foo"
+    }
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    let entry = Entry {
+        value: Shared.get(),
+        handle: Rc::new(Shared),
+    };
+    assert_eq!(format!("{:?}", entry.clone()), "Entry { value: 42 }");
+    assert!(entry == entry.clone());
+
+    let entry = Entry {
+        value: NoTraits.get(),
+        handle: Rc::new(NoTraits),
+    };
+    assert_eq!(format!("{:?}", entry.clone()), "Entry { value: \"foo\" }");
+    assert_eq!(hash(&entry), hash(&entry.clone()));
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Builder)]
+struct Request {
+    #[derivative(Default(required))]
+    url: String,
+    #[derivative(Default(value = "\"GET\""))]
+    method: &'static str,
+    #[derivative(Default(value = "format!(\"{} {}\", method, url)"))]
+    line: String,
+    headers: Vec<(String, String)>,
+    #[derivative(Builder = "ignore", Default(value = "1"))]
+    version: u8,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Builder, Default)]
+struct Options<T> {
+    value: T,
+    #[derivative(Default(value = "8"))]
+    capacity: usize,
+    #[derivative(Builder(ignore))]
+    cache: Vec<T>,
+}
+
+#[derive(Derivative)]
+#[derivative(Builder)]
+struct Handler<'a> {
+    name: &'a str,
+    callback: fn(u8) -> u8,
+}
+
+fn double(x: u8) -> u8 {
+    x * 2
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Request::builder("/".to_string()).build(),
+        Request {
+            url: "/".to_string(),
+            method: "GET",
+            line: "GET /".to_string(),
+            headers: Vec::new(),
+            version: 1,
+        }
+    );
+
+    let request = Request::builder("/login".to_string())
+        .method("POST")
+        .headers(vec![("Accept".to_string(), "*/*".to_string())])
+        .build();
+    assert_eq!(request.line, "POST /login");
+    assert_eq!(request.headers.len(), 1);
+    assert_eq!(request.version, 1);
+
+    let request = Request::builder("/".to_string())
+        .line("HEAD /".to_string())
+        .build();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.line, "HEAD /");
+
+    assert_eq!(Options::<u8>::builder().build(), Options::default());
+    assert_eq!(
+        Options::builder().value(3).capacity(16).build(),
+        Options {
+            value: 3,
+            capacity: 16,
+            cache: Vec::new()
+        }
+    );
+
+    let handler = Handler::builder(double).name("double").build();
+    assert_eq!(handler.name, "double");
+    assert_eq!((handler.callback)(2), 4);
+}
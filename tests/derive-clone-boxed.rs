@@ -0,0 +1,48 @@
+#[macro_use]
+extern crate derivative;
+
+use std::fmt::Debug;
+
+trait Shape: Debug {
+    fn area(&self) -> f64;
+    fn box_clone(&self) -> Box<dyn Shape>;
+}
+
+#[derive(Clone, Debug)]
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn box_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone(clone_from))]
+struct Scene {
+    name: String,
+    #[derivative(Clone(boxed="Shape::box_clone"))]
+    shape: Box<dyn Shape>,
+}
+
+#[test]
+fn main() {
+    let scene = Scene {
+        name: "one".to_string(),
+        shape: Box::new(Square(2.0)),
+    };
+    let copy = scene.clone();
+    assert_eq!(copy.name, "one");
+    assert_eq!(copy.shape.area(), 4.0);
+
+    let mut target = Scene {
+        name: String::new(),
+        shape: Box::new(Square(1.0)),
+    };
+    target.clone_from(&scene);
+    assert_eq!(target.shape.area(), 4.0);
+}
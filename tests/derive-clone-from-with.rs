@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate derivative;
+
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, PartialEq)]
+struct Buffer(Vec<u8>);
+
+impl Buffer {
+    fn copy_into(target: &mut Buffer, source: &Buffer) {
+        target.0.clear();
+        target.0.extend_from_slice(&source.0);
+    }
+
+    fn fresh(source: &Buffer) -> Buffer {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        Buffer(source.0.clone())
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone)]
+struct Frame {
+    id: u32,
+    #[derivative(Clone(clone_with="Buffer::fresh", clone_from_with="Buffer::copy_into"))]
+    pixels: Buffer,
+    #[derivative(Clone(clone_with="Buffer::fresh"))]
+    header: Buffer,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone)]
+enum Slot {
+    Full(
+        #[derivative(Clone(clone_with="Buffer::fresh", clone_from_with="Buffer::copy_into"))]
+        Buffer,
+    ),
+    Empty,
+}
+
+#[test]
+fn main() {
+    let source = Frame {
+        id: 1,
+        pixels: Buffer(vec![1, 2, 3]),
+        header: Buffer(vec![4]),
+    };
+
+    let mut target = source.clone();
+    assert_eq!(ALLOCATIONS.with(Cell::get), 2);
+    target.pixels.0.reserve(100);
+    let capacity = target.pixels.0.capacity();
+
+    target.clone_from(&source);
+    assert_eq!(target, source);
+    assert_eq!(target.pixels.0.capacity(), capacity);
+    assert_eq!(ALLOCATIONS.with(Cell::get), 3);
+
+    let mut slot = Slot::Empty;
+    slot.clone_from(&Slot::Full(Buffer(vec![1])));
+    assert_eq!(slot, Slot::Full(Buffer(vec![1])));
+    assert_eq!(ALLOCATIONS.with(Cell::get), 4);
+    slot.clone_from(&Slot::Full(Buffer(vec![2])));
+    assert_eq!(slot, Slot::Full(Buffer(vec![2])));
+    assert_eq!(ALLOCATIONS.with(Cell::get), 4);
+}
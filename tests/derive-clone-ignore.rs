@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate derivative;
+
+use std::cell::Cell;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone, Default)]
+struct Query {
+    text: String,
+    #[derivative(Clone="ignore")]
+    cached: Cell<Option<usize>>,
+    #[derivative(Clone="ignore", Default(value="1"))]
+    generation: u32,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone(clone_from))]
+enum Counter<T> {
+    Idle,
+    Running(&'static str, #[derivative(Clone="ignore")] T),
+}
+
+// Not `Clone`: the ignored field does not need to be.
+#[derive(Debug, Default, PartialEq)]
+struct Stats(u32);
+
+// Derives `Copy`, but can't be cloned with `*self`.
+#[derive(Derivative)]
+#[derive(Copy, Debug, PartialEq)]
+#[derivative(Clone)]
+struct Point {
+    x: i32,
+    #[derivative(Clone="ignore")]
+    hits: u8,
+}
+
+#[test]
+fn main() {
+    let query = Query {
+        text: "select".to_string(),
+        cached: Cell::new(Some(3)),
+        generation: 5,
+    };
+    assert_eq!(
+        query.clone(),
+        Query {
+            text: "select".to_string(),
+            cached: Cell::new(None),
+            generation: 1,
+        }
+    );
+
+    let counter = Counter::Running("jobs", Stats(8));
+    assert_eq!(counter.clone(), Counter::Running("jobs", Stats(0)));
+
+    let mut target = Counter::Running("old", Stats(2));
+    target.clone_from(&counter);
+    assert_eq!(target, Counter::Running("jobs", Stats(0)));
+    let mut target = Counter::Idle;
+    target.clone_from(&counter);
+    assert_eq!(target, Counter::Running("jobs", Stats(0)));
+
+    let point = Point { x: 1, hits: 4 };
+    assert_eq!(point.clone(), Point { x: 1, hits: 0 });
+}
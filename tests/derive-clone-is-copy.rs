@@ -0,0 +1,81 @@
+#![allow(dead_code, clippy::clone_on_copy, clippy::missing_const_for_thread_local)]
+#![allow(clippy::non_canonical_clone_impl)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[macro_use]
+extern crate derivative;
+
+use std::cell::Cell;
+
+#[derive(Derivative)]
+#[derivative(Clone(is_copy))]
+#[derive(Copy)]
+struct Generic<T> {
+    foo: T,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+#[derive(Copy)]
+struct Detected {
+    foo: u8,
+}
+
+thread_local! {
+    static CLONES: Cell<usize> = Cell::new(0);
+}
+
+#[derive(Copy)]
+struct Counted;
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        CLONES.with(|clones| clones.set(clones.get() + 1));
+        Counted
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy)]
+struct Fast {
+    foo: Counted,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+#[derive(std::marker::Copy)]
+struct DetectedPath {
+    foo: Counted,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(is_copy="false"), Copy)]
+struct Slow {
+    foo: Counted,
+}
+
+// `Copy` is detected, but the field is still cloned with its function.
+#[derive(Derivative)]
+#[derivative(Clone)]
+#[derive(Copy)]
+struct WithCloneWith {
+    #[derivative(Clone(clone_with="increment"))]
+    a: u8,
+}
+
+fn increment(a: &u8) -> u8 {
+    a + 1
+}
+
+#[test]
+fn main() {
+    assert_eq!(Generic { foo: 42 }.clone().foo, 42);
+    assert_eq!(Detected { foo: 42 }.clone().foo, 42);
+    assert_eq!(WithCloneWith { a: 1 }.clone().a, 2);
+
+    let _ = Fast { foo: Counted }.clone();
+    let _ = DetectedPath { foo: Counted }.clone();
+    assert_eq!(CLONES.with(Cell::get), 0);
+    let _ = Slow { foo: Counted }.clone();
+    assert_eq!(CLONES.with(Cell::get), 1);
+}
@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate derivative;
+
+use std::cell::RefCell;
+use std::rc::{self, Rc};
+use std::sync::Arc;
+
+// Neither `Config` nor `Log` is `Clone`.
+#[derive(Debug, PartialEq)]
+struct Config {
+    verbose: bool,
+}
+
+#[derive(Debug)]
+struct Log(RefCell<Vec<&'static str>>);
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_from))]
+struct Context<C, L> {
+    name: String,
+    #[derivative(Clone="shared")]
+    config: Arc<C>,
+    #[derivative(Clone(shared))]
+    log: Rc<L>,
+    #[derivative(Clone(shared))]
+    parent: rc::Weak<L>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+enum Node<T> {
+    Leaf(#[derivative(Clone="shared")] Rc<T>),
+    Empty,
+}
+
+#[test]
+fn main() {
+    let log = Rc::new(Log(RefCell::new(vec![])));
+    let context = Context {
+        name: "main".to_string(),
+        config: Arc::new(Config { verbose: true }),
+        log: log.clone(),
+        parent: Rc::downgrade(&log),
+    };
+
+    let copy = context.clone();
+    assert_eq!(copy.name, "main");
+    assert!(Arc::ptr_eq(&copy.config, &context.config));
+    assert!(Rc::ptr_eq(&copy.log, &log));
+    assert_eq!(Rc::strong_count(&log), 3);
+    assert_eq!(Rc::weak_count(&log), 2);
+    copy.log.0.borrow_mut().push("cloned");
+    assert_eq!(*log.0.borrow(), vec!["cloned"]);
+
+    let mut target = Context {
+        name: String::new(),
+        config: Arc::new(Config { verbose: false }),
+        log: Rc::new(Log(RefCell::new(vec![]))),
+        parent: rc::Weak::new(),
+    };
+    target.clone_from(&context);
+    assert_eq!(*target.config, Config { verbose: true });
+    assert!(target.parent.upgrade().is_some());
+
+    let leaf = Rc::new(Log(RefCell::new(vec![])));
+    let node = Node::Leaf(leaf.clone());
+    let _copy = node.clone();
+    assert_eq!(Rc::strong_count(&leaf), 3);
+    assert!(matches!(Node::<Log>::Empty.clone(), Node::Empty));
+}
@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate derivative;
+
+use std::cell::Cell;
+
+thread_local! {
+    static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone(clone_with="Pool::checked_clone"))]
+struct Pool<T> {
+    items: Vec<T>,
+    generation: u32,
+}
+
+impl<T: Clone> Pool<T> {
+    fn checked_clone(&self) -> Self {
+        CLONES.with(|c| c.set(c.get() + 1));
+        Pool {
+            items: self.items.clone(),
+            generation: self.generation + 1,
+        }
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Clone(clone_with="Slot::reset", clone_from))]
+enum Slot {
+    Full(Vec<u8>),
+    Empty,
+}
+
+impl Slot {
+    fn reset(&self) -> Self {
+        Slot::Empty
+    }
+}
+
+#[test]
+fn main() {
+    let pool = Pool {
+        items: vec![1, 2],
+        generation: 0,
+    };
+    let copy = pool.clone();
+    assert_eq!(copy.items, vec![1, 2]);
+    assert_eq!(copy.generation, 1);
+    assert_eq!(CLONES.with(Cell::get), 1);
+
+    let mut target = Pool {
+        items: vec![],
+        generation: 7,
+    };
+    target.clone_from(&copy);
+    assert_eq!(target.generation, 2);
+    assert_eq!(CLONES.with(Cell::get), 2);
+
+    assert_eq!(Slot::Full(vec![1]).clone(), Slot::Empty);
+
+    // The generated `clone_from` clones the fields of the same variant, and falls back to `clone`.
+    let mut slot = Slot::Full(vec![1]);
+    slot.clone_from(&Slot::Full(vec![2]));
+    assert_eq!(slot, Slot::Full(vec![2]));
+    slot.clone_from(&Slot::Empty);
+    assert_eq!(slot, Slot::Empty);
+    let mut slot = Slot::Empty;
+    slot.clone_from(&Slot::Full(vec![3]));
+    assert_eq!(slot, Slot::Empty);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "syn-1")]
+#![allow(clippy::clone_on_copy)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(clone_from),
+    Debug,
+    Default(new, reset),
+    Eq,
+    Hash(content_hash),
+    PartialEq
+)]
+struct Foo<T = u8, const N: usize = 3> {
+    #[derivative(Debug(max_len="2"), Default(value="[0; N]"))]
+    items: [u8; N],
+    len: T,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Default, PartialEq="feature_allow_slow_enum")]
+enum Bar<const N: usize = 2> {
+    #[derivative(Default)]
+    A,
+    B([u8; N]),
+}
+
+#[test]
+fn main() {
+    let mut foo: Foo = Foo::new();
+    assert_eq!(format!("{:?}", foo), "Foo { items: [0, 0, … (1 more)], len: 0 }");
+    foo.len = 42;
+    assert!(foo.clone() == foo);
+    let _ = foo.content_hash();
+    foo.reset();
+    assert_eq!(foo.len, 0);
+
+    let bar: Bar = Bar::B([4, 2]);
+    assert_eq!(format!("{:?}", bar), "B([4, 2])");
+    assert!(bar == bar.clone());
+    assert!(Bar::<2>::default() == Bar::A);
+}
@@ -0,0 +1,52 @@
+#![cfg(all(feature = "const_impls", feature = "test-nightly"))]
+#![feature(const_trait_impl, const_clone, const_cmp)]
+#![deny(warnings)]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone="const", PartialEq="const")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone="const", PartialEq="const")]
+struct Wrapper<T>(T);
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone="const", PartialEq(const, feature_allow_slow_enum))]
+enum Shape {
+    Dot,
+    Line(Point, Point),
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Clone="const", PartialEq="const")]
+struct Legacy {
+    id: u8,
+    #[deprecated(note = "use `id`")]
+    code: u8,
+}
+
+const ORIGIN: Point = Point { x: 0, y: 0 };
+const CLONED: Point = ORIGIN.clone();
+const SAME: bool = CLONED.eq(&ORIGIN);
+const DIFFERENT: bool = Wrapper(1u8).eq(&Wrapper(2u8));
+const LINE: Shape = Shape::Line(Point { x: 1, y: 2 }, ORIGIN).clone();
+const IS_DOT: bool = LINE.eq(&Shape::Dot);
+#[allow(deprecated)]
+const LEGACY: Legacy = Legacy { id: 1, code: 2 };
+const LEGACY_SAME: bool = LEGACY.clone().eq(&LEGACY);
+
+#[test]
+fn main() {
+    assert!(SAME);
+    assert!(!DIFFERENT);
+    assert!(!IS_DOT);
+    assert!(LEGACY_SAME);
+    assert_eq!(Wrapper(3).clone(), Wrapper(3));
+    assert_eq!(LINE, Shape::Line(Point { x: 1, y: 2 }, Point { x: 0, y: 0 }));
+}
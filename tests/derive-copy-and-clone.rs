@@ -0,0 +1,47 @@
+#![allow(clippy::clone_on_copy)]
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Copy(and_clone))]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Copy="and_clone")]
+enum Cell<T> {
+    Empty,
+    Value(T),
+}
+
+// `Tag` is neither `Clone` nor `Copy`, the bounds of both traits are given.
+#[derive(Debug, Derivative)]
+#[derivative(Copy(and_clone, bound=""))]
+struct Id<Tag>(u32, PhantomData<fn() -> Tag>);
+
+#[derive(Debug)]
+struct Tag;
+
+fn is_copy<T: Copy>(value: &T) -> T {
+    *value
+}
+
+#[test]
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(point.clone(), point);
+    assert_eq!(is_copy(&point), point);
+
+    let cell = Cell::Value(3u8);
+    assert_eq!(cell.clone(), Cell::Value(3));
+    assert_eq!(is_copy(&Cell::<u8>::Empty), Cell::Empty);
+
+    let id = Id::<Tag>(7, PhantomData);
+    assert_eq!(id.clone().0, 7);
+    assert_eq!(is_copy(&id).0, 7);
+}
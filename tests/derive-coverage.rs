@@ -0,0 +1,19 @@
+#![cfg(feature = "coverage")]
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Default="new", Hash, PartialEq, Eq)]
+struct Foo {
+    foo: u8,
+    #[derivative(Debug="ignore")]
+    bar: u8,
+}
+
+#[test]
+fn main() {
+    assert_eq!(format!("{:?}", Foo::new().clone()), "Foo { foo: 0 }");
+    assert!(Foo::new() == Foo::default());
+}
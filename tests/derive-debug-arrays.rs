@@ -0,0 +1,56 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Block {
+    index: u8,
+    data: [u8; 40],
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Window<'a, T: 'a>(&'a [T; 3], [Option<T>; 33]);
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Grid([[u8; 2]; 2]);
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let block = Block {
+        index: 1,
+        data: [7; 40],
+    };
+    assert_eq!(
+        block.to_show(),
+        format!("Block {{ index: 1, data: {:?} }}", vec![7u8; 40])
+    );
+
+    let window = Window(&[1, 2, 3], [None; 33]);
+    assert_eq!(
+        window.to_show(),
+        format!("Window([1, 2, 3], {:?})", vec![None::<i32>; 33])
+    );
+
+    assert_eq!(Grid([[1, 2], [3, 4]]).to_show(), "[[1, 2], [3, 4]]");
+    assert_eq!(
+        format!("{:#?}", Grid([[1, 2], [3, 4]])),
+        "[\n    [\n        1,\n        2,\n    ],\n    [\n        3,\n        4,\n    ],\n]"
+    );
+}
@@ -0,0 +1,58 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct Graph;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Node {
+    id: u32,
+    #[derivative(Debug="as_pointer")]
+    graph: Rc<Graph>,
+    #[derivative(Debug(as_pointer))]
+    shared: Arc<Vec<u8>>,
+    #[derivative(Debug(as_pointer))]
+    parent: Box<u32>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Handle<'a>(#[derivative(Debug(as_pointer))] &'a Graph);
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let graph = Rc::new(Graph);
+    let shared = Arc::new(vec![1, 2, 3]);
+    let node = Node {
+        id: 1,
+        graph: graph.clone(),
+        shared: shared.clone(),
+        parent: Box::new(0),
+    };
+    assert_eq!(
+        node.to_show(),
+        format!(
+            "Node {{ id: 1, graph: {:p} (strong: 2), shared: {:p} (strong: 2), parent: {:p} }}",
+            graph, shared, node.parent
+        )
+    );
+
+    let graph = Graph;
+    assert_eq!(Handle(&graph).to_show(), format!("{:p}", &graph));
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_lifetimes)]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
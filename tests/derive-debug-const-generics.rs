@@ -0,0 +1,42 @@
+#![cfg(feature = "syn-1")]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Ring<T, const N: usize> {
+    items: [T; N],
+    #[derivative(Debug(max_len="2"))]
+    history: [u8; N],
+    padding: [u8; 40],
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let ring = Ring {
+        items: ["a", "b", "c"],
+        history: [1, 2, 3],
+        padding: [0; 40],
+    };
+    assert_eq!(
+        ring.to_show(),
+        format!(
+            "Ring {{ items: [\"a\", \"b\", \"c\"], history: [1, 2, … (1 more)], padding: {:?} }}",
+            vec![0u8; 40]
+        )
+    );
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+struct NoDebug;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Cached<T> {
+    #[derivative(Debug(delegate))]
+    value: u32,
+    cache: T,
+    hits: usize,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Token {
+    Number(#[derivative(Debug(delegate))] i64, usize),
+    Word {
+        #[derivative(Debug(delegate, format="{:?}!"))]
+        text: &'static str,
+        offset: usize,
+    },
+    End,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let cached = Cached {
+        value: 42,
+        cache: NoDebug,
+        hits: 3,
+    };
+    assert_eq!(cached.to_show(), "42");
+    assert_eq!(format!("{:5?}", cached), "   42");
+
+    assert_eq!(Token::Number(-1, 0).to_show(), "-1");
+    assert_eq!(
+        Token::Word {
+            text: "let",
+            offset: 2,
+        }
+        .to_show(),
+        "\"let\"!"
+    );
+    assert_eq!(Token::End.to_show(), "End");
+}
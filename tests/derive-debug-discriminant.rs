@@ -0,0 +1,41 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Clone, Copy, Derivative)]
+#[derivative(Debug(with_discriminant))]
+#[repr(u8)]
+enum State {
+    Idle,
+    Ready = 3,
+    Done,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="with_discriminant")]
+enum Signed {
+    Negative = -1,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(State::Idle.to_show(), "Idle(=0)");
+    assert_eq!(State::Ready.to_show(), "Ready(=3)");
+    assert_eq!(State::Done.to_show(), "Done(=4)");
+    assert_eq!(Signed::Negative.to_show(), "Negative(=-1)");
+    assert_eq!(format!("{:?}", Some(State::Ready)), "Some(Ready(=3))");
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Request {
+    method: &'static str,
+    #[derivative(Debug(rename="url"))]
+    path: &'static str,
+    #[derivative(Debug="ignore")]
+    #[allow(dead_code)]
+    body: Vec<u8>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="flattenable")]
+struct Timed {
+    #[derivative(Debug(flatten))]
+    inner: Request,
+    elapsed_ms: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Log {
+    id: u32,
+    #[derivative(Debug(flatten))]
+    request: Timed,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Event {
+    Received {
+        #[derivative(Debug(flatten))]
+        request: Box<Request>,
+    },
+    Dropped(&'static str),
+}
+
+fn request() -> Request {
+    Request {
+        method: "GET",
+        path: "/",
+        body: Vec::new(),
+    }
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(request().to_show(), "Request { method: \"GET\", url: \"/\" }");
+
+    let log = Log {
+        id: 1,
+        request: Timed {
+            inner: request(),
+            elapsed_ms: 3,
+        },
+    };
+    assert_eq!(
+        log.to_show(),
+        "Log { id: 1, method: \"GET\", url: \"/\", elapsed_ms: 3 }"
+    );
+    assert_eq!(
+        format!("{:#?}", log),
+        "Log {\n    id: 1,\n    method: \"GET\",\n    url: \"/\",\n    elapsed_ms: 3,\n}"
+    );
+
+    let received = Event::Received {
+        request: Box::new(request()),
+    };
+    assert_eq!(received.to_show(), "Received { method: \"GET\", url: \"/\" }");
+    assert_eq!(Event::Dropped("full").to_show(), "Dropped(\"full\")");
+}
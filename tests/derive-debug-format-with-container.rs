@@ -0,0 +1,58 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt;
+
+// `fmt_version` needs the bound `T: Debug`, which is inferred from the fields.
+#[derive(Derivative)]
+#[derivative(Debug(format_with="Version::fmt_version"))]
+struct Version<T> {
+    major: T,
+    minor: T,
+    patch: T,
+}
+
+impl<T: fmt::Debug> Version<T> {
+    fn fmt_version(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{:?}.{:?}.{:?}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(format_with="fmt_shape"))]
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+fn fmt_shape(shape: &Shape, f: &mut fmt::Formatter) -> fmt::Result {
+    match *shape {
+        Shape::Circle(r) => write!(f, "○ r={}", r),
+        Shape::Square(s) => write!(f, "□ s={}", s),
+    }
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let version = Version {
+        major: 1,
+        minor: 2,
+        patch: 3,
+    };
+    assert_eq!(version.to_show(), "v1.2.3");
+    assert_eq!(Shape::Circle(1.5).to_show(), "○ r=1.5");
+    assert_eq!(Shape::Square(2.0).to_show(), "□ s=2");
+}
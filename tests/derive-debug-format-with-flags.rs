@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate derivative;
+
+use std::fmt;
+
+/// Shows the flags of the formatter it is given.
+fn flags<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "alternate={} width={:?} precision={:?}", f.alternate(), f.width(), f.precision())
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Foo {
+    #[derivative(Debug(format_with="flags"))]
+    foo: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Bar(#[derivative(Debug(format_with="flags"))] u8);
+
+#[test]
+fn main() {
+    assert_eq!(
+        format!("{:?}", Foo { foo: 42 }),
+        "Foo { foo: alternate=false width=None precision=None }"
+    );
+    assert_eq!(
+        format!("{:#?}", Foo { foo: 42 }),
+        "Foo {\n    foo: alternate=true width=None precision=None,\n}"
+    );
+    assert_eq!(
+        format!("{:5.2?}", Foo { foo: 42 }),
+        "Foo { foo: alternate=false width=Some(5) precision=Some(2) }"
+    );
+
+    assert_eq!(format!("{:?}", Bar(42)), "alternate=false width=None precision=None");
+    assert_eq!(format!("{:#3?}", Bar(42)), "alternate=true width=Some(3) precision=None");
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt;
+
+fn fmt_unit(value: &u32, name: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    let unit = match name {
+        "timeout" => "ms",
+        "size" => "B",
+        _ => "",
+    };
+    if f.alternate() {
+        write!(f, "{} {} ({})", value, unit, name)
+    } else {
+        write!(f, "{}{}", value, unit)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Limits {
+    #[derivative(Debug(format_with="fmt_unit", pass_name))]
+    timeout: u32,
+    #[derivative(Debug(format_with="fmt_unit", pass_name, rename="size"))]
+    max_size: u32,
+    retries: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Pair(
+    #[derivative(Debug(format_with="fmt_unit", pass_name))] u32,
+    #[derivative(Debug(format_with="fmt_unit", pass_name, name="size"))] u32,
+);
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Timeout {
+    #[derivative(Debug(format_with="fmt_unit", pass_name))]
+    timeout: u32,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let limits = Limits {
+        timeout: 30,
+        max_size: 512,
+        retries: 3,
+    };
+    assert_eq!(
+        limits.to_show(),
+        "Limits { timeout: 30ms, size: 512B, retries: 3 }"
+    );
+    assert_eq!(
+        format!("{:#?}", limits),
+        "Limits {\n    timeout: 30 ms (timeout),\n    size: 512 B (size),\n    retries: 3,\n}"
+    );
+    assert_eq!(Pair(1, 2).to_show(), "Pair { 0: 1, size: 2B }");
+    assert_eq!(Timeout { timeout: 5 }.to_show(), "5ms");
+}
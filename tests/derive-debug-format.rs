@@ -0,0 +1,49 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Register {
+    #[derivative(Debug(format="{:#010x}"))]
+    address: u32,
+    #[derivative(Debug(format="{:.2}"))]
+    ratio: f64,
+    name: &'static str,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Value {
+    Flags(#[derivative(Debug(format="{:08b}"))] u8),
+    #[derivative(Debug="transparent")]
+    Code(#[derivative(Debug(format="E{:04}"))] u16),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let register = Register {
+        address: 0xbeef,
+        ratio: 0.5,
+        name: "status",
+    };
+    assert_eq!(
+        register.to_show(),
+        "Register { address: 0x0000beef, ratio: 0.50, name: \"status\" }"
+    );
+    assert_eq!(Value::Flags(5).to_show(), "Flags(00000101)");
+    assert_eq!(Value::Code(42).to_show(), "E0042");
+    assert_eq!(format!("{:#?}", Value::Flags(5)), "Flags(\n    00000101,\n)");
+}
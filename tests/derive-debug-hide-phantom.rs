@@ -0,0 +1,44 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+struct Meters;
+
+#[derive(Derivative)]
+#[derivative(Debug(hide_phantom))]
+struct Length<Unit> {
+    value: f64,
+    unit: PhantomData<Unit>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="hide_phantom")]
+enum Id<T> {
+    Known(u32, std::marker::PhantomData<fn() -> T>),
+    Unknown,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let length = Length::<Meters> {
+        value: 1.5,
+        unit: PhantomData,
+    };
+    assert_eq!(length.to_show(), "Length { value: 1.5 }");
+    assert_eq!(Id::<Meters>::Known(3, PhantomData).to_show(), "Known(3)");
+    assert_eq!(Id::<Meters>::Unknown.to_show(), "Unknown");
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+struct NoDebug;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Message {
+    Ping(u32),
+    #[derivative(Debug="ignore")]
+    Blob(Vec<u8>, usize),
+    #[derivative(Debug="ignore")]
+    Secret {
+        #[allow(dead_code)]
+        key: NoDebug,
+    },
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="non_exhaustive_hint")]
+enum Hinted {
+    #[derivative(Debug="ignore")]
+    Blob(Vec<u8>),
+    #[derivative(Debug="ignore")]
+    Named { len: usize },
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+enum Transparent<T> {
+    Value(u8),
+    #[derivative(Debug="ignore")]
+    Opaque(T),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(Message::Ping(1).to_show(), "Ping(1)");
+    assert_eq!(Message::Blob(vec![1, 2, 3], 3).to_show(), "Blob");
+    assert_eq!(Message::Secret { key: NoDebug }.to_show(), "Secret");
+
+    assert_eq!(Hinted::Blob(vec![1]).to_show(), "Blob(..)");
+    assert_eq!(Hinted::Named { len: 1 }.to_show(), "Named { .. }");
+
+    assert_eq!(Transparent::<NoDebug>::Value(42).to_show(), "42");
+    assert_eq!(Transparent::Opaque(NoDebug).to_show(), "Opaque");
+}
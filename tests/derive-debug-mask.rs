@@ -0,0 +1,56 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+struct Key([u8; 32]);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Credentials {
+    user: &'static str,
+    #[derivative(Debug(mask))]
+    password: &'static str,
+    #[derivative(Debug(mask="<redacted>"))]
+    key: Key,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Auth<T> {
+    Token(#[derivative(Debug="mask")] T),
+    #[derivative(Debug="transparent")]
+    Secret(#[derivative(Debug(mask="{}"))] T),
+    Anonymous,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let credentials = Credentials {
+        user: "root",
+        password: "hunter2",
+        key: Key([0; 32]),
+    };
+    assert_eq!(
+        credentials.to_show(),
+        "Credentials { user: \"root\", password: ***, key: <redacted> }"
+    );
+    let _ = credentials.password;
+    let _ = credentials.key.0;
+
+    // No `Debug` bound is needed on `T`.
+    assert_eq!(Auth::Token(Key([0; 32])).to_show(), "Token(***)");
+    assert_eq!(Auth::Secret(Key([0; 32])).to_show(), "{}");
+    assert_eq!(Auth::<Key>::Anonymous.to_show(), "Anonymous");
+}
@@ -0,0 +1,59 @@
+#![cfg(not(feature = "use_core"))]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug(max_depth="2"))]
+struct Tree {
+    value: u32,
+    children: Vec<Tree>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(max_depth="1"))]
+enum List {
+    Cons(u32, Box<List>),
+    Nil,
+}
+
+fn leaf(value: u32) -> Tree {
+    Tree {
+        value,
+        children: Vec::new(),
+    }
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let tree = Tree {
+        value: 1,
+        children: vec![
+            Tree {
+                value: 2,
+                children: vec![leaf(4)],
+            },
+            leaf(3),
+        ],
+    };
+    assert_eq!(
+        tree.to_show(),
+        "Tree { value: 1, children: [Tree { value: 2, children: [\u{2026}] }, \
+         Tree { value: 3, children: [] }] }"
+    );
+    // The depth is restored once the value is formatted.
+    assert_eq!(leaf(5).to_show(), "Tree { value: 5, children: [] }");
+
+    assert_eq!(List::Cons(1, Box::new(List::Nil)).to_show(), "Cons(1, \u{2026})");
+    assert_eq!(vec![List::Nil, List::Nil].to_show(), "[Nil, Nil]");
+}
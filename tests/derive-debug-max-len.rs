@@ -0,0 +1,59 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::BTreeMap;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Packet<T> {
+    id: u32,
+    #[derivative(Debug(max_len="4"))]
+    payload: Vec<u8>,
+    #[derivative(Debug(max_len="2"))]
+    headers: BTreeMap<&'static str, T>,
+    #[derivative(Debug(max_len="3"))]
+    checksum: [u8; 2],
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Buffer<'a>(#[derivative(Debug(max_len="1"))] &'a [u16]);
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let mut headers = BTreeMap::new();
+    headers.insert("a", 1);
+    headers.insert("b", 2);
+    headers.insert("c", 3);
+    let packet = Packet {
+        id: 7,
+        payload: (0..10).collect(),
+        headers,
+        checksum: [1, 2],
+    };
+    assert_eq!(
+        packet.to_show(),
+        "Packet { id: 7, payload: [0, 1, 2, 3, \u{2026} (6 more)], \
+         headers: [(\"a\", 1), (\"b\", 2), \u{2026} (1 more)], checksum: [1, 2] }"
+    );
+
+    assert_eq!(Buffer(&[]).to_show(), "[]");
+    assert_eq!(Buffer(&[5, 6]).to_show(), "[5, \u{2026} (1 more)]");
+    assert_eq!(
+        format!("{:#?}", Buffer(&[5, 6])),
+        "[\n    5,\n    \u{2026} (1 more),\n]"
+    );
+}
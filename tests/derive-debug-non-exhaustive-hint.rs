@@ -0,0 +1,73 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="non_exhaustive_hint")]
+struct Credentials {
+    user: &'static str,
+    #[derivative(Debug="ignore")]
+    password: &'static str,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(non_exhaustive_hint))]
+struct Handle(u32, #[derivative(Debug="ignore")] u64);
+
+#[derive(Derivative)]
+#[derivative(Debug="non_exhaustive_hint")]
+enum Event {
+    Login {
+        user: &'static str,
+        #[derivative(Debug="ignore")]
+        token: u64,
+    },
+    Logout(&'static str),
+    Timeout,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+    fn to_show_alternate(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn to_show_alternate(&self) -> String {
+        format!("{:#?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let credentials = Credentials {
+        user: "root",
+        password: "hunter2",
+    };
+    assert_eq!(credentials.to_show(), "Credentials { user: \"root\", .. }");
+    assert_eq!(
+        credentials.to_show_alternate(),
+        "Credentials {\n    user: \"root\",\n    ..\n}"
+    );
+    let _ = credentials.password;
+
+    let handle = Handle(1, 2);
+    assert_eq!(handle.to_show(), "Handle(1, ..)");
+    let _ = handle.1;
+
+    let login = Event::Login {
+        user: "root",
+        token: 42,
+    };
+    assert_eq!(login.to_show(), "Login { user: \"root\", .. }");
+    if let Event::Login { token, .. } = login {
+        assert_eq!(token, 42);
+    }
+    assert_eq!(Event::Logout("root").to_show(), "Logout(\"root\")");
+    assert_eq!(Event::Timeout.to_show(), "Timeout");
+}
@@ -0,0 +1,61 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Clone, Copy, Derivative)]
+#[derivative(Debug)]
+#[repr(C, packed)]
+struct Header {
+    tag: u8,
+    length: u32,
+    #[derivative(Debug(format="{:#x}"))]
+    checksum: u16,
+    #[derivative(Debug="ignore")]
+    _reserved: u8,
+}
+
+#[derive(Clone, Copy, Derivative)]
+#[derivative(Debug="transparent")]
+#[repr(C, packed(2))]
+struct Id(u64);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[repr(C, packed)]
+struct Pair<T> {
+    flag: bool,
+    value: T,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let header = Header {
+        tag: 1,
+        length: 512,
+        checksum: 0xbeef,
+        _reserved: 0,
+    };
+    assert_eq!(
+        header.to_show(),
+        "Header { tag: 1, length: 512, checksum: 0xbeef }"
+    );
+    assert_eq!(Id(42).to_show(), "42");
+    assert_eq!(
+        Pair { flag: true, value: 7u64 }.to_show(),
+        "Pair { flag: true, value: 7 }"
+    );
+}
@@ -0,0 +1,61 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+mod net {
+    #[derive(Derivative)]
+    #[derivative(Debug(qualified))]
+    pub struct Addr {
+        pub port: u16,
+    }
+
+    #[derive(Derivative)]
+    #[derivative(Debug(qualified))]
+    pub enum State {
+        Open,
+        Closed(u8),
+    }
+
+    #[derive(Derivative)]
+    #[derivative(Debug(qualified, with_discriminant))]
+    pub enum Code {
+        Ok = 200,
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="qualified")]
+struct Unit;
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        net::Addr { port: 80 }.to_show(),
+        "derive_debug_qualified::net::Addr { port: 80 }"
+    );
+    assert_eq!(
+        net::State::Open.to_show(),
+        "derive_debug_qualified::net::State::Open"
+    );
+    assert_eq!(
+        net::State::Closed(1).to_show(),
+        "derive_debug_qualified::net::State::Closed(1)"
+    );
+    assert_eq!(
+        net::Code::Ok.to_show(),
+        "derive_debug_qualified::net::Code::Ok(=200)"
+    );
+    assert_eq!(Unit.to_show(), "derive_debug_qualified::Unit");
+}
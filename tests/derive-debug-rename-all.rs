@@ -0,0 +1,49 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug(rename_all="camelCase"))]
+struct Request {
+    request_id: u32,
+    #[derivative(Debug(rename="path"))]
+    request_path: &'static str,
+    retry_count: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(rename_all="SCREAMING-KEBAB-CASE"), rename_all="snake_case")]
+enum Event {
+    KeyPress { key_code: u16 },
+    Resize(u16, u16),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let request = Request {
+        request_id: 1,
+        request_path: "/",
+        retry_count: 0,
+    };
+    assert_eq!(
+        request.to_show(),
+        "Request { requestId: 1, path: \"/\", retryCount: 0 }"
+    );
+    assert_eq!(
+        Event::KeyPress { key_code: 13 }.to_show(),
+        "key_press { KEY-CODE: 13 }"
+    );
+    assert_eq!(Event::Resize(80, 24).to_show(), "resize(80, 24)");
+}
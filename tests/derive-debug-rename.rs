@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Handle {
+    #[derivative(Debug(rename = "inner"))]
+    __inner: u32,
+    #[derivative(Debug(rename = "type"))]
+    kind: &'static str,
+    open: bool,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug = "verbose_debug")]
+enum Event {
+    Click {
+        #[derivative(Debug(rename = "x-position"))]
+        x: i32,
+        #[derivative(Debug(rename = "secret", ignore))]
+        token: u8,
+    },
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let handle = Handle {
+        __inner: 3,
+        kind: "file",
+        open: true,
+    };
+    assert_eq!(
+        handle.to_show(),
+        "Handle { inner: 3, type: \"file\", open: true }"
+    );
+
+    let event = Event::Click { x: 1, token: 2 };
+    assert_eq!(event.to_show(), "Click { x-position: 1 }");
+    assert_eq!(
+        event.verbose_debug().to_show(),
+        "Click { x-position: 1, secret: 2 }"
+    );
+}
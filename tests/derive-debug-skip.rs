@@ -0,0 +1,84 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Config<T> {
+    name: &'static str,
+    #[derivative(Debug(skip_if_default))]
+    retries: u32,
+    #[derivative(Debug="skip_if_default")]
+    extra: T,
+    #[derivative(Debug(skip_if_none))]
+    timeout: Option<u64>,
+    proxy: Option<&'static str>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(skip_if_default, skip_if_none), Debug="verbose_debug")]
+struct Options {
+    verbose: bool,
+    level: u8,
+    #[derivative(Debug="ignore")]
+    path: Option<&'static str>,
+    color: Option<bool>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(skip_if_none))]
+enum Event {
+    Click { x: i32, button: Option<u8> },
+    Key(Option<char>),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let config = Config {
+        name: "app",
+        retries: 0,
+        extra: String::new(),
+        timeout: None,
+        proxy: None,
+    };
+    assert_eq!(config.to_show(), "Config { name: \"app\", proxy: None }");
+    let config = Config {
+        name: "app",
+        retries: 3,
+        extra: "x".to_string(),
+        timeout: Some(10),
+        proxy: None,
+    };
+    assert_eq!(
+        config.to_show(),
+        "Config { name: \"app\", retries: 3, extra: \"x\", timeout: Some(10), proxy: None }"
+    );
+
+    let options = Options {
+        verbose: false,
+        level: 2,
+        path: None,
+        color: None,
+    };
+    assert_eq!(options.to_show(), "Options { level: 2 }");
+    assert_eq!(
+        options.verbose_debug().to_show(),
+        "Options { verbose: false, level: 2, path: None, color: None }"
+    );
+
+    let click = Event::Click { x: 1, button: None };
+    assert_eq!(click.to_show(), "Click { x: 1 }");
+    assert_eq!(Event::Key(None).to_show(), "Key(None)");
+}
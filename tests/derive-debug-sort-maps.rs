@@ -0,0 +1,49 @@
+#![cfg(not(feature = "use_core"))]
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Snapshot<K> {
+    #[derivative(Debug(sort_maps))]
+    counts: HashMap<K, u32>,
+    #[derivative(Debug(sort_maps))]
+    tags: HashSet<&'static str>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+struct Index<'a>(#[derivative(Debug(sort_maps))] &'a HashMap<u8, char>);
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let snapshot = Snapshot {
+        counts: (0..20).rev().map(|i| (i, i * 2)).collect(),
+        tags: ["gamma", "alpha", "beta"].iter().cloned().collect(),
+    };
+    let counts: Vec<_> = (0..20).map(|i| format!("{}: {}", i, i * 2)).collect();
+    assert_eq!(
+        snapshot.to_show(),
+        format!(
+            "Snapshot {{ counts: {{{}}}, tags: {{\"alpha\", \"beta\", \"gamma\"}} }}",
+            counts.join(", ")
+        )
+    );
+
+    let index = [(3, 'c'), (1, 'a'), (2, 'b')].iter().cloned().collect();
+    assert_eq!(Index(&index).to_show(), "{1: 'a', 2: 'b', 3: 'c'}");
+    assert_eq!(Index(&HashMap::new()).to_show(), "{}");
+}
@@ -20,6 +20,18 @@ enum C {
     Bar(u8),
 }
 
+#[derive(Derivative)]
+#[derivative(Debug="transparent")]
+enum D {
+    Int(i64),
+    Text(&'static str),
+    #[derivative(Debug(transparent="false"))]
+    Id(u32),
+    Pair(i64, i64),
+    Named { x: u8 },
+    Null,
+}
+
 trait ToDebug {
     fn to_show(&self) -> String;
 }
@@ -36,4 +48,10 @@ fn main() {
     assert_eq!(B([42]).to_show(), "[42]".to_string());
     assert_eq!(C::Foo(42).to_show(), "Foo(42)".to_string());
     assert_eq!(C::Bar(42).to_show(), "42".to_string());
+    assert_eq!(D::Int(42).to_show(), "42".to_string());
+    assert_eq!(D::Text("foo").to_show(), "\"foo\"".to_string());
+    assert_eq!(D::Id(7).to_show(), "Id(7)".to_string());
+    assert_eq!(D::Pair(1, 2).to_show(), "Pair(1, 2)".to_string());
+    assert_eq!(D::Named { x: 1 }.to_show(), "1".to_string());
+    assert_eq!(D::Null.to_show(), "Null".to_string());
 }
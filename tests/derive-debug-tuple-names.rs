@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Point(#[derivative(Debug(name="x"))] i32, #[derivative(Debug(name="y"))] i32);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Partial(
+    #[derivative(Debug(name="id"))] u32,
+    #[derivative(Debug="ignore")] u8,
+    &'static str,
+);
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Shape {
+    Circle(#[derivative(Debug(name="radius"))] u32),
+    Line(u32, u32),
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(Point(1, 2).to_show(), "Point { x: 1, y: 2 }");
+    assert_eq!(Partial(7, 0, "foo").to_show(), "Partial { id: 7, 2: \"foo\" }");
+    assert_eq!(Shape::Circle(3).to_show(), "Circle { radius: 3 }");
+    assert_eq!(Shape::Line(1, 2).to_show(), "Line(1, 2)");
+    assert_eq!(
+        format!("{:#?}", Point(1, 2)),
+        "Point {\n    x: 1,\n    y: 2,\n}"
+    );
+}
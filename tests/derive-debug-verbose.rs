@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Debug="verbose_debug")]
+struct Credentials {
+    user: String,
+    #[derivative(Debug="ignore")]
+    password: String,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug(verbose_debug))]
+enum Token<'a, T> {
+    Bearer(#[derivative(Debug="ignore")] &'a T),
+    Anonymous,
+}
+
+trait ToDebug {
+    fn to_show(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> ToDebug for T {
+    fn to_show(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn main() {
+    let credentials = Credentials {
+        user: "root".to_string(),
+        password: "hunter2".to_string(),
+    };
+    assert_eq!(credentials.to_show(), "Credentials { user: \"root\" }");
+    assert_eq!(
+        credentials.verbose_debug().to_show(),
+        "Credentials { user: \"root\", password: \"hunter2\" }"
+    );
+
+    let token: Token<u8> = Token::Bearer(&42);
+    assert_eq!(token.to_show(), "Bearer");
+    assert_eq!(token.verbose_debug().to_show(), "Bearer(42)");
+    let _: TokenVerboseDebug<u8> = Token::Anonymous.verbose_debug();
+    assert_eq!(Token::<u8>::Anonymous.verbose_debug().to_show(), "Anonymous");
+}
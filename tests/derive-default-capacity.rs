@@ -0,0 +1,40 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Derivative)]
+#[derivative(Default(reset))]
+struct Buffers {
+    #[derivative(Default(capacity="1024"))]
+    bytes: Vec<u8>,
+    #[derivative(Default(capacity="64"))]
+    text: String,
+    #[derivative(Default(capacity="16"))]
+    map: HashMap<u32, String>,
+    #[derivative(Default(capacity="CAPACITY * 2"))]
+    queue: VecDeque<u8>,
+}
+
+const CAPACITY: usize = 8;
+
+#[test]
+fn main() {
+    let mut buffers = Buffers::default();
+    assert!(buffers.bytes.is_empty());
+    assert!(buffers.bytes.capacity() >= 1024);
+    assert!(buffers.text.capacity() >= 64);
+    assert!(buffers.map.capacity() >= 16);
+    assert!(buffers.queue.capacity() >= 16);
+
+    buffers.bytes = vec![1, 2, 3];
+    buffers.text = "foo".to_string();
+    buffers.reset();
+    assert!(buffers.bytes.is_empty());
+    assert!(buffers.bytes.capacity() >= 1024);
+    assert!(buffers.text.is_empty());
+    assert!(buffers.text.capacity() >= 64);
+}
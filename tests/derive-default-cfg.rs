@@ -0,0 +1,34 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct Foo {
+    #[derivative(Default(value(windows="\"C:\\\\data\"", unix="\"/var/data\"")))]
+    path: &'static str,
+    #[derivative(Default(value(test="1", unix="2")))]
+    first: u8,
+    #[derivative(Default(value(miri="42")))]
+    fallback: u8,
+    #[derivative(Default(value(cfg="feature = \"use_core\"", value="1", test="2")))]
+    feature: u8,
+    #[derivative(Default(value(cfg="all(test, any(unix, windows))", value="3", test="4")))]
+    predicate: u8,
+}
+
+#[test]
+fn main() {
+    let foo = Foo::default();
+
+    if cfg!(windows) {
+        assert_eq!(foo.path, "C:\\data");
+    } else if cfg!(unix) {
+        assert_eq!(foo.path, "/var/data");
+    } else {
+        assert_eq!(foo.path, "");
+    }
+    assert_eq!(foo.first, 1);
+    assert_eq!(foo.fallback, 0);
+    assert_eq!(foo.feature, if cfg!(feature = "use_core") { 1 } else { 2 });
+    assert_eq!(foo.predicate, if cfg!(any(unix, windows)) { 3 } else { 4 });
+}
@@ -0,0 +1,54 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const))]
+struct Limits {
+    #[derivative(Default(value = "16"))]
+    connections: u16,
+    #[derivative(Default(value = "connections as u32 * 1024"))]
+    buffer: u32,
+    #[derivative(Default(value = "\"localhost\""))]
+    host: &'static str,
+    #[derivative(Default(value = "Vec::new()"))]
+    allowed: Vec<u8>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default = "const")]
+enum Level {
+    #[allow(dead_code)]
+    Low,
+    #[derivative(Default)]
+    Medium(#[derivative(Default(value = "5"))] u8),
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(const, new))]
+struct Marker;
+
+static LIMITS: Limits = Limits::DEFAULT;
+const LEVEL: Level = Level::DEFAULT;
+
+#[test]
+fn main() {
+    assert_eq!(LIMITS.connections, 16);
+    assert_eq!(LIMITS.buffer, 16 * 1024);
+    assert_eq!(Limits::default(), Limits::DEFAULT);
+    assert_eq!(
+        Limits::default(),
+        Limits {
+            connections: 16,
+            buffer: 16384,
+            host: "localhost",
+            allowed: Vec::new(),
+        }
+    );
+
+    assert_eq!(LEVEL, Level::Medium(5));
+    assert_eq!(Level::default(), Level::Medium(5));
+    assert_eq!(Marker::new(), Marker::DEFAULT);
+}
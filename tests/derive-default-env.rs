@@ -0,0 +1,31 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct Foo {
+    #[derivative(Default(value_env="CARGO_PKG_NAME"))]
+    name: &'static str,
+    #[derivative(Default(value_env="CARGO_PKG_NAME"))]
+    owned: String,
+    #[derivative(Default(value_env="DERIVATIVE_TEST_UNSET_VARIABLE", env_fallback="fallback"))]
+    fallback: String,
+    #[derivative(Default(value_env="CARGO_PKG_NAME", env_fallback="fallback"))]
+    present: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Foo::default(),
+        Foo {
+            name: "derivative",
+            owned: "derivative".to_string(),
+            fallback: "fallback".to_string(),
+            present: "derivative",
+        }
+    );
+}
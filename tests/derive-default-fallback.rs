@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+enum Disabled {
+    #[cfg(not(test))]
+    #[derivative(Default)]
+    Gpu,
+    #[derivative(Default(fallback))]
+    Cpu,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+enum Enabled {
+    #[cfg(test)]
+    #[derivative(Default)]
+    Gpu,
+    #[derivative(Default(fallback))]
+    Cpu,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+enum OnlyFallback {
+    A,
+    #[derivative(Default(fallback))]
+    B(u8),
+}
+
+#[test]
+fn main() {
+    assert_eq!(Disabled::default(), Disabled::Cpu);
+    assert_eq!(Enabled::default(), Enabled::Gpu);
+    assert_eq!(OnlyFallback::default(), OnlyFallback::B(0));
+}
@@ -0,0 +1,40 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+struct Buffer {
+    #[derivative(Default(value="Vec::with_capacity(1024)"))]
+    data: Vec<u8>,
+    #[derivative(Default(value="data.capacity() / 2"))]
+    low_water_mark: usize,
+    #[derivative(Default(value="low_water_mark * 2"))]
+    high_water_mark: usize,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default)]
+enum Shape {
+    #[derivative(Default)]
+    Rect {
+        #[derivative(Default(value="4"))]
+        width: u32,
+        #[derivative(Default(value="width / 2"))]
+        height: u32,
+    },
+    #[allow(dead_code)]
+    Circle { radius: u32 },
+}
+
+#[test]
+fn main() {
+    let buffer = Buffer::default();
+    assert!(buffer.data.capacity() >= 1024);
+    assert_eq!(buffer.low_water_mark, buffer.data.capacity() / 2);
+    assert_eq!(buffer.high_water_mark, buffer.low_water_mark * 2);
+
+    assert_eq!(Shape::default(), Shape::Rect { width: 4, height: 2 });
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new))]
+struct Connection<T> {
+    #[derivative(Default(required))]
+    host: String,
+    #[derivative(Default(value="80"))]
+    port: u16,
+    #[derivative(Default(value="format!(\"{}:{}\", host, port)"))]
+    address: String,
+    #[derivative(Default(required))]
+    handler: T,
+    retries: u8,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(new, reset))]
+struct Settings {
+    #[derivative(Default(required))]
+    name: String,
+    #[derivative(Default(value="3"))]
+    retries: u8,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default="new")]
+struct Pair(#[derivative(Default(required))] NoDefault, u8);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default="new")]
+enum Message {
+    #[derivative(Default)]
+    Text {
+        #[derivative(Default(required))]
+        text: &'static str,
+        urgent: bool,
+    },
+    #[allow(dead_code)]
+    Empty,
+}
+
+#[derive(Debug, PartialEq)]
+struct NoDefault;
+
+#[derive(Derivative)]
+#[derivative(Default(new))]
+struct Callback<'a> {
+    name: &'a str,
+    target: &'a NoDefault,
+    call: fn(u8) -> u8,
+    #[derivative(Default(value="double"))]
+    fallback: fn(u8) -> u8,
+    calls: u32,
+}
+
+fn double(x: u8) -> u8 {
+    x * 2
+}
+
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[test]
+fn main() {
+    let connection = Connection::new("localhost".to_string(), NoDefault);
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".to_string(),
+            port: 80,
+            address: "localhost:80".to_string(),
+            handler: NoDefault,
+            retries: 0,
+        }
+    );
+
+    let mut settings = Settings::new("foo".to_string());
+    assert_eq!(settings.retries, 3);
+    settings.name = "bar".to_string();
+    settings.retries = 0;
+    settings.reset();
+    assert_eq!(settings, Settings { name: "bar".to_string(), retries: 3 });
+
+    assert_eq!(Pair::new(NoDefault), Pair(NoDefault, 0));
+    assert_eq!(Message::new("hi"), Message::Text { text: "hi", urgent: false });
+
+    let target = NoDefault;
+    let callback = Callback::new(&target, increment);
+    assert_eq!(callback.name, "");
+    assert_eq!(*callback.target, NoDefault);
+    assert_eq!((callback.call)(1), 2);
+    assert_eq!((callback.fallback)(1), 2);
+    assert_eq!(callback.calls, 0);
+}
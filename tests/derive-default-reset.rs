@@ -0,0 +1,66 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(reset))]
+struct Foo {
+    foo: u8,
+    #[derivative(Default(value="42"))]
+    bar: u8,
+    buffer: Vec<u8>,
+    #[derivative(Default(value="vec![1, 2, 3]"))]
+    list: Vec<u8>,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(reset="true"))]
+struct Bar(String, #[derivative(Default(value="7"))] u32);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(reset))]
+struct Window {
+    #[derivative(Default(value="Vec::with_capacity(8)"))]
+    buffer: Vec<u8>,
+    #[derivative(Default(value="buffer.capacity() / 2"))]
+    low: usize,
+    #[derivative(Default(value="low * 2"))]
+    high: usize,
+    title: String,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(reset))]
+enum Baz {
+    #[allow(dead_code)]
+    A(u8),
+    #[derivative(Default)]
+    B,
+}
+
+#[test]
+fn main() {
+    let mut foo = Foo { foo: 1, bar: 2, buffer: Vec::with_capacity(64), list: vec![] };
+    foo.buffer.push(3);
+    foo.reset();
+    assert_eq!(foo, Foo { foo: 0, bar: 42, buffer: vec![], list: vec![1, 2, 3] });
+    assert!(foo.buffer.capacity() >= 64);
+
+    let mut bar = Bar("foo".to_string(), 0);
+    bar.reset();
+    assert_eq!(bar, Bar(String::new(), 7));
+
+    let mut window = Window { buffer: vec![1], low: 0, high: 0, title: "foo".to_string() };
+    window.reset();
+    assert!(window.buffer.is_empty());
+    assert_eq!(window.low, window.buffer.capacity() / 2);
+    assert_eq!(window.high, window.low * 2);
+    assert!(window.high >= 8);
+    assert_eq!(window.title, "");
+
+    let mut baz = Baz::A(4);
+    baz.reset();
+    assert_eq!(baz, Baz::B);
+}
@@ -0,0 +1,59 @@
+#![allow(dead_code)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::time::Duration;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(value_fns))]
+struct Config {
+    #[derivative(Default(value="Duration::from_secs(30)"))]
+    timeout: Duration,
+    #[derivative(Default(capacity="4"))]
+    hosts: Vec<String>,
+    retries: u8,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default="value_fns")]
+struct Pair<T: Default>(T, #[derivative(Default(value="7"))] u8);
+
+fn len() -> usize {
+    3
+}
+
+mod data {
+    pub fn new() -> Vec<u8> {
+        vec![1, 2]
+    }
+}
+
+// The functions and the module have the names of fields, but the values don't use the fields.
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(Default(value_fns))]
+struct Buffer {
+    #[derivative(Default(value="len()"))]
+    len: usize,
+    #[derivative(Default(value="data::new()"))]
+    data: Vec<u8>,
+    #[derivative(Default(value="\"abc\".len()"))]
+    size: usize,
+}
+
+#[test]
+fn main() {
+    assert_eq!(Config::default_timeout(), Duration::from_secs(30));
+    assert_eq!(Config::default_hosts().capacity(), 4);
+    assert_eq!(Config::default().timeout, Config::default_timeout());
+
+    assert_eq!(Pair::<String>::default_1(), 7);
+    assert_eq!(Pair::<String>::default(), Pair(String::new(), 7));
+
+    assert_eq!(Buffer::default_len(), 3);
+    assert_eq!(Buffer::default_data(), vec![1, 2]);
+    assert_eq!(Buffer::default_size(), 3);
+}
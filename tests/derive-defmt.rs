@@ -0,0 +1,76 @@
+#![cfg(feature = "defmt")]
+
+#[macro_use]
+extern crate derivative;
+extern crate defmt;
+
+use std::fmt;
+
+#[derive(Derivative)]
+#[derivative(Debug, Format)]
+struct Credentials {
+    user: &'static str,
+    #[derivative(Debug = "ignore")]
+    password: &'static str,
+    #[derivative(Debug(format_with = "fmt_key"))]
+    key: Key,
+    #[derivative(Format(format_with = "format_token"))]
+    token: Token,
+    #[derivative(Format = "ignore")]
+    attempts: u32,
+}
+
+struct Key([u8; 4]);
+
+#[derive(Debug)]
+struct Token(u64);
+
+fn fmt_key(key: &Key, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "<key of {} bytes>", key.0.len())
+}
+
+fn format_token(token: &Token, f: defmt::Formatter) {
+    defmt::write!(f, "<token {=u64:x}>", token.0)
+}
+
+#[derive(Derivative)]
+#[derivative(Debug = "transparent", Format)]
+struct Id(u32);
+
+#[derive(Derivative)]
+#[derivative(Format(bound = ""))]
+enum Event<T> {
+    Connected(Id),
+    Closed { code: u16 },
+    Idle,
+    Marker(std::marker::PhantomData<T>),
+}
+
+fn is_format<T: defmt::Format>(_: &T) -> bool {
+    true
+}
+
+#[test]
+fn main() {
+    let events: Vec<Event<std::cell::Cell<u8>>> = vec![
+        Event::Connected(Id(1)),
+        Event::Closed { code: 1000 },
+        Event::Idle,
+        Event::Marker(std::marker::PhantomData),
+    ];
+    // `Event<T>` is `Format` even when `T` is not.
+    assert!(events.iter().all(is_format));
+
+    let credentials = Credentials {
+        user: "user",
+        password: "hunter2",
+        key: Key([0; 4]),
+        token: Token(0),
+        attempts: 3,
+    };
+    assert!(is_format(&credentials));
+    assert_eq!(
+        format!("{:?}", credentials),
+        "Credentials { user: \"user\", key: <key of 4 bytes>, token: Token(0), attempts: 3 }"
+    );
+}
@@ -0,0 +1,42 @@
+#![deny(deprecated)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Default(new, reset), Hash, PartialEq, Eq)]
+struct Config {
+    name: &'static str,
+    #[deprecated(note = "use `name`")]
+    #[derivative(Default(value="\"legacy\""))]
+    legacy_name: &'static str,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Default, PartialEq="feature_allow_slow_enum")]
+enum Mode {
+    #[derivative(Default)]
+    Fast,
+    #[deprecated]
+    Slow(#[deprecated] u8),
+}
+
+#[deprecated]
+#[derive(Derivative)]
+#[derivative(Clone, Debug(verbose_debug), Default)]
+struct Old(#[derivative(Debug="ignore")] u8);
+
+#[test]
+#[allow(deprecated)]
+fn main() {
+    let config = Config::new();
+    assert_eq!(config.legacy_name, "legacy");
+    assert!(config.clone() == config);
+    assert_eq!(Mode::default(), Mode::Fast);
+    assert_eq!(Mode::Slow(1).clone(), Mode::Slow(1));
+    assert_eq!(format!("{:?}", Old::default().verbose_debug()), "Old(0)");
+}
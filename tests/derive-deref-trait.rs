@@ -0,0 +1,47 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Derivative)]
+#[derivative(Deref, DerefMut)]
+struct Meters(f64);
+
+/// A guard that counts how many times it was taken.
+#[derive(Derivative)]
+#[derivative(Deref, DerefMut)]
+struct Guard<'a, T: 'a> {
+    #[derivative(Deref)]
+    inner: MutexGuard<'a, T>,
+    taken: usize,
+}
+
+#[derive(Derivative)]
+#[derivative(Deref)]
+struct Tagged<T>(&'static str, #[derivative(Deref)] Vec<T>);
+
+#[test]
+fn main() {
+    let mut meters = Meters(1.0);
+    *meters += 1.5;
+    assert_eq!(*meters, 2.5);
+    assert_eq!(meters.sqrt(), 2.5f64.sqrt());
+
+    let mutex = Mutex::new(vec![1]);
+    let mut guard = Guard {
+        inner: mutex.lock().unwrap(),
+        taken: 1,
+    };
+    guard.push(2);
+    assert_eq!(guard.len(), 2);
+    assert_eq!(guard.taken, 1);
+    drop(guard);
+    assert_eq!(*mutex.lock().unwrap(), vec![1, 2]);
+
+    let tagged = Tagged("numbers", vec![1, 2, 3]);
+    assert_eq!(tagged.len(), 3);
+    assert_eq!(tagged.0, "numbers");
+}
@@ -0,0 +1,87 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A handle to an interned string, which does not implement `Hash` or `PartialEq` itself.
+struct Interned(&'static str);
+
+impl Deref for Interned {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Hash, PartialEq, Eq)]
+struct Key {
+    #[derivative(Hash(deref), PartialEq(deref))]
+    name: Interned,
+}
+
+impl Borrow<str> for Key {
+    fn borrow(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Hash, PartialEq, Eq)]
+struct Shared<P: Deref> {
+    #[derivative(eq_hash(deref))]
+    ptr: P,
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq)]
+struct Length {
+    #[derivative(PartialEq(deref, compare_with = "same_length"))]
+    name: Box<str>,
+}
+
+fn same_length(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+}
+
+fn hash<T: ?Sized + Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    let key = Key {
+        name: Interned("foo"),
+    };
+    assert_eq!(hash(&key), hash("foo"));
+    assert!(key == Key { name: Interned("foo") });
+    assert!(key != Key { name: Interned("bar") });
+
+    let mut keys = HashSet::new();
+    keys.insert(key);
+    assert!(keys.contains("foo"));
+    assert!(!keys.contains("bar"));
+
+    let shared = Shared {
+        ptr: Rc::<str>::from("foo"),
+    };
+    assert!(shared == Shared { ptr: Rc::from("foo") });
+    assert!(shared != Shared { ptr: Rc::from("bar") });
+    assert_eq!(hash(&shared), hash("foo"));
+
+    assert!(Length { name: "foo".into() } == Length { name: "bar".into() });
+    assert!(Length { name: "foo".into() } != Length { name: "quux".into() });
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+struct Handle {
+    name: &'static str,
+    log: Log,
+}
+
+fn close_handle(handle: &mut Handle) {
+    handle.log.borrow_mut().push(format!("close {}", handle.name));
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, Drop(with = "Connection::disconnect"))]
+struct Connection {
+    id: u32,
+    #[derivative(Debug = "ignore")]
+    log: Log,
+}
+
+impl Connection {
+    fn disconnect(&mut self) {
+        self.log.borrow_mut().push(format!("disconnect {}", self.id));
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Drop)]
+enum Resource<T> {
+    File {
+        #[derivative(Drop(with = "close_handle"))]
+        handle: Handle,
+        buffer: Vec<T>,
+    },
+    Closed,
+}
+
+#[derive(Derivative)]
+#[derivative(Drop(with = "Session::end"))]
+struct Session(
+    #[derivative(Drop(with = "close_handle"))] Handle,
+    #[derivative(Drop(with = "close_handle"))] Handle,
+);
+
+impl Session {
+    fn end(&mut self) {
+        self.0.log.borrow_mut().push("end".to_string());
+    }
+}
+
+#[test]
+fn main() {
+    let log = Log::default();
+    let handle = |name| Handle {
+        name,
+        log: log.clone(),
+    };
+
+    drop(Connection {
+        id: 1,
+        log: log.clone(),
+    });
+    assert_eq!(*log.borrow(), ["disconnect 1"]);
+    log.borrow_mut().clear();
+
+    drop(Resource::File {
+        handle: handle("file"),
+        buffer: vec![1, 2],
+    });
+    drop(Resource::Closed::<u8>);
+    assert_eq!(*log.borrow(), ["close file"]);
+    log.borrow_mut().clear();
+
+    // The hook of the type is called first, then those of the fields in order.
+    {
+        let _session = Session(handle("a"), handle("b"));
+    }
+    assert_eq!(*log.borrow(), ["end", "close a", "close b"]);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "dyn-clone")]
+
+#[macro_use]
+extern crate derivative;
+extern crate dyn_clone;
+
+use dyn_clone::DynClone;
+
+trait Shape: DynClone {
+    fn area(&self) -> f64;
+}
+
+#[derive(Clone)]
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone)]
+struct Scene {
+    name: String,
+    #[derivative(Clone="boxed")]
+    shape: Box<dyn Shape>,
+}
+
+// `T` only needs to be `DynClone`, which any `Clone` type is.
+#[derive(Derivative)]
+#[derivative(Clone)]
+enum Slot<T: ?Sized> {
+    Empty,
+    Full(#[derivative(Clone(boxed))] Box<T>),
+}
+
+#[test]
+fn main() {
+    let scene = Scene {
+        name: "one".to_string(),
+        shape: Box::new(Square(2.0)),
+    };
+    let copy = scene.clone();
+    assert_eq!(copy.name, "one");
+    assert_eq!(copy.shape.area(), 4.0);
+
+    let slot: Slot<dyn Shape> = Slot::Full(Box::new(Square(3.0)));
+    match slot.clone() {
+        Slot::Full(shape) => assert_eq!(shape.area(), 9.0),
+        Slot::Empty => panic!("the slot should be full"),
+    }
+    assert!(matches!(Slot::<str>::Empty.clone(), Slot::Empty));
+}
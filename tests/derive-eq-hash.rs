@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Derivative)]
+#[derivative(eq_hash)]
+struct Key {
+    id: u32,
+    #[derivative(eq_hash="ignore")]
+    cached_len: usize,
+    #[derivative(eq_hash(compare_with="eq_ignore_case", hash_with="hash_ignore_case"))]
+    name: String,
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(eq_hash(feature_allow_slow_enum, bound=""))]
+enum Shape<T> {
+    Circle(u32),
+    #[allow(dead_code)]
+    Other(#[derivative(eq_hash="ignore")] T),
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+fn hash_ignore_case<H: Hasher>(s: &str, state: &mut H) {
+    s.to_ascii_lowercase().hash(state)
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn assert_eq_impl<T: Eq>() {}
+
+#[test]
+fn main() {
+    let a = Key {
+        id: 1,
+        cached_len: 3,
+        name: "Foo".to_string(),
+    };
+    let b = Key {
+        id: 1,
+        cached_len: 0,
+        name: "FOO".to_string(),
+    };
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq_impl::<Key>();
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(set.contains(&b));
+
+    struct NoTraits;
+    assert!(Shape::Circle::<NoTraits>(1) == Shape::Circle(1));
+    assert_eq!(hash(&Shape::Circle::<NoTraits>(1)), hash(&Shape::Circle::<NoTraits>(1)));
+    assert_eq_impl::<Shape<NoTraits>>();
+}
@@ -1,3 +1,7 @@
+#![allow(ambiguous_wide_pointer_comparisons)]
+#![allow(bare_trait_objects)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -0,0 +1,52 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::BTreeMap;
+
+#[derive(Derivative)]
+#[derivative(FromIterator, Extend)]
+struct Bytes(Vec<u8>);
+
+#[derive(Derivative)]
+#[derivative(Extend(item = "(&'a str, u32)"))]
+struct Registry<'a> {
+    name: &'static str,
+    #[derivative(Extend = "target")]
+    entries: BTreeMap<&'a str, u32>,
+}
+
+#[derive(Derivative)]
+#[derivative(Extend(bound = "T: Copy"))]
+struct Log<T> {
+    #[derivative(Extend(target))]
+    lines: Vec<T>,
+    limit: usize,
+}
+
+#[test]
+fn main() {
+    let mut bytes: Bytes = (1..3).collect();
+    bytes.extend(vec![3, 4]);
+    // `Vec<u8>` can also be extended with `&u8`.
+    bytes.extend(&[5, 6]);
+    assert_eq!(bytes.0, [1, 2, 3, 4, 5, 6]);
+
+    let mut registry = Registry {
+        name: "registry",
+        entries: BTreeMap::new(),
+    };
+    registry.extend(vec![("b", 2), ("a", 1)]);
+    assert_eq!(registry.entries.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+    assert_eq!(registry.name, "registry");
+
+    let mut log = Log {
+        lines: vec!["a"],
+        limit: 2,
+    };
+    log.extend(Some("b"));
+    assert_eq!(log.lines, ["a", "b"]);
+    assert_eq!(log.limit, 2);
+}
@@ -0,0 +1,43 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Derivative)]
+#[derivative(FromIterator)]
+struct Bytes(Vec<u8>);
+
+#[derive(Derivative)]
+#[derivative(FromIterator)]
+struct Text(String);
+
+#[derive(Derivative)]
+#[derivative(FromIterator(item = "(&'a str, u32)"))]
+struct Registry<'a> {
+    entries: BTreeMap<&'a str, u32>,
+}
+
+#[derive(Derivative)]
+#[derivative(FromIterator(bound = "T: Eq + std::hash::Hash"))]
+struct Tags<T>(HashSet<T>);
+
+#[test]
+fn main() {
+    let bytes: Bytes = (1..4).collect();
+    assert_eq!(bytes.0, [1, 2, 3]);
+
+    // `String` can be collected from `char` and from `&str`.
+    let text: Text = "abc".chars().rev().collect();
+    assert_eq!(text.0, "cba");
+    let text: Text = vec!["ab", "c"].into_iter().collect();
+    assert_eq!(text.0, "abc");
+
+    let registry: Registry = vec![("b", 2), ("a", 1)].into_iter().collect();
+    assert_eq!(registry.entries.keys().collect::<Vec<_>>(), [&"a", &"b"]);
+
+    let tags: Tags<&str> = "a b a".split(' ').collect();
+    assert_eq!(tags.0.len(), 2);
+}
@@ -0,0 +1,69 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::str::FromStr;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(FromStr)]
+enum Level {
+    Error,
+    #[derivative(FromStr(rename = "warning", alias = "warn", alias = "w"))]
+    Warning,
+    #[derivative(FromStr(alias = "information"))]
+    Info,
+}
+
+#[derive(Debug, PartialEq)]
+struct UnknownColor(String);
+
+impl<'a> From<&'a str> for UnknownColor {
+    fn from(s: &'a str) -> Self {
+        UnknownColor(s.to_string())
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(FromStr(error = "UnknownColor"))]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Derivative)]
+#[derivative(FromStr)]
+enum Empty {}
+
+#[test]
+fn main() {
+    assert_eq!("Error".parse(), Ok(Level::Error));
+    assert_eq!("warning".parse(), Ok(Level::Warning));
+    assert_eq!("warn".parse(), Ok(Level::Warning));
+    assert_eq!("w".parse(), Ok(Level::Warning));
+    assert_eq!("Info".parse(), Ok(Level::Info));
+    assert_eq!("information".parse(), Ok(Level::Info));
+    assert_eq!("Warning".parse::<Level>(), Err(ParseLevelError));
+    assert_eq!("error".parse::<Level>(), Err(ParseLevelError));
+    assert_eq!(
+        ParseLevelError.to_string(),
+        "invalid `Level`, expected one of `Error`, `warning`, `warn`, `w`, `Info`, `information`"
+    );
+
+    assert_eq!(Color::from_str("Red"), Ok(Color::Red));
+    assert_eq!(
+        Color::from_str("Blue"),
+        Err(UnknownColor("Blue".to_string()))
+    );
+
+    assert!(Empty::from_str("").is_err());
+    assert_eq!(
+        ParseEmptyError.to_string(),
+        "`Empty` has no variant to parse"
+    );
+    #[cfg(not(feature = "use_core"))]
+    {
+        let _: &dyn std::error::Error = &ParseEmptyError;
+    }
+}
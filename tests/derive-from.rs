@@ -0,0 +1,51 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt::Display;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(From = "into")]
+struct Meters(f64);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(From)]
+struct Name {
+    value: String,
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(From(into))]
+struct Ids<T>(Vec<T>);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(From(bound = "T: Display"))]
+struct Label<T>(T);
+
+#[derive(Derivative)]
+#[derivative(From)]
+struct Borrowed<'a, T: 'a + ?Sized>(&'a T);
+
+#[test]
+fn main() {
+    assert_eq!(Meters::from(1.5), Meters(1.5));
+    let meters: Meters = 2.0.into();
+    assert_eq!(f64::from(meters), 2.0);
+
+    assert_eq!(
+        Name::from("foo".to_string()),
+        Name {
+            value: "foo".to_string()
+        }
+    );
+
+    let ids: Ids<u8> = vec![1, 2].into();
+    assert_eq!(Vec::from(ids), vec![1, 2]);
+
+    assert_eq!(Label::from(1), Label(1));
+
+    let borrowed: Borrowed<str> = "foo".into();
+    assert_eq!(borrowed.0, "foo");
+}
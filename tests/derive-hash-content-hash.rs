@@ -0,0 +1,51 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Hash(content_hash))]
+struct Foo {
+    foo: u8,
+    #[derivative(Hash="ignore")]
+    bar: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(Hash(content_hash_seed="42"))]
+struct Seeded<T> {
+    foo: T,
+}
+
+#[derive(Derivative)]
+#[derivative(Hash(content_hash))]
+struct Packet {
+    id: u32,
+    payload: Vec<u8>,
+}
+
+#[test]
+fn main() {
+    // The expected values are 64-bit FNV-1a hashes of the seed, then of the bytes written by
+    // `Hash`, with the integers in little-endian order and `usize` as `u64`.
+    assert_eq!(Foo { foo: 7, bar: 0 }.content_hash(), 0xe604_7f3a_2490_24a6);
+    assert_eq!(Foo { foo: 7, bar: 0 }.content_hash(), Foo { foo: 7, bar: 1 }.content_hash());
+    assert!(Foo { foo: 7, bar: 0 }.content_hash() != Foo { foo: 8, bar: 0 }.content_hash());
+    assert_eq!(Foo { foo: 7, bar: 0 }.content_hash_with_seed(0), 0xe604_7f3a_2490_24a6);
+    assert!(Foo { foo: 7, bar: 0 }.content_hash_with_seed(1) != 0xe604_7f3a_2490_24a6);
+
+    assert_eq!(Seeded { foo: "foo" }.content_hash(), 0x3136_8fbf_36d9_8312);
+    assert_eq!(
+        Seeded { foo: "foo" }.content_hash(),
+        Seeded { foo: "foo" }.content_hash_with_seed(42)
+    );
+
+    let packet = Packet {
+        id: 0x0102_0304,
+        payload: vec![1, 2],
+    };
+    assert_eq!(packet.content_hash(), 0x8f35_71f6_ba05_6e40);
+}
@@ -0,0 +1,41 @@
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Derivative)]
+#[derivative(Hash(type_tag))]
+struct UserId(u64);
+
+#[derive(Derivative)]
+#[derivative(Hash(type_tag))]
+struct GroupId(u64);
+
+#[derive(Derivative)]
+#[derivative(Hash(type_tag="0x1234"))]
+struct Tagged {
+    a: u64,
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    assert!(hash(&UserId(42)) != hash(&GroupId(42)));
+    assert_eq!(hash(&UserId(42)), hash(&UserId(42)));
+
+    let mut hasher = DefaultHasher::new();
+    concat!(module_path!(), "::", "UserId").hash(&mut hasher);
+    42u64.hash(&mut hasher);
+    assert_eq!(hash(&UserId(42)), hasher.finish());
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u64(0x1234);
+    1u64.hash(&mut hasher);
+    assert_eq!(hash(&Tagged { a: 1 }), hasher.finish());
+}
@@ -1,5 +1,7 @@
 //! This tests that we compute the same hash as `derive(Hash)`.
 
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+mod time {
+    #[derive(Debug, PartialEq, Hash)]
+    pub struct Instant(pub u64);
+}
+
+use time::Instant;
+
+struct NoTraits;
+
+#[derive(Derivative)]
+#[derivative(Debug, Hash, PartialEq, ignore_types(PhantomData, Instant))]
+struct Request<T> {
+    id: u64,
+    created: Instant,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, PartialEq, ignore_types="time::Instant, Option<Instant>")]
+struct Paths {
+    id: u64,
+    created: time::Instant,
+    updated: Option<Instant>,
+    deleted: Option<u64>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+enum Event {
+    #[derivative(ignore_types(Instant))]
+    Created(u64, Instant),
+    Deleted(u64, Instant),
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    let request = Request::<NoTraits> {
+        id: 1,
+        created: Instant(1),
+        _marker: PhantomData,
+    };
+    let other = Request::<NoTraits> {
+        id: 1,
+        created: Instant(2),
+        _marker: PhantomData,
+    };
+    assert_eq!(format!("{:?}", request), "Request { id: 1 }");
+    assert!(request == other);
+    assert_eq!(hash(&request), hash(&other));
+
+    let paths = Paths {
+        id: 1,
+        created: Instant(1),
+        updated: Some(Instant(2)),
+        deleted: Some(3),
+    };
+    assert_eq!(format!("{:?}", paths), "Paths { id: 1, deleted: Some(3) }");
+
+    assert_eq!(format!("{:?}", Event::Created(1, Instant(1))), "Created(1)");
+    assert_eq!(format!("{:?}", Event::Deleted(1, Instant(1))), "Deleted(1, Instant(1))");
+}
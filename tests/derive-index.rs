@@ -0,0 +1,52 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::HashMap;
+
+#[derive(Derivative)]
+#[derivative(Index, IndexMut)]
+struct Bytes(Vec<u8>);
+
+#[derive(Derivative)]
+#[derivative(Index(index = "&'a str"))]
+struct Registry<'a> {
+    name: &'static str,
+    #[derivative(Index = "target")]
+    entries: HashMap<&'a str, u32>,
+}
+
+#[derive(Derivative)]
+#[derivative(Index, IndexMut(bound = "T: Copy"))]
+struct Grid<T> {
+    width: usize,
+    #[derivative(Index(target))]
+    cells: Vec<T>,
+}
+
+#[test]
+fn main() {
+    let mut bytes = Bytes(vec![1, 2, 3]);
+    bytes[0] = 4;
+    assert_eq!(bytes[0], 4);
+    assert_eq!(&bytes[1..], &[2, 3]);
+
+    let mut entries = HashMap::new();
+    entries.insert("a", 1);
+    let registry = Registry {
+        name: "registry",
+        entries,
+    };
+    assert_eq!(registry["a"], 1);
+    assert_eq!(registry.name, "registry");
+
+    let mut grid = Grid {
+        width: 2,
+        cells: vec![0; 4],
+    };
+    grid[3] = 1;
+    assert_eq!(grid[2..], [0, 1]);
+    assert_eq!(grid.width, 2);
+}
@@ -0,0 +1,43 @@
+#![cfg(not(feature = "use_core"))]
+
+#[macro_use]
+extern crate derivative;
+
+use std::io::{Cursor, Read, Write};
+
+#[derive(Derivative)]
+#[derivative(Read, Write)]
+struct Stream(Cursor<Vec<u8>>);
+
+#[derive(Derivative)]
+#[derivative(Read, Write(bound = "W: Send"))]
+struct Tee<R, W> {
+    #[derivative(Read = "target")]
+    reader: R,
+    #[derivative(Write(target))]
+    writer: W,
+    label: &'static str,
+}
+
+#[test]
+fn main() {
+    let mut stream = Stream(Cursor::new(Vec::new()));
+    write!(stream, "{}-{}", 1, 2).unwrap();
+    stream.flush().unwrap();
+    stream.0.set_position(0);
+    let mut read = String::new();
+    stream.read_to_string(&mut read).unwrap();
+    assert_eq!(read, "1-2");
+
+    let mut tee = Tee {
+        reader: &b"abc"[..],
+        writer: Vec::new(),
+        label: "tee",
+    };
+    let mut buf = [0; 2];
+    tee.read_exact(&mut buf).unwrap();
+    tee.write_all(&buf).unwrap();
+    assert_eq!(tee.writer, b"ab");
+    assert_eq!(tee.reader, b"c");
+    assert_eq!(tee.label, "tee");
+}
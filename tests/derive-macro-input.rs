@@ -0,0 +1,44 @@
+//! The code generated for each field has the span of the field, which comes from the macro
+//! below. The locals of the generated code must still resolve.
+
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+macro_rules! define {
+    ($name:ident, $tuple:ident, $field:ident) => {
+        #[derive(Derivative)]
+        #[derivative(Debug, Clone(clone_from), Hash, PartialEq, Eq, Default)]
+        struct $name {
+            $field: u8,
+            #[derivative(Hash(deref), PartialEq(deref))]
+            name: Box<str>,
+        }
+
+        #[derive(Derivative)]
+        #[derivative(Debug, Clone(clone_from), Hash, PartialEq, Default)]
+        struct $tuple(u8, String);
+    };
+}
+
+define!(Foo, Bar, foo);
+
+#[test]
+fn main() {
+    let foo = Foo {
+        foo: 42,
+        name: "foo".into(),
+    };
+    let mut copy = Foo::default();
+    copy.clone_from(&foo);
+    assert_eq!(format!("{:?}", copy), "Foo { foo: 42, name: \"foo\" }");
+    assert!(copy == foo);
+
+    let bar = Bar(1, "bar".into());
+    assert_eq!(format!("{:?}", bar.clone()), "Bar(1, \"bar\")");
+    assert!(bar != Bar::default());
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::marker::PhantomData;
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Neg)]
+struct Vector<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Not)]
+struct Mask(u8);
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Neg, Not)]
+struct Reading {
+    #[derivative(Not(with = "flip_sign"))]
+    value: i32,
+    #[derivative(Neg = "ignore", Not = "ignore")]
+    unit: &'static str,
+}
+
+#[derive(Debug, PartialEq, Derivative)]
+#[derivative(Neg(bound = "T: std::ops::Neg<Output = T> + Copy"))]
+struct Meters<T>(T, PhantomData<T>);
+
+fn flip_sign(value: i32) -> i32 {
+    -value
+}
+
+#[test]
+fn main() {
+    assert_eq!(-Vector { x: 1, y: -2 }, Vector { x: -1, y: 2 });
+    assert_eq!(!Mask(0b1010_0101), Mask(0b0101_1010));
+
+    let reading = Reading {
+        value: 3,
+        unit: "m",
+    };
+    assert_eq!(
+        -reading,
+        Reading {
+            value: -3,
+            unit: "m",
+        }
+    );
+    let reading = Reading {
+        value: 3,
+        unit: "m",
+    };
+    assert_eq!(
+        !reading,
+        Reading {
+            value: -3,
+            unit: "m",
+        }
+    );
+
+    assert_eq!(-Meters(1.5, PhantomData), Meters(-1.5, PhantomData));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "test-nightly")]
+#![cfg_attr(feature = "test-nightly", feature(never_type))]
+#![deny(warnings)]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(clone_from),
+    Debug,
+    Hash,
+    PartialEq="feature_allow_slow_enum",
+    Eq
+)]
+enum Maybe<T> {
+    Nothing(!),
+    Just(T),
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct Never {
+    never: !,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_from), Debug, Hash, PartialEq, Eq)]
+struct NeverToo(u8, !);
+
+#[test]
+fn main() {
+    let mut a = Maybe::Just(1);
+    a.clone_from(&Maybe::Just(2));
+    assert!(a == Maybe::Just(2));
+    assert_eq!(format!("{:?}", a.clone()), "Just(2)");
+}
@@ -0,0 +1,147 @@
+//! The generated code must not trigger any warning in crates that deny them: ignored fields,
+//! single-variant and empty enums, and functions that are only called by the generated code.
+
+#![deny(warnings, unused, unreachable_patterns, unused_mut, unused_variables)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+fn fmt_id(id: &u32, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "#{}", id)
+}
+
+fn clone_id(id: &u32) -> u32 {
+    *id
+}
+
+fn hash_id<H: Hasher>(id: &u32, state: &mut H) {
+    id.hash(state)
+}
+
+fn eq_id(a: &u32, b: &u32) -> bool {
+    a == b
+}
+
+fn cmp_id(a: &u32, b: &u32) -> Option<Ordering> {
+    a.partial_cmp(b)
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, Clone(clone_from), Hash, PartialEq, Eq, PartialOrd, Default)]
+struct Ignored {
+    #[derivative(
+        Debug = "ignore",
+        Hash = "ignore",
+        PartialEq = "ignore",
+        PartialOrd = "ignore"
+    )]
+    cache: u8,
+    name: String,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, Clone, Hash, PartialEq, Eq, PartialOrd)]
+struct With(
+    #[derivative(
+        Debug(format_with = "fmt_id"),
+        Clone(clone_with = "clone_id"),
+        Hash(hash_with = "hash_id"),
+        PartialEq(compare_with = "eq_id"),
+        PartialOrd(partial_cmp_with = "cmp_id")
+    )]
+    u32,
+);
+
+#[derive(Derivative)]
+#[derivative(
+    Debug,
+    Clone(clone_from),
+    Hash,
+    PartialEq = "feature_allow_slow_enum",
+    Eq,
+    PartialOrd = "feature_allow_slow_enum",
+    Default
+)]
+enum Single {
+    #[derivative(Default)]
+    Only(
+        #[derivative(
+            Debug = "ignore",
+            Hash = "ignore",
+            PartialEq = "ignore",
+            PartialOrd = "ignore"
+        )]
+        u8,
+    ),
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug,
+    Clone(clone_from),
+    PartialEq = "feature_allow_slow_enum",
+    Eq,
+    PartialOrd = "feature_allow_slow_enum",
+    Default
+)]
+enum Multi {
+    #[derivative(Default)]
+    Unit,
+    #[derivative(Debug = "transparent")]
+    Tuple(#[derivative(PartialEq = "ignore", PartialOrd = "ignore")] u8),
+    Struct { x: String, y: Vec<u8> },
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug,
+    Clone(clone_from),
+    Hash,
+    PartialEq = "feature_allow_slow_enum",
+    Eq,
+    PartialOrd = "feature_allow_slow_enum"
+)]
+enum Empty {}
+
+#[derive(Derivative)]
+#[derivative(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Default(new))]
+struct Unit;
+
+fn compare(a: &Empty, b: &Empty) -> Option<Ordering> {
+    a.partial_cmp(b)
+}
+
+#[test]
+fn main() {
+    let ignored = Ignored {
+        cache: 1,
+        name: "foo".into(),
+    };
+    let mut other = Ignored::default();
+    other.clone_from(&ignored);
+    assert_eq!(other.cache, 1);
+    assert_eq!(format!("{:?}", other), "Ignored { name: \"foo\" }");
+
+    assert_eq!(format!("{:?}", With(42).clone()), "With(#42)");
+    assert!(With(1) < With(2));
+
+    assert_eq!(Single::default(), Single::Only(1));
+    assert!(Multi::Unit < Multi::Tuple(1));
+    assert_eq!(format!("{:?}", Multi::Tuple(1).clone()), "1");
+    let multi = Multi::Struct {
+        x: "x".into(),
+        y: vec![1],
+    };
+    assert_eq!(format!("{:?}", multi), "Struct { x: \"x\", y: [1] }");
+
+    let _ = compare;
+    assert_eq!(Unit::new(), Unit);
+}
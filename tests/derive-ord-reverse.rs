@@ -0,0 +1,88 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+
+#[derive(Derivative, PartialEq, Eq)]
+#[derivative(PartialOrd, Ord)]
+struct Task {
+    name: &'static str,
+    #[derivative(PartialOrd(reverse, priority = "1"), Ord(reverse, priority = "1"))]
+    urgency: u8,
+    #[derivative(PartialOrd(priority = "-1"), Ord(priority = "-1"))]
+    id: u32,
+    #[derivative(PartialOrd(reverse), Ord(reverse))]
+    retries: u8,
+}
+
+fn task(name: &'static str, urgency: u8, id: u32, retries: u8) -> Task {
+    Task {
+        name,
+        urgency,
+        id,
+        retries,
+    }
+}
+
+fn cmp_len(a: &&str, b: &&str) -> Ordering {
+    a.len().cmp(&b.len())
+}
+
+fn partial_cmp_len(a: &&str, b: &&str) -> Option<Ordering> {
+    Some(cmp_len(a, b))
+}
+
+#[derive(Derivative, PartialEq, Eq)]
+#[derivative(PartialOrd = "feature_allow_slow_enum", Ord = "feature_allow_slow_enum")]
+enum Word {
+    Long(
+        #[derivative(
+            PartialOrd(partial_cmp_with = "partial_cmp_len", reverse),
+            Ord(cmp_with = "cmp_len", reverse)
+        )]
+        &'static str,
+    ),
+    Short,
+}
+
+#[test]
+fn main() {
+    let mut tasks = [
+        task("b", 1, 0, 0),
+        task("a", 1, 0, 0),
+        task("c", 2, 0, 0),
+        task("a", 1, 0, 1),
+        task("a", 1, 1, 0),
+    ];
+    tasks.sort();
+    let sorted: Vec<_> = tasks
+        .iter()
+        .map(|t| (t.name, t.urgency, t.id, t.retries))
+        .collect();
+    assert_eq!(
+        sorted,
+        [
+            ("c", 2, 0, 0),
+            ("a", 1, 0, 1),
+            ("a", 1, 0, 0),
+            ("a", 1, 1, 0),
+            ("b", 1, 0, 0),
+        ]
+    );
+    for a in &tasks {
+        for b in &tasks {
+            assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        }
+    }
+
+    assert_eq!(Word::Long("a").cmp(&Word::Long("bb")), Ordering::Greater);
+    assert_eq!(
+        Word::Long("a").partial_cmp(&Word::Long("bb")),
+        Some(Ordering::Greater)
+    );
+    // `reverse` only applies to the field, not to the order of the variants.
+    assert!(Word::Long("a") < Word::Short);
+}
@@ -0,0 +1,55 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+
+/// Declared alphabetically, ordered by severity.
+#[derive(Debug, Derivative, PartialEq, Eq)]
+#[derivative(
+    PartialOrd = "feature_allow_slow_enum",
+    Ord = "feature_allow_slow_enum"
+)]
+enum Level {
+    #[derivative(PartialOrd(order = "3"), Ord(order = "3"))]
+    Error,
+    #[derivative(PartialOrd(order = "1"), Ord(order = "1"))]
+    Info,
+    #[derivative(PartialOrd(order = "0"), Ord(order = "0"))]
+    Trace(u8),
+    #[derivative(PartialOrd(order = "2"), Ord(order = "2"))]
+    Warning { code: u32 },
+}
+
+#[test]
+fn main() {
+    let mut levels = [
+        Level::Warning { code: 2 },
+        Level::Error,
+        Level::Trace(1),
+        Level::Info,
+        Level::Trace(0),
+        Level::Warning { code: 1 },
+    ];
+    levels.sort();
+    assert_eq!(
+        levels,
+        [
+            Level::Trace(0),
+            Level::Trace(1),
+            Level::Info,
+            Level::Warning { code: 1 },
+            Level::Warning { code: 2 },
+            Level::Error,
+        ]
+    );
+
+    for a in &levels {
+        for b in &levels {
+            assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        }
+    }
+    assert_eq!(Level::Error.cmp(&Level::Info), Ordering::Greater);
+}
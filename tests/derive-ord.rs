@@ -0,0 +1,133 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+macro_rules! define {
+    ($kw:tt $($rest:tt)*) => {
+        #[derive(Derivative, PartialEq, Eq, PartialOrd)]
+        #[derivative(Ord="feature_allow_slow_enum")]
+        $kw Ours $($rest)*
+
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        $kw Theirs $($rest)*
+    }
+}
+
+/// Compare every pair of `values` with the implementation of derivative and the built-in one.
+macro_rules! check {
+    ($t:ident => $($value:expr),*) => {{
+        let ours = {
+            type $t = Ours;
+            [$($value),*]
+        };
+        let theirs = {
+            type $t = Theirs;
+            [$($value),*]
+        };
+        for (i, a) in ours.iter().enumerate() {
+            for (j, b) in ours.iter().enumerate() {
+                assert_eq!(a.cmp(b), theirs[i].cmp(&theirs[j]));
+            }
+        }
+    }};
+}
+
+struct NotOrd;
+
+#[derive(Derivative)]
+#[derivative(PartialEq, Eq, PartialOrd, Ord)]
+struct Id<T> {
+    id: u32,
+    #[derivative(
+        PartialEq = "ignore",
+        PartialOrd = "ignore",
+        Ord = "ignore"
+    )]
+    owner: Rc<T>,
+}
+
+/// Compare strings as a case-insensitive collation would.
+fn cmp_ignore_case(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+fn partial_cmp_ignore_case(a: &str, b: &str) -> Option<Ordering> {
+    Some(cmp_ignore_case(a, b))
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq, Eq, PartialOrd, Ord)]
+struct Name {
+    #[derivative(
+        PartialEq(compare_with = "eq_ignore_case"),
+        PartialOrd(partial_cmp_with = "partial_cmp_ignore_case"),
+        Ord(cmp_with = "cmp_ignore_case")
+    )]
+    name: String,
+    number: u8,
+}
+
+#[test]
+fn main() {
+    {
+        define! {
+            struct { foo: u8, bar: i32 }
+        }
+        check!(
+            T => T { foo: 0, bar: 1 },
+            T { foo: 0, bar: -1 },
+            T { foo: 1, bar: -1 },
+            T { foo: 1, bar: 1 }
+        );
+    }
+
+    {
+        define! {
+            enum {
+                A(u8),
+                B { foo: i32, bar: bool },
+                C,
+            }
+        }
+        check!(
+            T => T::A(1),
+            T::A(0),
+            T::B { foo: 1, bar: false },
+            T::B { foo: 1, bar: true },
+            T::C,
+            T::A(2)
+        );
+    }
+
+    {
+        define! {
+            enum { Only(u8) }
+        }
+        check!(T => T::Only(1), T::Only(0));
+    }
+
+    let a = Id { id: 1, owner: Rc::new(NotOrd) };
+    let b = Id { id: 2, owner: Rc::new(NotOrd) };
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(a.cmp(&a), Ordering::Equal);
+
+    let mut names = [
+        Name { name: "b".into(), number: 0 },
+        Name { name: "A".into(), number: 1 },
+        Name { name: "a".into(), number: 0 },
+    ];
+    names.sort();
+    let names: Vec<_> = names.iter().map(|n| (&n.name[..], n.number)).collect();
+    assert_eq!(names, [("a", 0), ("A", 1), ("b", 0)]);
+}
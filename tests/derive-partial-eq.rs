@@ -1,3 +1,8 @@
+#![allow(ambiguous_wide_pointer_comparisons)]
+#![allow(bare_trait_objects)]
+#![allow(clippy::default_constructed_unit_structs)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
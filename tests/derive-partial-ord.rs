@@ -0,0 +1,134 @@
+#![allow(ambiguous_wide_pointer_comparisons)]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+macro_rules! define {
+    ($kw:tt $($rest:tt)*) => {
+        #[derive(Derivative, PartialEq)]
+        #[derivative(PartialOrd="feature_allow_slow_enum")]
+        $kw Ours $($rest)*
+
+        #[derive(PartialEq, PartialOrd)]
+        $kw Theirs $($rest)*
+    }
+}
+
+/// Compare every pair of `values` with the implementation of derivative and the built-in one.
+macro_rules! check {
+    ($t:ident => $($value:expr),*) => {{
+        let ours = {
+            type $t = Ours;
+            [$($value),*]
+        };
+        let theirs = {
+            type $t = Theirs;
+            [$($value),*]
+        };
+        for (i, a) in ours.iter().enumerate() {
+            for (j, b) in ours.iter().enumerate() {
+                assert_eq!(a.partial_cmp(b), theirs[i].partial_cmp(&theirs[j]));
+            }
+        }
+    }};
+}
+
+struct NotOrd;
+
+#[derive(Derivative)]
+#[derivative(PartialEq, PartialOrd)]
+struct Id<T> {
+    id: u32,
+    #[derivative(PartialEq = "ignore", PartialOrd = "ignore")]
+    owner: Rc<T>,
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq, PartialOrd)]
+struct WithPtr<T: ?Sized> {
+    #[derivative(PartialEq(bound = ""), PartialOrd(bound = ""))]
+    foo: *const T,
+}
+
+fn reverse(a: &u8, b: &u8) -> Option<Ordering> {
+    b.partial_cmp(a)
+}
+
+#[derive(Derivative, PartialEq)]
+#[derivative(PartialOrd)]
+struct Reversed {
+    #[derivative(PartialOrd(partial_cmp_with = "reverse"))]
+    foo: u8,
+    bar: u8,
+}
+
+#[test]
+fn main() {
+    {
+        define! {
+            struct { foo: u8, bar: i32 }
+        }
+        check!(
+            T => T { foo: 0, bar: 1 },
+            T { foo: 0, bar: -1 },
+            T { foo: 1, bar: -1 },
+            T { foo: 1, bar: 1 }
+        );
+    }
+
+    {
+        define! {
+            struct (f64, u8);
+        }
+        check!(
+            T => T { 0: 0.0, 1: 1 },
+            T { 0: f64::NAN, 1: 0 },
+            T { 0: 1.0, 1: 0 },
+            T { 0: 0.0, 1: 0 }
+        );
+    }
+
+    {
+        define! {
+            enum {
+                A(u8),
+                B { foo: i32, bar: bool },
+                C,
+            }
+        }
+        check!(
+            T => T::A(1),
+            T::A(0),
+            T::B { foo: 1, bar: false },
+            T::B { foo: 1, bar: true },
+            T::C,
+            T::A(2)
+        );
+    }
+
+    {
+        define! {
+            enum { Only(u8) }
+        }
+        check!(T => T::Only(1), T::Only(0));
+    }
+
+    let a = Id { id: 1, owner: Rc::new(NotOrd) };
+    let b = Id { id: 2, owner: Rc::new(NotOrd) };
+    assert!(a < b);
+    assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+
+    let x = 1;
+    let y = 2;
+    assert!(WithPtr { foo: &x as *const i32 } != WithPtr { foo: &y as *const i32 });
+    assert!(WithPtr { foo: &x as *const i32 } <= WithPtr { foo: &x as *const i32 });
+
+    assert!(Reversed { foo: 2, bar: 0 } < Reversed { foo: 1, bar: 0 });
+    assert!(Reversed { foo: 1, bar: 0 } < Reversed { foo: 1, bar: 1 });
+}
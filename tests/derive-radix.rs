@@ -0,0 +1,36 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Binary, LowerHex, Octal, UpperHex)]
+struct Flags(u8);
+
+#[derive(Derivative)]
+#[derivative(LowerHex, UpperHex)]
+struct Address<T> {
+    raw: T,
+}
+
+#[derive(Derivative)]
+#[derivative(LowerHex(bound = "T: std::fmt::LowerHex + Copy"))]
+struct Mask<T>(T);
+
+#[test]
+fn main() {
+    let flags = Flags(0b1010_0101);
+    assert_eq!(format!("{:b}", flags), "10100101");
+    assert_eq!(format!("{:x}", flags), "a5");
+    assert_eq!(format!("{:o}", flags), "245");
+    assert_eq!(format!("{:X}", flags), "A5");
+    // The flags of the formatter are passed to the field.
+    assert_eq!(format!("{:#010b}", flags), "0b10100101");
+    assert_eq!(format!("{:#06x}", flags), "0x00a5");
+
+    let address = Address { raw: 0xdead_u32 };
+    assert_eq!(format!("{:x}/{:X}", address, address), "dead/DEAD");
+
+    assert_eq!(format!("{:x}", Mask(255u16)), "ff");
+}
@@ -0,0 +1,62 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[cfg(feature = "use_core")]
+extern crate core;
+
+extern crate derivative;
+
+mod facade {
+    pub use derivative::Derivative as Derive;
+}
+
+use facade::Derive;
+
+#[derive(Derive)]
+#[derivative(Debug, Default)]
+struct Foo {
+    foo: u8,
+    #[derivative(Debug="ignore", Default(value="42"))]
+    bar: u8,
+}
+
+macro_rules! define {
+    ($(#[$attr:meta])* struct $name:ident { $($(#[$fattr:meta])* $field:ident: $ty:ty),* $(,)* }) => {
+        #[derive(Derive)]
+        $(#[$attr])*
+        struct $name {
+            $($(#[$fattr])* $field: $ty),*
+        }
+    };
+}
+
+define! {
+    #[derivative(Debug, Clone, PartialEq, Hash)]
+    struct Bar {
+        foo: u8,
+        #[derivative(Debug="ignore", PartialEq="ignore", Hash="ignore")]
+        bar: u8,
+    }
+}
+
+macro_rules! define_generic {
+    ($name:ident, $t:ident) => {
+        #[derive(Derive)]
+        #[derivative(Debug, Default(bound=""))]
+        struct $name<$t> {
+            foo: Option<$t>,
+        }
+    };
+}
+
+define_generic!(Baz, T);
+
+struct NoDefault;
+
+#[test]
+fn main() {
+    assert_eq!(format!("{:?}", Foo::default()), "Foo { foo: 0 }");
+    assert_eq!(format!("{:?}", Bar { foo: 1, bar: 2 }), "Bar { foo: 1 }");
+    assert!(Bar { foo: 1, bar: 2 } == Bar { foo: 1, bar: 3 }.clone());
+    assert!(Baz::<NoDefault>::default().foo.is_none());
+    assert_eq!(format!("{:?}", Baz::<u8>::default()), "Baz { foo: None }");
+}
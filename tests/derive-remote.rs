@@ -0,0 +1,119 @@
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[macro_use]
+extern crate derivative;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+mod other {
+    pub struct Duration {
+        pub secs: u64,
+        pub nanos: u32,
+    }
+
+    pub enum Shape<T> {
+        Circle(T),
+        Rect { width: T, height: T },
+        Empty,
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, Hash, PartialEq, PartialOrd, Ord, remote = "other::Duration")]
+struct Duration {
+    secs: u64,
+    #[derivative(Debug = "ignore", PartialOrd = "ignore", Ord = "ignore")]
+    nanos: u32,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug,
+    PartialEq = "feature_allow_slow_enum",
+    PartialOrd = "feature_allow_slow_enum",
+    remote = "other::Shape"
+)]
+enum Shape<T> {
+    Circle(T),
+    Rect { width: T, height: T },
+    Empty,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+struct Timeout {
+    #[derivative(
+        Debug(format_with = "Duration::debug_remote"),
+        Hash(hash_with = "Duration::hash_remote"),
+        PartialEq(compare_with = "Duration::eq_remote"),
+        PartialOrd(partial_cmp_with = "Duration::partial_cmp_remote"),
+        Ord(cmp_with = "Duration::cmp_remote")
+    )]
+    duration: other::Duration,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, PartialEq)]
+struct Drawing {
+    #[derivative(
+        Debug(format_with = "Shape::debug_remote"),
+        PartialEq(compare_with = "Shape::eq_remote")
+    )]
+    shape: other::Shape<u8>,
+}
+
+fn timeout(secs: u64, nanos: u32) -> Timeout {
+    Timeout {
+        duration: other::Duration { secs, nanos },
+    }
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        format!("{:?}", timeout(1, 5)),
+        "Timeout { duration: Duration { secs: 1 } }"
+    );
+    assert!(timeout(1, 5) == timeout(1, 5));
+    assert!(timeout(1, 5) != timeout(1, 6));
+    assert_eq!(hash(&timeout(1, 5)), hash(&timeout(1, 5)));
+    assert_ne!(hash(&timeout(1, 5)), hash(&timeout(1, 6)));
+    assert!(timeout(1, 5) < timeout(2, 0));
+    assert_eq!(
+        timeout(1, 5).partial_cmp(&timeout(1, 6)),
+        Some(std::cmp::Ordering::Equal)
+    );
+    assert_eq!(timeout(3, 0).cmp(&timeout(2, 9)), std::cmp::Ordering::Greater);
+
+    let circle = Drawing {
+        shape: other::Shape::Circle(1),
+    };
+    let rect = Drawing {
+        shape: other::Shape::Rect {
+            width: 1,
+            height: 2,
+        },
+    };
+    let empty = Drawing {
+        shape: other::Shape::Empty,
+    };
+    assert_eq!(format!("{:?}", circle), "Drawing { shape: Circle(1) }");
+    assert_eq!(
+        format!("{:?}", rect),
+        "Drawing { shape: Rect { width: 1, height: 2 } }"
+    );
+    assert_eq!(format!("{:?}", empty), "Drawing { shape: Empty }");
+    assert!(circle == circle);
+    assert!(circle != rect);
+    assert_eq!(
+        Shape::partial_cmp_remote(&circle.shape, &empty.shape),
+        Some(std::cmp::Ordering::Less)
+    );
+}
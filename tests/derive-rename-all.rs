@@ -0,0 +1,51 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::str::FromStr;
+
+#[derive(Derivative, PartialEq)]
+#[derivative(Debug, FromStr, rename_all = "kebab-case")]
+enum Mode {
+    ReadOnly,
+    ReadWrite,
+    #[derivative(FromStr(rename = "append"))]
+    AppendOnly,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, rename_all = "SCREAMING_SNAKE_CASE")]
+enum Event {
+    KeyDown {
+        code: u8,
+    },
+    MouseMove(i32, i32),
+    #[derivative(Debug = "transparent")]
+    Other(&'static str),
+}
+
+#[derive(Derivative)]
+#[derivative(Debug, rename_all = "snake_case")]
+struct NotRenamed;
+
+#[test]
+fn main() {
+    assert_eq!(Mode::from_str("read-only"), Ok(Mode::ReadOnly));
+    assert_eq!(Mode::from_str("read-write"), Ok(Mode::ReadWrite));
+    assert_eq!(Mode::from_str("append"), Ok(Mode::AppendOnly));
+    assert!(Mode::from_str("ReadOnly").is_err());
+    assert!(Mode::from_str("append-only").is_err());
+    assert_eq!(format!("{:?}", Mode::ReadWrite), "read-write");
+    assert_eq!(format!("{:?}", Mode::AppendOnly), "append-only");
+
+    assert_eq!(
+        format!("{:?}", Event::KeyDown { code: 1 }),
+        "KEY_DOWN { code: 1 }"
+    );
+    assert_eq!(format!("{:?}", Event::MouseMove(1, 2)), "MOUSE_MOVE(1, 2)");
+    assert_eq!(format!("{:?}", Event::Other("x")), "\"x\"");
+
+    assert_eq!(format!("{:?}", NotRenamed), "NotRenamed");
+}
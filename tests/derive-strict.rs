@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(strict, Debug, PartialEq)]
+struct Foo {
+    foo: u8,
+    #[derivative(Debug="ignore", PartialEq(compare_with="always_eq"))]
+    bar: u8,
+}
+
+#[derive(Derivative)]
+#[derivative(strict, Debug="transparent")]
+struct Bar(u8);
+
+#[derive(Derivative)]
+#[derivative(strict, Debug, Default)]
+enum Baz {
+    #[derivative(Debug="transparent")]
+    A(u8),
+    #[derivative(Default)]
+    B,
+}
+
+fn always_eq(_: &u8, _: &u8) -> bool {
+    true
+}
+
+#[test]
+fn main() {
+    assert_eq!(format!("{:?}", Foo { foo: 42, bar: 1 }), "Foo { foo: 42 }");
+    assert!(Foo { foo: 42, bar: 1 } == Foo { foo: 42, bar: 2 });
+    assert_eq!(format!("{:?}", Bar(42)), "42");
+    assert_eq!(format!("{:?}", Baz::A(42)), "42");
+    assert_eq!(format!("{:?}", Baz::default()), "B");
+}
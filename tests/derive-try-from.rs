@@ -0,0 +1,91 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+use std::convert::TryFrom;
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(TryFrom)]
+#[repr(u8)]
+enum Opcode {
+    Nop,
+    Load = 4,
+    Store,
+}
+
+#[derive(Debug, PartialEq)]
+struct BadLevel(i64);
+
+impl From<isize> for BadLevel {
+    fn from(value: isize) -> Self {
+        BadLevel(value as i64)
+    }
+}
+
+fn not_reserved(value: &isize) -> Result<(), BadLevel> {
+    if *value == 7 {
+        Err(BadLevel(-1))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(TryFrom(error = "BadLevel", validate_with = "not_reserved"))]
+enum Level {
+    Low = 1,
+    High = 7,
+}
+
+#[derive(Debug, PartialEq)]
+struct NotEven;
+
+fn even(value: &u32) -> Result<(), NotEven> {
+    if value % 2 == 0 {
+        Ok(())
+    } else {
+        Err(NotEven)
+    }
+}
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(TryFrom(error = "NotEven", validate_with = "even"))]
+struct Even(u32);
+
+#[derive(Debug, Derivative, PartialEq)]
+#[derivative(TryFrom(error = "NotEven", validate_with = "even"))]
+struct Named {
+    value: u32,
+}
+
+#[test]
+fn main() {
+    assert_eq!(Opcode::try_from(0), Ok(Opcode::Nop));
+    assert_eq!(Opcode::try_from(4), Ok(Opcode::Load));
+    assert_eq!(Opcode::try_from(5), Ok(Opcode::Store));
+    assert_eq!(Opcode::try_from(1), Err(TryFromOpcodeError(1)));
+    assert_eq!(
+        TryFromOpcodeError(1).to_string(),
+        "`1` is not the discriminant of a variant of `Opcode`"
+    );
+
+    assert_eq!(Level::try_from(1), Ok(Level::Low));
+    assert_eq!(Level::try_from(7), Err(BadLevel(-1)));
+    assert_eq!(Level::try_from(3), Err(BadLevel(3)));
+
+    assert_eq!(Even::try_from(2), Ok(Even(2)));
+    assert_eq!(Even::try_from(3), Err(NotEven));
+    assert_eq!(Named::try_from(4), Ok(Named { value: 4 }));
+}
+
+#[test]
+#[cfg(not(feature = "use_core"))]
+fn error_trait() {
+    let error: Box<dyn std::error::Error> = Box::new(TryFromOpcodeError(2));
+    assert_eq!(
+        error.to_string(),
+        "`2` is not the discriminant of a variant of `Opcode`"
+    );
+}
@@ -0,0 +1,56 @@
+#![allow(clippy::clone_on_copy)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[macro_use]
+extern crate derivative;
+
+use std::fmt;
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(clone_from),
+    Debug,
+    Default(new, reset),
+    Eq,
+    Hash(content_hash),
+    PartialEq
+)]
+struct Foo<T = u32, U: Clone = String> {
+    foo: T,
+    #[derivative(Debug(format_with="fmt_u"), Hash(hash_with="hash_u"), PartialEq(compare_with="eq_u"))]
+    bar: U,
+}
+
+fn fmt_u<U>(_: &U, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("U")
+}
+
+fn hash_u<U, H: std::hash::Hasher>(_: &U, _: &mut H) {}
+
+fn eq_u<U>(_: &U, _: &U) -> bool {
+    true
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Default, PartialEq="feature_allow_slow_enum")]
+enum Bar<T = u8> {
+    #[derivative(Default)]
+    A,
+    B(T),
+}
+
+#[test]
+fn main() {
+    let mut foo: Foo = Foo::new();
+    assert_eq!(format!("{:?}", foo), "Foo { foo: 0, bar: U }");
+    foo.foo = 42;
+    assert!(foo.clone() == foo);
+    let _ = foo.content_hash();
+    foo.reset();
+    assert_eq!(foo.foo, 0);
+
+    let bar: Bar = Bar::B(42);
+    assert_eq!(format!("{:?}", bar), "B(42)");
+    assert!(bar == bar.clone());
+    assert!(Bar::<u8>::default() == Bar::A);
+}
@@ -0,0 +1,37 @@
+#![deny(warnings)]
+#![allow(dead_code)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(Clone(clone_from), Copy, Debug, Hash, PartialEq, Eq)]
+enum Void {}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(clone_from),
+    Debug,
+    Hash,
+    PartialEq="feature_allow_slow_enum",
+    Eq
+)]
+enum Maybe<T> {
+    Nothing(Void),
+    Just(T),
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, Hash, PartialEq, Eq)]
+struct Impossible {
+    void: Void,
+}
+
+#[test]
+fn main() {
+    let mut a = Maybe::Just(1);
+    a.clone_from(&Maybe::Just(2));
+    assert!(a == Maybe::Just(2));
+    assert_eq!(format!("{:?}", a.clone()), "Just(2)");
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "valuable", not(feature = "use_core")))]
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate derivative;
+extern crate valuable;
+
+use std::fmt;
+
+use valuable::{Enumerable, Fields, NamedValues, Structable, Valuable, Value, Visit};
+
+#[derive(Derivative)]
+#[derivative(Debug, Valuable)]
+struct Credentials {
+    user: String,
+    #[derivative(Debug="ignore")]
+    password: String,
+    #[derivative(Debug(format_with="hex"))]
+    key: u32,
+    #[derivative(Valuable="ignore")]
+    cache: Vec<u8>,
+    #[derivative(Debug(mask))]
+    token: u64,
+}
+
+#[derive(Derivative)]
+#[derivative(Valuable)]
+enum Event<T> {
+    Login { user: T, #[derivative(Valuable(with="redact"))] token: u64 },
+    Tick(u8),
+    Quit,
+}
+
+fn hex(value: &u32, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:#x}", value)
+}
+
+fn redact(_: &u64) -> Value<'static> {
+    Value::String("***")
+}
+
+/// Render a value as a string, like a structured logger would.
+struct Render(String);
+
+impl Visit for Render {
+    fn visit_value(&mut self, value: Value) {
+        match value {
+            Value::Structable(v) => {
+                self.0.push_str(v.definition().name());
+                v.visit(self);
+            }
+            Value::Enumerable(v) => {
+                self.0.push_str(v.variant().name());
+                v.visit(self);
+            }
+            Value::String(s) => self.0.push_str(&format!("{:?}", s)),
+            Value::U8(n) => self.0.push_str(&n.to_string()),
+            _ => self.0.push('?'),
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues) {
+        self.0.push_str(" {");
+        for (field, value) in named_values {
+            self.0.push_str(&format!(" {}: ", field.name()));
+            self.visit_value(*value);
+        }
+        self.0.push_str(" }");
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value]) {
+        if !values.is_empty() {
+            self.0.push('(');
+            for value in values {
+                self.visit_value(*value);
+            }
+            self.0.push(')');
+        }
+    }
+}
+
+fn render<V: Valuable>(value: &V) -> String {
+    let mut render = Render(String::new());
+    render.visit_value(value.as_value());
+    render.0
+}
+
+#[test]
+fn main() {
+    let credentials = Credentials {
+        user: "root".to_string(),
+        password: "hunter2".to_string(),
+        key: 255,
+        cache: vec![],
+        token: 42,
+    };
+    assert_eq!(
+        render(&credentials),
+        "Credentials { user: \"root\" key: \"0xff\" token: \"***\" }"
+    );
+    match credentials.definition().fields() {
+        Fields::Named(fields) => assert_eq!(fields.len(), 3),
+        Fields::Unnamed(_) => panic!("the fields are named"),
+    }
+
+    let login = Event::Login { user: "root", token: 42 };
+    assert_eq!(render(&login), "Login { user: \"root\" token: \"***\" }");
+    assert_eq!(render(&Event::<&str>::Tick(3)), "Tick(3)");
+    assert_eq!(render(&Event::<&str>::Quit), "Quit");
+    assert_eq!(Event::<&str>::Quit.definition().variants().len(), 3);
+}
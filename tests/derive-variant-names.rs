@@ -0,0 +1,50 @@
+#[cfg(feature = "use_core")]
+extern crate core;
+
+#[macro_use]
+extern crate derivative;
+
+#[derive(Derivative)]
+#[derivative(VariantNames, FromStr, rename_all = "snake_case")]
+enum Mode {
+    ReadOnly,
+    ReadWrite,
+    #[derivative(VariantNames(rename = "append"))]
+    AppendOnly,
+    #[derivative(FromStr(rename = "rw+"))]
+    Truncate,
+}
+
+#[derive(Derivative)]
+#[derivative(VariantNames)]
+enum Message<T> {
+    Text(T),
+    #[allow(dead_code)]
+    Move { x: i32, y: i32 },
+    Quit,
+}
+
+#[derive(Derivative)]
+#[derivative(VariantNames)]
+enum Never {}
+
+#[test]
+fn main() {
+    assert_eq!(
+        Mode::VARIANT_NAMES,
+        &["read_only", "read_write", "append", "rw+"]
+    );
+    assert_eq!(Mode::ReadOnly.variant_name(), "read_only");
+    assert_eq!(Mode::AppendOnly.variant_name(), "append");
+    assert_eq!(Mode::Truncate.variant_name(), "rw+");
+    for name in &Mode::VARIANT_NAMES[..2] {
+        assert_eq!(name.parse::<Mode>().unwrap().variant_name(), *name);
+    }
+
+    assert_eq!(Message::<u8>::VARIANT_NAMES, &["Text", "Move", "Quit"]);
+    assert_eq!(Message::Text(1).variant_name(), "Text");
+    assert_eq!(Message::<u8>::Move { x: 1, y: 2 }.variant_name(), "Move");
+    assert_eq!(Message::<u8>::Quit.variant_name(), "Quit");
+
+    assert!(Never::VARIANT_NAMES.is_empty());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "zeroize")]
+
+#[macro_use]
+extern crate derivative;
+extern crate zeroize;
+
+use zeroize::Zeroize;
+
+#[derive(Derivative)]
+#[derivative(Debug, Zeroize)]
+struct Credentials {
+    user: String,
+    #[derivative(Debug = "ignore")]
+    password: String,
+    #[derivative(Zeroize(zeroize_with = "reset_key"))]
+    key: Key,
+    #[derivative(Zeroize = "ignore")]
+    attempts: u32,
+}
+
+#[derive(Debug)]
+struct Key([u8; 4]);
+
+fn reset_key(key: &mut Key) {
+    key.0 = [0xff; 4];
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Zeroize)]
+enum Secret<T> {
+    Pin(u16),
+    Bytes { bytes: Vec<u8>, tag: T },
+    Unknown,
+}
+
+#[derive(Debug, Derivative)]
+#[derivative(Zeroize(bound = ""))]
+struct Wrapper<T> {
+    #[derivative(Zeroize = "ignore")]
+    inner: T,
+    len: usize,
+}
+
+struct NotZeroize;
+
+#[test]
+fn main() {
+    let mut credentials = Credentials {
+        user: "user".to_string(),
+        password: "hunter2".to_string(),
+        key: Key([1, 2, 3, 4]),
+        attempts: 3,
+    };
+    assert_eq!(
+        format!("{:?}", credentials),
+        "Credentials { user: \"user\", key: Key([1, 2, 3, 4]), attempts: 3 }"
+    );
+    credentials.zeroize();
+    assert_eq!(credentials.user, "");
+    assert_eq!(credentials.password, "");
+    assert_eq!(credentials.key.0, [0xff; 4]);
+    assert_eq!(credentials.attempts, 3);
+
+    let mut pin = Secret::<u8>::Pin(1234);
+    pin.zeroize();
+    assert!(matches!(pin, Secret::Pin(0)));
+
+    let mut bytes = Secret::Bytes {
+        bytes: vec![1, 2, 3],
+        tag: 7u8,
+    };
+    bytes.zeroize();
+    match bytes {
+        Secret::Bytes { bytes, tag } => {
+            assert!(bytes.is_empty());
+            assert_eq!(tag, 0);
+        }
+        _ => unreachable!(),
+    }
+
+    let mut unknown = Secret::<u8>::Unknown;
+    unknown.zeroize();
+    assert!(matches!(unknown, Secret::Unknown));
+
+    let mut wrapper = Wrapper {
+        inner: NotZeroize,
+        len: 12,
+    };
+    wrapper.zeroize();
+    assert_eq!(wrapper.len, 0);
+    let Wrapper { inner: NotZeroize, .. } = wrapper;
+}
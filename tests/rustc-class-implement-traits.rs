@@ -9,6 +9,9 @@
 // except according to those terms.
 
 #![allow(non_camel_case_types)]
+#![allow(clippy::manual_is_multiple_of)]
+#![allow(clippy::needless_return)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;
@@ -10,6 +10,8 @@
 
 // test for issue #30244
 
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
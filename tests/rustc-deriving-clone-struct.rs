@@ -10,6 +10,8 @@
 
 // pretty-expanded FIXME #23616
 
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -10,6 +10,9 @@
 
 // pretty-expanded FIXME #23616
 
+#![allow(dead_code)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -10,6 +10,8 @@
 
 // no-pretty-expanded FIXME #15189
 
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -11,6 +11,10 @@
 //! Test that #[derive(Copy, Clone)] produces a shallow copy
 //! even when a member violates RFC 1521
 
+#![allow(deprecated)]
+#![allow(clippy::clone_on_copy)]
+#![allow(clippy::non_canonical_clone_impl)]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
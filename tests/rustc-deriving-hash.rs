@@ -9,6 +9,8 @@
 // except according to those terms.
 
 #![allow(non_camel_case_types)]
+#![allow(clippy::inconsistent_digit_grouping)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;
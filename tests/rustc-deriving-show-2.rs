@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 #![deny(unused_variables)] // We used to generate warning: unused variable: `f`
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;
@@ -12,6 +12,10 @@
 // inference due to the requirement that `'a:b'`, which was getting
 // incorrectly translated in connection with the closure below.
 
+#![allow(clippy::only_used_in_recursion)]
+#![allow(clippy::redundant_closure)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
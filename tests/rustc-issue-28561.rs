@@ -8,6 +8,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(dead_code)]
+#![allow(unpredictable_function_pointer_comparisons)]
+#![allow(unused_parens)]
+#![allow(clippy::type_complexity)]
+#![allow(clippy::zero_prefixed_literal)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
+
 #[cfg(feature = "use_core")]
 extern crate core;
 
@@ -10,6 +10,7 @@
 
 #![deny(unused_results)]
 #![allow(dead_code)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 #![deny(warnings)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;
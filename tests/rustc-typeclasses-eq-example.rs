@@ -10,6 +10,9 @@
 
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
+#![allow(clippy::match_like_matches_macro)]
+#![allow(clippy::needless_borrowed_reference)]
+#![cfg_attr(feature = "lints", allow(deprecated))]
 
 #[cfg(feature = "use_core")]
 extern crate core;